@@ -6,6 +6,13 @@ pub fn build_app() -> App<'static, 'static> {
         .author(crate_authors!())
         .about(crate_description!())
         .arg(Arg::with_name("all").long("all").help("display all available information expect code disassembly"))
+        .arg(
+            Arg::with_name("apiset_schema")
+                .long("apiset-schema")
+                .takes_value(true)
+                .value_name("file")
+                .help("resolve API set contracts (api-ms-win-*) to their host DLLs with the apisetschema.dll"),
+        )
         .arg(Arg::with_name("archive_members").long("archive-members").help("display minimal information about member objects"))
         .arg(Arg::with_name("clr_header").long("clr-header").help("display CLR specific information"))
         .arg(Arg::with_name("clr_container").long("clr").help("display CLR specific information that contains metadata"))
@@ -13,10 +20,41 @@ pub fn build_app() -> App<'static, 'static> {
         .arg(Arg::with_name("dependents").long("dependents").help("display the names of the DLLs from which the image imports functions"))
         .arg(Arg::with_name("directives").long("directives").help("display the compiler-generated .directives section"))
         .arg(Arg::with_name("exports").long("exports").help("display all definitions that exported from the image"))
+        .arg(Arg::with_name("extract_icons").long("extract-icons").takes_value(true).value_name("dir").help("extract icons and cursors into the directory as .ico/.cur files"))
+        .arg(
+            Arg::with_name("extract_managed_resources")
+                .long("extract-managed-resources")
+                .takes_value(true)
+                .value_name("dir")
+                .help("extract the embedded .NET resources and the entries of .resources files into the directory"),
+        )
         .arg(Arg::with_name("fpo").long("fpo").help("display frame pointer optimization (FPO) records"))
         .arg(Arg::with_name("headers").long("headers").help("display the file header and the header for each sections"))
+        .arg(
+            Arg::with_name("il")
+                .long("il")
+                .takes_value(true)
+                .value_name("Type::Method")
+                .help("disassemble the IL of the .NET method, the type is either the full or the simple name"),
+        )
         .arg(Arg::with_name("imports").long("imports").help("display the list of DLLs that are imported to and all the imports from each DLLs"))
         .arg(Arg::with_name("load_config").long("load-config").help("display the dump of the loader configuration"))
+        .arg(Arg::with_name("manifest").long("manifest").help("display the embedded application manifest"))
+        .arg(Arg::with_name("relocations").long("relocations").help("display the base relocations"))
+        .arg(Arg::with_name("resources").long("resources").help("display the resource directory tree"))
+        .arg(
+            Arg::with_name("search_path")
+                .long("search-path")
+                .takes_value(true)
+                .value_name("dirs")
+                .multiple(true)
+                .number_of_values(1)
+                .use_delimiter(true)
+                .requires("dependents")
+                .help("resolve the dependents recursively against the DLLs in the comma separated directories"),
+        )
+        .arg(Arg::with_name("signature").long("signature").help("display the Authenticode signatures and their certificate chains"))
         .arg(Arg::with_name("tls").long("tls").help("display the dump of tls"))
+        .arg(Arg::with_name("version_info").long("version-info").help("display the version information resource"))
         .arg(Arg::with_name("path").required(true).takes_value(true))
 }
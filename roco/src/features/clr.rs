@@ -0,0 +1,346 @@
+use roki::clr::{
+    table_name, verify_strong_name, FieldSignature, GenericContext, ManagedResourceLocation, MetadataRoot, MetadataTables, MethodSignature, PropertySignature, SignatureFormatter, StrongNamePublicKey, Token,
+    TABLE_TYPE_DEF,
+};
+use roki::containers::ClrContainer;
+use roki::Executable;
+
+pub fn print(executable: &Executable) -> Result<(), failure::Error> {
+    let clr_data = match executable.com_descriptor_data() {
+        Some(clr_data) => clr_data,
+        None => {
+            println!("\nThe image is not a .NET assembly");
+            return Ok(());
+        }
+    };
+
+    print_assembly(clr_data);
+    print_strong_name(executable, clr_data);
+    print_managed_resources(clr_data);
+
+    let metadata_root = clr_data.metadata_root();
+
+    println!(
+        "
+METADATA ROOT
+    signature : {:#010X}
+    version   : {}.{}
+    runtime   : {}
+    flags     : {:#06X}",
+        metadata_root.signature(),
+        metadata_root.major_version(),
+        metadata_root.minor_version(),
+        metadata_root.version(),
+        metadata_root.flags()
+    );
+
+    println!("\n    STREAMS");
+    for stream in metadata_root.streams() {
+        println!("        {:<10} offset {:#010X} size {:#010X}", stream.name(), stream.header().offset(), stream.header().size());
+    }
+
+    let guids = metadata_root.guids();
+    if !guids.is_empty() {
+        println!("\n    GUIDS");
+        for i in 1..=guids.len() {
+            if let Some(guid) = guids.get(i as u32)? {
+                println!("        [{}] {}", i, guid);
+            }
+        }
+    }
+
+    match clr_data.metadata_tables() {
+        Ok(Some(tables)) => print_tables(metadata_root, tables),
+        Ok(None) => (),
+        Err(e) => println!("\nFailed to parse the metadata tables: {}", e),
+    }
+
+    Ok(())
+}
+
+fn print_tables(metadata_root: &MetadataRoot, tables: &MetadataTables) {
+    println!(
+        "
+METADATA TABLES
+    version    : {}.{}
+    heap sizes : {:#04X}
+    valid      : {:#018X}
+    sorted     : {:#018X}
+",
+        tables.major_version(),
+        tables.minor_version(),
+        tables.heap_sizes(),
+        tables.valid(),
+        tables.sorted()
+    );
+
+    println!("    {:<24} {:>8} {:>8}", "Table", "Rows", "Size");
+    for table in 0..64 {
+        if tables.valid() & (1 << table) == 0 {
+            continue;
+        }
+
+        let name = table_name(table).map_or_else(|| format!("({:#04X})", table), |w| w.to_owned());
+        println!("    {:<24} {:>8} {:>8}", name, tables.row_count(table), tables.row_size(table));
+    }
+
+    let type_defs = tables.type_defs();
+    if type_defs.is_empty() {
+        return;
+    }
+
+    let formatter = SignatureFormatter::new(metadata_root, tables);
+
+    println!("\n    TYPES");
+    for (i, type_def) in type_defs.iter().enumerate() {
+        let row = i as u32 + 1;
+        let context = GenericContext::new(Some(row), None);
+
+        let extends = if type_def.extends().is_null() { "-".to_owned() } else { formatter.type_name(type_def.extends(), context) };
+        println!(
+            "        {} {} (flags {:#010X}, extends {})",
+            Token::new(TABLE_TYPE_DEF, row),
+            formatter.type_name(Token::new(TABLE_TYPE_DEF, row), context),
+            type_def.flags(),
+            extends
+        );
+
+        print_members(metadata_root, tables, &formatter, row);
+    }
+}
+
+// ECMA-335 II.23.1.5 FieldAttributes and II.23.1.10 MethodAttributes share the access mask
+fn access(flags: u16) -> &'static str {
+    match flags & 0x0007 {
+        0x0001 => "private ",
+        0x0002 => "private protected ",
+        0x0003 => "internal ",
+        0x0004 => "protected ",
+        0x0005 => "protected internal ",
+        0x0006 => "public ",
+        _ => "",
+    }
+}
+
+fn print_members(metadata_root: &MetadataRoot, tables: &MetadataTables, formatter: &SignatureFormatter, type_def: u32) {
+    let blobs = metadata_root.blobs();
+    let context = GenericContext::new(Some(type_def), None);
+
+    for field in tables.fields_of(type_def).into_iter().filter_map(|w| tables.field(w)) {
+        let flags = field.flags();
+        let modifiers = match (flags & 0x0040 != 0, flags & 0x0010 != 0, flags & 0x0020 != 0) {
+            (true, _, _) => "const ",
+            (false, true, true) => "static readonly ",
+            (false, true, false) => "static ",
+            (false, false, true) => "readonly ",
+            _ => "",
+        };
+
+        let r#type = blobs.get(field.signature()).and_then(FieldSignature::parse).map(|w| formatter.format_type(w.r#type(), context));
+        match r#type {
+            Ok(r#type) => println!("            {}{}{} {};", access(flags), modifiers, r#type, field.name()),
+            Err(e) => println!("            {} /* invalid signature: {} */", field.name(), e),
+        }
+    }
+
+    for property in tables.properties_of(type_def) {
+        let r#type = blobs.get(property.signature()).and_then(PropertySignature::parse).map(|w| formatter.format_type(w.r#type(), context));
+        match r#type {
+            Ok(r#type) => println!("            {} {} {{ ... }}", r#type, property.name()),
+            Err(e) => println!("            {} /* invalid signature: {} */", property.name(), e),
+        }
+    }
+
+    for row in tables.methods_of(type_def) {
+        let method_def = match tables.method_def(row) {
+            Some(method_def) => method_def,
+            None => continue,
+        };
+
+        let flags = method_def.flags();
+        let modifiers = if flags & 0x0400 != 0 {
+            "abstract "
+        } else if flags & 0x0040 != 0 && flags & 0x0020 != 0 {
+            "sealed virtual "
+        } else if flags & 0x0040 != 0 {
+            "virtual "
+        } else {
+            ""
+        };
+
+        let params = tables.params_of(row);
+        let mut param_names: Vec<&str> = Vec::new();
+        for param in params.iter().filter(|w| w.sequence() > 0) {
+            let index = param.sequence() as usize - 1;
+            if param_names.len() <= index {
+                param_names.resize(index + 1, "");
+            }
+
+            param_names[index] = param.name();
+        }
+
+        let context = GenericContext::new(Some(type_def), Some(row));
+        let declaration = blobs
+            .get(method_def.signature())
+            .and_then(MethodSignature::parse)
+            .map(|w| formatter.format_method(method_def.name(), &w, &param_names, context));
+        match declaration {
+            Ok(declaration) => println!("            {}{}{};", access(flags), modifiers, declaration),
+            Err(e) => println!("            {} /* invalid signature: {} */", method_def.name(), e),
+        }
+    }
+}
+
+fn print_assembly(clr_data: &ClrContainer) {
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|w| format!("{:02x}", w)).collect()
+    }
+
+    // ALG_ID
+    fn hash_algorithm(hash_alg_id: u32) -> &'static str {
+        match hash_alg_id {
+            0x0000 => "none",
+            0x8003 => "MD5",
+            0x8004 => "SHA1",
+            0x800C => "SHA256",
+            0x800D => "SHA384",
+            0x800E => "SHA512",
+            _ => "unknown",
+        }
+    }
+
+    match clr_data.assembly() {
+        Ok(Some(assembly)) => println!(
+            "
+ASSEMBLY
+    name             : {}
+    version          : {}
+    culture          : {}
+    flags            : {:#010X}
+    hash algorithm   : {} ({:#06X})
+    public key       : {}
+    public key token : {}
+    display name     : {}",
+            assembly.name(),
+            assembly.version(),
+            if assembly.culture().is_empty() { "neutral" } else { assembly.culture() },
+            assembly.flags(),
+            hash_algorithm(assembly.hash_alg_id()),
+            assembly.hash_alg_id(),
+            if assembly.public_key().is_empty() { "-".to_owned() } else { hex(assembly.public_key()) },
+            assembly.public_key_token().map_or("-".to_owned(), |w| hex(&w)),
+            assembly.display_name()
+        ),
+        Ok(None) => (),
+        Err(e) => println!("\nFailed to parse the assembly: {}", e),
+    }
+
+    let assembly_refs = match clr_data.assembly_refs() {
+        Ok(assembly_refs) if !assembly_refs.is_empty() => assembly_refs,
+        Ok(_) => return,
+        Err(e) => {
+            println!("\nFailed to parse the assembly references: {}", e);
+            return;
+        }
+    };
+
+    println!("\nASSEMBLY REFERENCES");
+    for assembly_ref in assembly_refs {
+        let retargetable = if assembly_ref.is_retargetable() { ", Retargetable=Yes" } else { "" };
+        println!("    {}{}", assembly_ref.display_name(), retargetable);
+    }
+}
+
+fn print_strong_name(executable: &Executable, clr_data: &ClrContainer) {
+    let signature = match clr_data.strong_name_signature() {
+        Ok(Some(signature)) => signature,
+        Ok(None) => return,
+        Err(e) => {
+            println!("\nFailed to parse the strong name signature: {}", e);
+            return;
+        }
+    };
+
+    println!(
+        "
+STRONG NAME
+    signature rva/size : {:#010X} / {:#010X}",
+        signature.rva(),
+        signature.size()
+    );
+
+    if signature.is_delay_signed() {
+        println!("    status             : delay signed, the signature was never computed");
+        return;
+    }
+
+    if signature.is_ecma_key() {
+        println!("    status             : signed with the ECMA key, not verifiable without the runtime key");
+        return;
+    }
+
+    if signature.has_signature_key() {
+        println!("    status             : signed with the key of AssemblySignatureKeyAttribute, not verifiable with the assembly public key");
+        return;
+    }
+
+    if let Ok(public_key) = StrongNamePublicKey::parse(signature.public_key()) {
+        println!("    key                : RSA {}-bit, exponent {}", public_key.bit_length(), public_key.exponent());
+    }
+
+    match verify_strong_name(executable, signature) {
+        Ok(verification) => {
+            let hash = verification.computed().iter().map(|w| format!("{:02x}", w)).collect::<String>();
+            println!("    hash               : {} ({})", hash, verification.algorithm());
+
+            let status = match verification.signed() {
+                _ if verification.is_valid() => "valid",
+                Some(_) => "INVALID, the image was modified after it was signed",
+                None => "INVALID, the signature was not made with the public key of the assembly",
+            };
+            println!("    status             : {}", status);
+        }
+        Err(e) => println!("    status             : {}", e),
+    }
+}
+
+fn print_managed_resources(clr_data: &ClrContainer) {
+    let resources = match clr_data.managed_resources() {
+        Ok(resources) if !resources.is_empty() => resources,
+        Ok(_) => return,
+        Err(e) => {
+            println!("\nFailed to parse the managed resources: {}", e);
+            return;
+        }
+    };
+
+    println!("\nMANAGED RESOURCES");
+    for resource in resources {
+        let visibility = if resource.is_public() {
+            "public"
+        } else if resource.is_private() {
+            "private"
+        } else {
+            "unknown"
+        };
+
+        let location = match resource.location() {
+            ManagedResourceLocation::Embedded => format!("embedded at {:#010X}, {} bytes", resource.offset(), resource.size()),
+            ManagedResourceLocation::File(token) => format!("in the file {}", token),
+            ManagedResourceLocation::Assembly(token) => format!("in the assembly {}", token),
+        };
+
+        println!("    {} ({}, {})", resource.name(), visibility, location);
+
+        if let Some(resource_set) = resource.resource_set() {
+            match resource_set {
+                Ok(resource_set) => {
+                    for entry in resource_set.entries() {
+                        println!("        {} : {}", entry.name(), entry.type_name());
+                    }
+                }
+                Err(e) => println!("        Failed to parse the .resources: {}", e),
+            }
+        }
+    }
+}
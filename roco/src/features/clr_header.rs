@@ -1,28 +1,25 @@
+use roki::clr::{ready_to_run_machine, NGenHeader, NativeHeader, ReadyToRunHeader, READYTORUN_FLAGS};
+use roki::headers::{ClrEntryPoint, COMIMAGE_FLAGS};
 use roki::Executable;
 
 pub fn print(executable: &Executable) -> () {
   let clr_container = match executable.com_descriptor_data() {
     Some(com_descriptor_data) => com_descriptor_data,
-    None => return,
+    None => {
+      // composite images have no CLR header but still carry a ReadyToRun header
+      match ReadyToRunHeader::parse_composite(executable) {
+        Ok(Some(header)) => print_ready_to_run(executable, &header, None),
+        Ok(None) => (),
+        Err(e) => println!("\nFailed to parse the ReadyToRun header: {}", e),
+      }
+      return;
+    }
   };
 
   println!("\nCOM DESCRIPTOR (CLR) HEADER VALUES");
 
   let clr_header = clr_container.cor20_header();
 
-  fn add_if_includes(flags: u32, flag: u32, vector: &mut Vec<String>, text: &str) -> () {
-    if flags & flag == flag {
-      vector.push(text.to_owned());
-    }
-  }
-
-  let mut flags: Vec<String> = Vec::new();
-  add_if_includes(clr_header.flags(), 0x00000001, &mut flags, "COMIMAGE_FLAGS_ILONLY");
-  add_if_includes(clr_header.flags(), 0x00000002, &mut flags, "COMIMAGE_FLAGS_32BITREQUIRED");
-  add_if_includes(clr_header.flags(), 0x00000008, &mut flags, "COMIMAGE_FLAGS_STRONGNAMESIGNED");
-  add_if_includes(clr_header.flags(), 0x00000010, &mut flags, "COMIMAGE_FLAGS_NATIVE_ENTRYPOINT");
-  add_if_includes(clr_header.flags(), 0x00010000, &mut flags, "COMIMAGE_FLAGS_TRACKDEBUGDATA");
-
   println!(
     "    cb                                  : {:#010X}
     runtime version                     : {}.{}
@@ -37,20 +34,35 @@ pub fn print(executable: &Executable) -> () {
     clr_header.flags()
   );
 
-  for flag in flags {
-    println!("        {}", flag);
+  for (flag, name) in COMIMAGE_FLAGS.iter() {
+    if clr_header.flags() & flag != 0 {
+      println!("        {}", name);
+    }
   }
 
+  let entry_point = match clr_header.entry_point() {
+    ClrEntryPoint::None => "-".to_owned(),
+    ClrEntryPoint::Token(token) => format!("{:#010X} (token)", token),
+    ClrEntryPoint::NativeRva(rva) => format!("{:#010X} (native rva)", rva),
+  };
+
+  println!(
+    "    platform target                     : {}
+    mixed mode                          : {}",
+    clr_container.platform_target().name(),
+    clr_header.is_mixed_mode()
+  );
+
   println!(
-    "    entry point rva                     : {:#010X}
+    "    entry point                         : {}
     resources rva/size                  : {:#010X} / {:#010X}
     strong name signature rva/size      : {:#010X} / {:#010X}
     code manager table rva/size         : {:#010X} / {:#010X}
     v table fixups rva/size             : {:#010X} / {:#010X}
     export address table jumps rva/size : {:#010X} / {:#010X}
-    managed native header rva/size      : {:#010X} / {:#010X}
-    ",
-    clr_header.entry_point_rva(),
+    managed native header rva/size      : {:#010X} / {:#010X}\
+  ",
+    entry_point,
     clr_header.resources().virtual_address(),
     clr_header.resources().size(),
     clr_header.strong_name_signature().virtual_address(),
@@ -64,4 +76,102 @@ pub fn print(executable: &Executable) -> () {
     clr_header.managed_native_header().virtual_address(),
     clr_header.managed_native_header().size()
   );
+
+  let native_header = match clr_container.native_header() {
+    Ok(Some(native_header)) => native_header,
+    Ok(None) => {
+      println!("    precompiled                         : no (IL only)\n");
+      return;
+    }
+    Err(e) => {
+      println!("    precompiled                         : {}\n", e);
+      return;
+    }
+  };
+
+  println!("    precompiled                         : yes ({})", native_header.name());
+
+  let method_count = clr_container.metadata_tables().ok().flatten().map(|w| w.method_defs().len());
+  match native_header {
+    NativeHeader::ReadyToRun(header) => print_ready_to_run(executable, header, method_count),
+    NativeHeader::NGen(header) => print_ngen(header),
+    // the layout is not known, only the directory of the CLR header is shown
+    NativeHeader::Unknown(_) => println!(),
+  }
+}
+
+fn print_ready_to_run(executable: &Executable, header: &ReadyToRunHeader, method_count: Option<usize>) {
+  let machine = executable.file_header().map_or(0, |w| w.machine());
+  let (_, target_os) = ready_to_run_machine(machine);
+
+  println!("\nREADYTORUN HEADER");
+  println!(
+    "    rva/size                            : {:#010X} / {:#010X}
+    version                             : {}.{}
+    target os                           : {}
+    compiler                            : {}
+    flags                               : {:#010X}\
+  ",
+    header.rva(),
+    header.size(),
+    header.major_version(),
+    header.minor_version(),
+    target_os.name(),
+    header.compiler_identifier().unwrap_or("-"),
+    header.flags()
+  );
+
+  for (flag, name) in READYTORUN_FLAGS.iter() {
+    if header.flags() & flag != 0 {
+      println!("        {}", name);
+    }
+  }
+
+  let image_kind = if header.is_composite() {
+    format!("composite of {} component assemblies", header.component_assemblies().len())
+  } else if header.is_component() {
+    format!("component of {}", header.owner_composite_executable().unwrap_or("an unknown composite image"))
+  } else {
+    "standalone".to_owned()
+  };
+
+  let precompiled_methods = match method_count {
+    Some(method_count) => format!("{} of {}", header.method_entry_points().len(), method_count),
+    None => header.method_entry_points().len().to_string(),
+  };
+
+  let import_cells: u64 = header.import_sections().iter().map(|w| w.entry_count() as u64).sum();
+
+  println!(
+    "    image                               : {}
+    import sections                     : {} ({} cells)
+    runtime functions                   : {}
+    precompiled methods                 : {}
+    available types                     : {}",
+    image_kind,
+    header.import_sections().len(),
+    import_cells,
+    header.runtime_functions().len(),
+    precompiled_methods,
+    header.available_types().len()
+  );
+
+  println!("\n    SECTIONS");
+  for section in header.sections() {
+    let name = section.name().map_or_else(|| format!("({})", section.section_type()), |w| w.to_owned());
+    println!("        {:<28} rva {:#010X} size {:#010X}", name, section.section().virtual_address(), section.section().size());
+  }
+}
+
+fn print_ngen(header: &NGenHeader) {
+  println!("\nNGEN HEADER");
+  println!("    rva/size                            : {:#010X} / {:#010X}", header.rva(), header.size());
+
+  for (name, directory) in header.directories() {
+    if directory.virtual_address() == 0 {
+      continue;
+    }
+
+    println!("    {:<35} : {:#010X} / {:#010X}", format!("{} rva/size", name), directory.virtual_address(), directory.size());
+  }
 }
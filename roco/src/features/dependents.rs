@@ -0,0 +1,305 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use roki::api_set::{is_api_set_name, ApiSetSchema};
+use roki::containers::{verify_binding, BindingIssue};
+use roki::Executable;
+
+// guards against pathological import chains, real-world trees are far shallower
+const MAX_DEPTH: usize = 32;
+
+#[derive(Clone)]
+enum Symbol {
+    Name(String),
+    Ordinal(u16),
+}
+
+impl Symbol {
+    fn name(&self) -> String {
+        match self {
+            Symbol::Name(name) => name.to_owned(),
+            Symbol::Ordinal(ordinal) => format!("(Ordinal {})", ordinal),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Dependent {
+    name: String,
+    is_delay: bool,
+    symbols: Vec<Symbol>,
+}
+
+struct Module {
+    path: PathBuf,
+    time_date_stamp: u32,
+    dependents: Vec<Dependent>,
+    names: HashSet<String>,
+    ordinals: HashSet<u32>,
+}
+
+impl Module {
+    fn exports(&self, symbol: &Symbol) -> bool {
+        match symbol {
+            Symbol::Name(name) => self.names.contains(name),
+            Symbol::Ordinal(ordinal) => self.ordinals.contains(&(*ordinal as u32)),
+        }
+    }
+}
+
+struct Resolver<'a> {
+    files: HashMap<String, PathBuf>,
+    modules: HashMap<String, Result<Module, String>>,
+    expanded: HashSet<String>,
+    api_set_schema: Option<&'a ApiSetSchema>,
+}
+
+impl<'a> Resolver<'a> {
+    fn new(files: HashMap<String, PathBuf>, api_set_schema: Option<&'a ApiSetSchema>) -> Self {
+        Resolver {
+            files,
+            modules: HashMap::new(),
+            expanded: HashSet::new(),
+            api_set_schema,
+        }
+    }
+
+    // parses the DLL once, nothing is cached if it is not found in any search path
+    fn load(&mut self, key: &str) {
+        if self.modules.contains_key(key) {
+            return;
+        }
+
+        if let Some(path) = self.files.get(key) {
+            let module = load_module(path).map_err(|e| e.to_string());
+            self.modules.insert(key.to_owned(), module);
+        }
+    }
+
+    fn time_date_stamp(&mut self, name: &str) -> Option<u32> {
+        let key = name.to_ascii_lowercase();
+        self.load(&key);
+
+        match self.modules.get(&key) {
+            Some(Ok(module)) => Some(module.time_date_stamp),
+            _ => None,
+        }
+    }
+
+    // `importer` is the file name of the module that imports the dependents
+    fn print_tree(&mut self, importer: &str, dependents: &[Dependent], depth: usize, ancestors: &mut Vec<String>) {
+        let indent = "    ".repeat(depth + 1);
+
+        for dependent in dependents {
+            let kind = if dependent.is_delay { " (delay)" } else { "" };
+
+            // API set contracts are redirected to the host DLL
+            let (label, host) = if is_api_set_name(&dependent.name) {
+                match self.api_set_schema.map(|w| w.resolve(&dependent.name, Some(importer))) {
+                    Some(Some(host)) => (format!("{}{} -> {}", dependent.name, kind, host), host.to_owned()),
+                    Some(None) => {
+                        println!("{}{}{} : [no host in the API set schema]", indent, dependent.name, kind);
+                        continue;
+                    }
+                    None => {
+                        println!("{}{}{} : [API set, no schema to resolve]", indent, dependent.name, kind);
+                        continue;
+                    }
+                }
+            } else {
+                (format!("{}{}", dependent.name, kind), dependent.name.to_owned())
+            };
+
+            let key = host.to_ascii_lowercase();
+            self.load(&key);
+            let module = match self.modules.get(&key) {
+                Some(Ok(module)) => module,
+                Some(Err(e)) => {
+                    println!("{}{} : [failed to parse: {}]", indent, label, e);
+                    continue;
+                }
+                None => {
+                    println!("{}{} : [missing]", indent, label);
+                    continue;
+                }
+            };
+
+            println!("{}{} : {}", indent, label, module.path.display());
+
+            for symbol in dependent.symbols.iter().filter(|w| !module.exports(w)) {
+                println!("{}    ! unresolved symbol {}", indent, symbol.name());
+            }
+
+            if ancestors.contains(&key) {
+                println!("{}    (cyclic dependency)", indent);
+                continue;
+            }
+
+            if self.expanded.contains(&key) {
+                if !module.dependents.is_empty() {
+                    println!("{}    (see above)", indent);
+                }
+                continue;
+            }
+
+            if depth + 1 >= MAX_DEPTH {
+                println!("{}    (too deep)", indent);
+                continue;
+            }
+
+            // the module is owned by the cache, so take a copy to recurse
+            let children = module.dependents.to_vec();
+
+            self.expanded.insert(key.to_owned());
+            ancestors.push(key);
+            self.print_tree(&host, &children, depth + 1, ancestors);
+            ancestors.pop();
+        }
+    }
+}
+
+// lowercase file name to path, the first search path wins
+fn list_files(search_paths: &[&Path]) -> Result<HashMap<String, PathBuf>, failure::Error> {
+    let mut files: HashMap<String, PathBuf> = HashMap::new();
+
+    for search_path in search_paths {
+        let entries = fs::read_dir(search_path).map_err(|_| {
+            let msg = format!("Failed to read the search path `{}`", search_path.display());
+            failure::err_msg(msg)
+        })?;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+
+            if let Some(name) = path.file_name().and_then(|w| w.to_str()) {
+                files.entry(name.to_ascii_lowercase()).or_insert(path);
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+pub fn load_api_set_schema(path: &Path) -> Result<ApiSetSchema, failure::Error> {
+    let mut executable = Executable::new(path)?;
+    executable.parse()?;
+
+    ApiSetSchema::parse(&executable)
+}
+
+// the resources, the signature and the other directories are not needed to follow the imports
+fn load_module(path: &Path) -> Result<Module, failure::Error> {
+    let mut executable = Executable::new(path)?;
+    executable.parse_dependencies()?;
+
+    let mut names: HashSet<String> = HashSet::new();
+    let mut ordinals: HashSet<u32> = HashSet::new();
+
+    if let Some(functions) = executable.export_data().and_then(|w| w.functions()) {
+        for function in functions.iter().filter(|w| w.function() != 0) {
+            names.insert(function.name().to_owned());
+            ordinals.insert(function.ordinal());
+        }
+    }
+
+    Ok(Module {
+        path: path.to_owned(),
+        time_date_stamp: executable.file_header().map_or(0, |w| w.time_date_stamps()),
+        dependents: dependents_of(&executable),
+        names,
+        ordinals,
+    })
+}
+
+fn dependents_of(executable: &Executable) -> Vec<Dependent> {
+    let mut dependents: Vec<Dependent> = Vec::new();
+
+    if let Some(import_data) = executable.import_data() {
+        for descriptor in import_data.descriptors() {
+            let symbols = descriptor
+                .functions()
+                .iter()
+                .map(|w| match w.ordinal() {
+                    Some(ordinal) => Symbol::Ordinal(ordinal),
+                    None => Symbol::Name(w.name()),
+                })
+                .collect();
+
+            dependents.push(Dependent {
+                name: descriptor.name(),
+                is_delay: false,
+                symbols,
+            });
+        }
+    }
+
+    if let Some(delay_import_data) = executable.delay_import_data() {
+        for descriptor in delay_import_data.descriptors() {
+            let symbols = descriptor
+                .functions()
+                .iter()
+                .map(|w| match w.ordinal() {
+                    Some(ordinal) => Symbol::Ordinal(ordinal),
+                    None => Symbol::Name(w.name()),
+                })
+                .collect();
+
+            dependents.push(Dependent {
+                name: descriptor.name(),
+                is_delay: true,
+                symbols,
+            });
+        }
+    }
+
+    dependents
+}
+
+pub fn print(executable: &Executable, search_paths: &[&Path], api_set_schema: Option<&ApiSetSchema>) -> Result<(), failure::Error> {
+    let dependents = dependents_of(executable);
+    let importer = Path::new(executable.path()).file_name().and_then(|w| w.to_str()).unwrap_or_default();
+
+    println!("\nDEPENDENTS");
+
+    for dependent in dependents.iter() {
+        let kind = if dependent.is_delay { " (delay)" } else { "" };
+        match api_set_schema.and_then(|w| w.resolve(&dependent.name, Some(importer))) {
+            Some(host) => println!("    {}{} -> {}", dependent.name, kind, host),
+            None => println!("    {}{}", dependent.name, kind),
+        }
+    }
+
+    if search_paths.is_empty() {
+        return Ok(());
+    }
+
+    // fall back to the schema in the search paths, e.g. system32
+    let files = list_files(search_paths)?;
+    let fallback = match (api_set_schema, files.get("apisetschema.dll")) {
+        (None, Some(path)) => Some(load_api_set_schema(path)?),
+        _ => None,
+    };
+
+    let mut resolver = Resolver::new(files, api_set_schema.or(fallback.as_ref()));
+
+    println!("\nDEPENDENCY TREE");
+    resolver.print_tree(importer, &dependents, 0, &mut Vec::new());
+
+    // the bound addresses are only used if the DLLs found are the ones the image was bound against
+    let stale_bindings: Vec<BindingIssue> = verify_binding(executable, |w| resolver.time_date_stamp(w))
+        .into_iter()
+        .filter(|w| matches!(w, BindingIssue::StaleBinding { .. }))
+        .collect();
+    if !stale_bindings.is_empty() {
+        println!("\nSTALE BINDINGS");
+        for issue in stale_bindings {
+            println!("    {}", issue);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use std::fs;
+use std::path::Path;
+
+use roki::resources::IconKind;
+use roki::Executable;
+
+pub fn extract(executable: &Executable, directory: &Path) -> Result<(), failure::Error> {
+    let resource_data = match executable.resource_data() {
+        Some(resource_data) => resource_data,
+        None => return Ok(()),
+    };
+
+    let mut groups = resource_data.icon_groups()?;
+    groups.append(&mut resource_data.cursor_groups()?);
+
+    if groups.is_empty() {
+        return Ok(());
+    }
+
+    fs::create_dir_all(directory)?;
+
+    println!("\nICONS");
+
+    for group in groups {
+        // resource names may contain characters that are not allowed in file names
+        let name = match group.name().id() {
+            Some(id) => id.to_string(),
+            None => group.name().to_string().chars().map(|w| if w.is_ascii_alphanumeric() || w == '-' { w } else { '_' }).collect::<String>(),
+        };
+        let prefix = match group.kind() {
+            IconKind::Icon => "icon",
+            IconKind::Cursor => "cursor",
+        };
+        let path = directory.join(format!("{}_{}_{}.{}", prefix, name, group.language(), group.extension()));
+
+        fs::write(&path, group.file())?;
+
+        let sizes = group.entries().iter().map(|w| format!("{}x{}", w.width(), w.height())).collect::<Vec<String>>();
+        println!("    {} ({})", path.display(), sizes.join(", "));
+    }
+
+    Ok(())
+}
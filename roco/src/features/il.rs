@@ -0,0 +1,168 @@
+use roki::clr::{disassemble, ExceptionClauseKind, GenericContext, MetadataRoot, MetadataTables, MethodBody, MethodSignature, NativeHeader, Operand, SignatureFormatter, Token, TABLE_METHOD_DEF, TABLE_TYPE_DEF};
+use roki::Executable;
+
+// `name` is Type::Method, the type is matched by its full name or by its simple name
+pub fn print(executable: &Executable, name: &str) -> Result<(), failure::Error> {
+    let (type_name, method_name) = match name.rfind("::") {
+        Some(position) => (&name[..position], &name[position + 2..]),
+        None => {
+            let msg = format!("`{}` is not in the form of Type::Method", name);
+            return Err(failure::err_msg(msg));
+        }
+    };
+
+    let clr_data = match executable.com_descriptor_data() {
+        Some(clr_data) => clr_data,
+        None => {
+            println!("\nThe image is not a .NET assembly");
+            return Ok(());
+        }
+    };
+
+    let tables = match clr_data.metadata_tables()? {
+        Some(tables) => tables,
+        None => {
+            println!("\nThe image has no metadata tables");
+            return Ok(());
+        }
+    };
+
+    let metadata_root = clr_data.metadata_root();
+    let formatter = SignatureFormatter::new(metadata_root, tables);
+
+    let mut found = false;
+    for (i, type_def) in tables.type_defs().iter().enumerate() {
+        let type_def_row = i as u32 + 1;
+        let full_name = formatter.type_name(Token::new(TABLE_TYPE_DEF, type_def_row), GenericContext::new(Some(type_def_row), None));
+        if full_name != type_name && type_def.type_name() != type_name {
+            continue;
+        }
+
+        for row in tables.methods_of(type_def_row) {
+            let method_def = match tables.method_def(row) {
+                Some(method_def) if method_def.name() == method_name => method_def,
+                _ => continue,
+            };
+
+            found = true;
+
+            let context = GenericContext::new(Some(type_def_row), Some(row));
+            let blobs = metadata_root.blobs();
+            let declaration = blobs
+                .get(method_def.signature())
+                .and_then(MethodSignature::parse)
+                .map(|w| formatter.format_method(method_def.name(), &w, &[], context));
+
+            println!(
+                "
+METHOD {}::{} ({})
+    declaration : {}
+    rva         : {:#010X}
+    impl flags  : {:#06X}",
+                full_name,
+                method_def.name(),
+                Token::new(TABLE_METHOD_DEF, row),
+                declaration.unwrap_or_else(|e| format!("invalid signature: {}", e)),
+                method_def.rva(),
+                method_def.impl_flags()
+            );
+
+            if let Ok(Some(NativeHeader::ReadyToRun(header))) = clr_data.native_header() {
+                match header.method_entry_point(row) {
+                    Some(rva) => println!("    native code : {:#010X} (ReadyToRun)", rva),
+                    None => println!("    native code : - (jitted)"),
+                }
+            }
+
+            // abstract, runtime implemented and P/Invoke methods have no body
+            if method_def.rva() == 0 {
+                println!("\n    The method has no IL body");
+                continue;
+            }
+
+            let body = match MethodBody::parse(executable, method_def.rva()) {
+                Ok(body) => body,
+                Err(e) => {
+                    println!("\n    Failed to parse the method body: {}", e);
+                    continue;
+                }
+            };
+
+            print_body(metadata_root, tables, &formatter, &body, context);
+        }
+    }
+
+    if !found {
+        println!("\nNo method named `{}` was found", name);
+    }
+
+    Ok(())
+}
+
+fn print_body(metadata_root: &MetadataRoot, tables: &MetadataTables, formatter: &SignatureFormatter, body: &MethodBody, context: GenericContext) {
+    println!(
+        "    header      : {} ({} bytes)
+    max stack   : {}
+    code size   : {:#X}
+    init locals : {}",
+        if body.is_fat() { "fat" } else { "tiny" },
+        body.header_size(),
+        body.max_stack(),
+        body.code_size(),
+        body.init_locals()
+    );
+
+    match body.locals(metadata_root, tables) {
+        Ok(Some(locals)) => {
+            println!("\n    LOCALS ({})", body.local_var_sig_token());
+            for (i, local) in locals.locals().iter().enumerate() {
+                println!("        [{}] {}", i, formatter.format_type(local, context));
+            }
+        }
+        Ok(None) => {}
+        Err(e) => println!("\n    Failed to decode the locals: {}", e),
+    }
+
+    let exception_clauses = body.exception_clauses();
+    if !exception_clauses.is_empty() {
+        println!("\n    EXCEPTION CLAUSES");
+        for clause in exception_clauses {
+            let handler = match clause.kind() {
+                ExceptionClauseKind::Catch => format!("catch {}", clause.class_token().map_or("-".to_owned(), |w| formatter.format_token(w, context))),
+                ExceptionClauseKind::Filter => format!("filter IL_{:04X}", clause.filter_offset().unwrap_or_default()),
+                ExceptionClauseKind::Finally => "finally".to_owned(),
+                ExceptionClauseKind::Fault => "fault".to_owned(),
+                ExceptionClauseKind::Unknown => format!("unknown ({:#X})", clause.flags()),
+            };
+
+            println!(
+                "        try IL_{:04X} to IL_{:04X} {} IL_{:04X} to IL_{:04X}",
+                clause.try_offset(),
+                clause.try_offset().wrapping_add(clause.try_length()),
+                handler,
+                clause.handler_offset(),
+                clause.handler_offset().wrapping_add(clause.handler_length())
+            );
+        }
+    }
+
+    println!("\n    CODE");
+    for instruction in disassemble(body.code()) {
+        let operand = match instruction.operand() {
+            Operand::None => String::new(),
+            Operand::Int8(value) => value.to_string(),
+            Operand::Int32(value) => value.to_string(),
+            Operand::Int64(value) => value.to_string(),
+            Operand::Float32(value) => value.to_string(),
+            Operand::Float64(value) => value.to_string(),
+            Operand::Variable(index) => index.to_string(),
+            Operand::Branch(target) => format!("IL_{:04X}", target),
+            Operand::Switch(targets) => format!("({})", targets.iter().map(|w| format!("IL_{:04X}", w)).collect::<Vec<String>>().join(", ")),
+            Operand::Token(token) => formatter.format_token(*token, context),
+            Operand::String(token) => formatter.format_user_string(*token),
+        };
+
+        let line = format!("        IL_{:04X}: {:<12} {}", instruction.offset(), instruction.name(), operand);
+        println!("{}", line.trim_end());
+    }
+}
@@ -1,9 +1,23 @@
 use std::iter::repeat;
 
-use roki::containers::ImportDescriptor;
+use std::path::Path;
+
+use roki::api_set::ApiSetSchema;
+use roki::clr::{GenericContext, PInvokeCallingConvention, PInvokeCharSet, SignatureFormatter, Token, TABLE_METHOD_DEF, TABLE_TYPE_DEF};
+use roki::containers::{verify_binding, ImportDescriptor};
 use roki::Executable;
 
-pub fn print(executable: &Executable) -> () {
+pub fn print(executable: &Executable, api_set_schema: Option<&ApiSetSchema>) {
+    print_import_descriptors(executable, api_set_schema);
+    print_bound_imports(executable);
+    print_binding_issues(executable);
+    print_delay_import_descriptors(executable);
+    print_pinvoke_imports(executable);
+}
+
+fn print_import_descriptors(executable: &Executable, api_set_schema: Option<&ApiSetSchema>) {
+    let importer = Path::new(executable.path()).file_name().and_then(|w| w.to_str());
+
     let import_data = match executable.import_data() {
         Some(import_data) => import_data,
         None => return,
@@ -17,14 +31,25 @@ pub fn print(executable: &Executable) -> () {
         println!(
             "
 IMPORT DESCRIPTOR #{}
-    time date stamps : {:#010X}
-    name             : {}
-",
+    time date stamps : {:#010X}",
             i + 1,
-            descriptor.time_date_stamp(),
-            descriptor.name()
+            descriptor.time_date_stamp()
         );
 
+        if descriptor.is_bound() {
+            let binding = if descriptor.is_new_style_bound() { "new style" } else { "old style" };
+            match descriptor.bound_time_date_stamp() {
+                Some(time_date_stamp) => println!("    bound to         : {:#010X} ({})", time_date_stamp, binding),
+                None => println!("    bound to         : - ({}, missing in the bound import table)", binding),
+            }
+        }
+
+        println!("    name             : {}", descriptor.name());
+        if let Some(host) = api_set_schema.and_then(|w| w.resolve(&descriptor.name(), importer)) {
+            println!("    host             : {}", host);
+        }
+        println!();
+
         let functions = descriptor.functions();
         let the_longest_function_length = functions.iter().max_by_key(|w| w.name().len()).unwrap().name().len();
 
@@ -51,3 +76,171 @@ IMPORT DESCRIPTOR #{}
         }
     }
 }
+
+fn print_bound_imports(executable: &Executable) {
+    let bound_import_data = match executable.bound_import_data() {
+        Some(bound_import_data) => bound_import_data,
+        None => return,
+    };
+
+    println!("\nBOUND IMPORTS");
+
+    for import in bound_import_data.imports() {
+        println!("    {:#010X} {}", import.time_date_stamp(), import.name());
+
+        for forwarder in import.forwarders() {
+            println!("        {:#010X} {} (forwarder)", forwarder.time_date_stamp(), forwarder.name());
+        }
+    }
+}
+
+// the DLLs are not loaded here, stale bindings are reported by --dependents with search paths
+fn print_binding_issues(executable: &Executable) {
+    let issues = verify_binding(executable, |_| None);
+    if issues.is_empty() {
+        return;
+    }
+
+    println!("\nBINDING ISSUES");
+    for issue in issues {
+        println!("    {}", issue);
+    }
+}
+
+fn print_delay_import_descriptors(executable: &Executable) {
+    let delay_import_data = match executable.delay_import_data() {
+        Some(delay_import_data) => delay_import_data,
+        None => return,
+    };
+
+    let address_width = if executable.optional_header().unwrap().is_pe32_plus() { 18 } else { 10 };
+
+    for (i, descriptor) in delay_import_data.descriptors().iter().enumerate() {
+        println!(
+            "
+DELAY IMPORT DESCRIPTOR #{}
+    attributes                 : {:#010X} ({})
+    name                       : {}
+    module handle              : {:#010X}
+    import address table       : {:#010X}
+    import name table          : {:#010X}
+    bound import address table : {:#010X}
+    unload information table   : {:#010X}
+    time date stamps           : {:#010X}
+",
+            i + 1,
+            descriptor.descriptor().attributes(),
+            if descriptor.is_rva_based() { "RVA based" } else { "VA based" },
+            descriptor.name(),
+            descriptor.module_handle(),
+            descriptor.import_address_table(),
+            descriptor.import_name_table(),
+            descriptor.bound_import_address_table(),
+            descriptor.unload_information_table(),
+            descriptor.time_date_stamp()
+        );
+
+        let functions = descriptor.functions();
+        let the_longest_function_length = functions.iter().map(|w| w.name().len()).max().unwrap_or(0).max(4);
+        let separator = format!("    +-{}-+--------+------------+-{}-+", "-".repeat(the_longest_function_length), "-".repeat(address_width));
+
+        println!("    DELAY IMPORT FUNCTIONS");
+        println!("{}", separator);
+        println!(
+            "    | {:<width$} | Hint   | Thunk      | {:<address_width$} |",
+            "Name",
+            "Address",
+            width = the_longest_function_length,
+            address_width = address_width
+        );
+        println!("{}", separator);
+
+        for function in functions {
+            let hint = match function.hint() {
+                Some(hint) => format!("{:#06X}", hint),
+                None => "".to_owned(),
+            };
+
+            println!(
+                "    | {:<width$} | {:<6} | {:#010X} | {:#0address_width$X} |",
+                function.name(),
+                hint,
+                function.thunk_rva(),
+                function.address(),
+                width = the_longest_function_length,
+                address_width = address_width
+            );
+            println!("{}", separator);
+        }
+    }
+}
+
+// .NET images import native functions through P/Invoke rather than the import directory
+fn print_pinvoke_imports(executable: &Executable) {
+    let clr_data = match executable.com_descriptor_data() {
+        Some(clr_data) => clr_data,
+        None => return,
+    };
+
+    let imports = match clr_data.pinvoke_imports() {
+        Ok(imports) => imports,
+        Err(e) => {
+            println!("\nFailed to parse the P/Invoke imports: {}", e);
+            return;
+        }
+    };
+    let tables = match clr_data.metadata_tables() {
+        Ok(Some(tables)) if !imports.is_empty() => tables,
+        _ => return,
+    };
+
+    let formatter = SignatureFormatter::new(clr_data.metadata_root(), tables);
+
+    let mut modules: Vec<&str> = imports.iter().map(|w| w.module()).collect();
+    modules.sort_by_key(|w| w.to_lowercase());
+    modules.dedup_by_key(|w| w.to_lowercase());
+
+    println!("\nMANAGED NATIVE IMPORTS (P/Invoke)");
+
+    for module in modules {
+        println!("\n    {}", module);
+
+        for import in imports.iter().filter(|w| w.module().eq_ignore_ascii_case(module)) {
+            let calling_convention = match import.calling_convention() {
+                PInvokeCallingConvention::PlatformApi => "winapi".to_owned(),
+                PInvokeCallingConvention::Cdecl => "cdecl".to_owned(),
+                PInvokeCallingConvention::Stdcall => "stdcall".to_owned(),
+                PInvokeCallingConvention::Thiscall => "thiscall".to_owned(),
+                PInvokeCallingConvention::Fastcall => "fastcall".to_owned(),
+                PInvokeCallingConvention::Unknown(value) => format!("unknown ({:#06X})", value),
+            };
+
+            let char_set = match import.char_set() {
+                PInvokeCharSet::NotSpecified => "not specified",
+                PInvokeCharSet::Ansi => "ansi",
+                PInvokeCharSet::Unicode => "unicode",
+                PInvokeCharSet::Auto => "auto",
+            };
+
+            let member = import.member();
+            let owner = match member.table() {
+                TABLE_METHOD_DEF => tables.owner_of_method(member.row()),
+                _ => tables.owner_of_field(member.row()),
+            };
+            let managed_name = match owner {
+                Some(owner) => format!("{}::{}", formatter.type_name(Token::new(TABLE_TYPE_DEF, owner), GenericContext::default()), import.member_name()),
+                None => import.member_name().to_owned(),
+            };
+
+            let mut attributes = vec![calling_convention, char_set.to_owned()];
+            if import.no_mangle() {
+                attributes.push("no mangle".to_owned());
+            }
+            if import.supports_last_error() {
+                attributes.push("last error".to_owned());
+            }
+
+            println!("        {} ({}) <- {}", import.entry_point(), attributes.join(", "), managed_name);
+        }
+    }
+}
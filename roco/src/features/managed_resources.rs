@@ -0,0 +1,102 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use roki::clr::{ManagedResourceLocation, ResourceValue};
+use roki::Executable;
+
+// resource names may contain characters that are not allowed in file names, or be `..`
+fn file_name(name: &str) -> String {
+    let name = name.chars().map(|w| if w.is_ascii_alphanumeric() || w == '-' || w == '.' { w } else { '_' }).collect::<String>();
+    if name.chars().all(|w| w == '.') {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+// distinct names may map to the same file name, the later ones get a ~N suffix before the extension
+fn unique_file_name(used: &mut HashSet<String>, name: &str) -> String {
+    let name = file_name(name);
+    let (stem, extension) = match name.rfind('.') {
+        Some(position) if position > 0 => name.split_at(position),
+        _ => (name.as_str(), ""),
+    };
+
+    // file systems may be case insensitive
+    let mut unique = name.clone();
+    let mut number = 1;
+    while !used.insert(unique.to_lowercase()) {
+        number += 1;
+        unique = format!("{}~{}{}", stem, number, extension);
+    }
+
+    unique
+}
+
+// writes each embedded resource as is, and the entries of .resources files into a directory named after the resource
+pub fn extract(executable: &Executable, directory: &Path) -> Result<(), failure::Error> {
+    let clr_data = match executable.com_descriptor_data() {
+        Some(clr_data) => clr_data,
+        None => return Ok(()),
+    };
+
+    let resources = clr_data.managed_resources()?;
+    if resources.iter().all(|w| w.location() != ManagedResourceLocation::Embedded) {
+        return Ok(());
+    }
+
+    fs::create_dir_all(directory)?;
+
+    println!("\nMANAGED RESOURCES");
+
+    let mut used: HashSet<String> = HashSet::new();
+    for resource in resources {
+        if resource.location() != ManagedResourceLocation::Embedded {
+            continue;
+        }
+
+        let name = unique_file_name(&mut used, resource.name());
+        let path = directory.join(&name);
+        match fs::write(&path, resource.data()) {
+            Ok(()) => println!("    {} ({} bytes)", path.display(), resource.size()),
+            Err(e) => println!("    Failed to write {}: {}", path.display(), e),
+        }
+
+        let resource_set = match resource.resource_set() {
+            Some(Ok(resource_set)) => resource_set,
+            Some(Err(e)) => {
+                println!("        Failed to parse the .resources: {}", e);
+                continue;
+            }
+            None => continue,
+        };
+
+        let entries_directory = directory.join(unique_file_name(&mut used, &format!("{}.entries", name)));
+        if let Err(e) = fs::create_dir_all(&entries_directory) {
+            println!("        Failed to create {}: {}", entries_directory.display(), e);
+            continue;
+        }
+
+        let mut used: HashSet<String> = HashSet::new();
+        for entry in resource_set.entries() {
+            let bytes = match entry.value() {
+                ResourceValue::String(value) => value.as_bytes(),
+                ResourceValue::ByteArray(value) | ResourceValue::Stream(value) | ResourceValue::Serialized(value) => value.as_slice(),
+                // primitives are only listed
+                _ => {
+                    println!("        {} ({}) {:?}", entry.name(), entry.type_name(), entry.value());
+                    continue;
+                }
+            };
+
+            let path = entries_directory.join(unique_file_name(&mut used, entry.name()));
+            match fs::write(&path, bytes) {
+                Ok(()) => println!("        {} ({}, {} bytes)", path.display(), entry.type_name(), bytes.len()),
+                Err(e) => println!("        Failed to write {}: {}", path.display(), e),
+            }
+        }
+    }
+
+    Ok(())
+}
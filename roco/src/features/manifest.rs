@@ -0,0 +1,56 @@
+use roki::Executable;
+
+pub fn print(executable: &Executable) -> Result<(), failure::Error> {
+    let manifests = match executable.resource_data() {
+        Some(resource_data) => resource_data.manifests()?,
+        None => return Ok(()),
+    };
+
+    for manifest in manifests {
+        println!("\nAPPLICATION MANIFEST #{} (language {:#06X})", manifest.resource_id(), manifest.language());
+
+        match manifest.requested_execution_level() {
+            Some(level) => {
+                let ui_access = match level.ui_access() {
+                    Some(ui_access) => ui_access.to_string(),
+                    None => "(not specified)".to_owned(),
+                };
+
+                println!("    requested execution level : {}", level.level());
+                println!("    ui access                 : {}", ui_access);
+            }
+            None => println!("    requested execution level : (not specified)"),
+        }
+
+        println!("    dpi aware                 : {}", manifest.dpi_aware().unwrap_or("(not specified)"));
+        println!("    dpi awareness             : {}", manifest.dpi_awareness().unwrap_or("(not specified)"));
+
+        for id in manifest.supported_os() {
+            let os = match id.to_lowercase().as_str() {
+                "{e2011457-1546-43c5-a5fe-008deee3d3f0}" => "Windows Vista",
+                "{35138b9a-5d96-4fbd-8e2d-a2440225f93a}" => "Windows 7",
+                "{4a2f28e3-53b9-4441-ba9c-d69d4a4a6e38}" => "Windows 8",
+                "{1f676c76-80e1-4239-95bb-83d0f6d0da78}" => "Windows 8.1",
+                "{8e0f7a12-bfb3-4fe8-b9a5-48fd50a15a9a}" => "Windows 10 / 11",
+                _ => "Unknown",
+            };
+
+            println!("    supported OS              : {} ({})", id, os);
+        }
+
+        for assembly in manifest.dependent_assemblies() {
+            println!(
+                "    dependent assembly        : {} {} ({}, {}, {})",
+                assembly.name().unwrap_or("(unnamed)"),
+                assembly.version().unwrap_or("(no version)"),
+                assembly.processor_architecture().unwrap_or("-"),
+                assembly.public_key_token().unwrap_or("-"),
+                assembly.language().unwrap_or("-"),
+            );
+        }
+
+        println!("\n{}", manifest.xml());
+    }
+
+    Ok(())
+}
@@ -1,13 +1,36 @@
+mod clr;
 mod clr_header;
 mod debug;
+mod dependents;
 mod exports;
 mod headers;
+mod icons;
+mod il;
 mod imports;
 mod load_config;
+mod managed_resources;
+mod manifest;
+mod relocations;
+mod resources;
+mod signature;
+mod tls;
+mod version_info;
 
+pub use clr::print as print_clr;
 pub use clr_header::print as print_clr_header;
 pub use debug::print as print_debug_directory;
+pub use dependents::load_api_set_schema;
+pub use dependents::print as print_dependents;
 pub use exports::print as print_exports;
 pub use headers::print as print_headers;
+pub use icons::extract as extract_icons;
+pub use il::print as print_il;
 pub use imports::print as print_imports;
 pub use load_config::print as print_load_config;
+pub use managed_resources::extract as extract_managed_resources;
+pub use manifest::print as print_manifest;
+pub use relocations::print as print_relocations;
+pub use resources::print as print_resources;
+pub use signature::print as print_signature;
+pub use tls::print as print_tls;
+pub use version_info::print as print_version_info;
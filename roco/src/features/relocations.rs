@@ -0,0 +1,40 @@
+use roki::containers::RelocationType;
+use roki::Executable;
+
+pub fn print(executable: &Executable) {
+    let base_relocation_data = match executable.base_relocation_data() {
+        Some(base_relocation_data) => base_relocation_data,
+        None => {
+            println!("\nThe image has no base relocations");
+            return;
+        }
+    };
+
+    println!(
+        "
+BASE RELOCATIONS
+    number of blocks      : {}
+    number of relocations : {}",
+        base_relocation_data.blocks().len(),
+        base_relocation_data.relocations().len()
+    );
+
+    for block in base_relocation_data.blocks() {
+        let relocations = block.relocations();
+
+        println!("\n    {:#010X} RVA, {:#X} SizeOfBlock ({} entries)", block.page_rva(), block.size_of_block(), relocations.len());
+
+        for relocation in relocations {
+            match relocation.r#type() {
+                RelocationType::HighAdjust => println!(
+                    "        {:#06X} {:<14} {:#010X} ({:#06X})",
+                    relocation.offset(),
+                    relocation.r#type().name(),
+                    relocation.rva(),
+                    relocation.parameter().unwrap_or(0)
+                ),
+                _ => println!("        {:#06X} {:<14} {:#010X}", relocation.offset(), relocation.r#type().name(), relocation.rva()),
+            }
+        }
+    }
+}
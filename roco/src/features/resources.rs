@@ -0,0 +1,299 @@
+use roki::containers::{ResourceContainer, ResourceName, ResourceTable};
+use roki::resources::MenuItem;
+use roki::Executable;
+
+pub fn print(executable: &Executable) -> Result<(), failure::Error> {
+    let resource_data = match executable.resource_data() {
+        Some(resource_data) => resource_data,
+        None => return Ok(()),
+    };
+
+    let root = resource_data.root();
+    let directory = root.directory();
+
+    println!(
+        "
+RESOURCE DIRECTORY
+    characteristics         : {:#010X}
+    time date stamps        : {:#010X}
+    version                 : {}.{}
+    number of named entries : {}
+    number of id entries    : {}
+    ",
+        directory.characteristics(),
+        directory.time_date_stamp(),
+        directory.major_version(),
+        directory.minor_version(),
+        directory.number_of_named_entries(),
+        directory.number_of_id_entries(),
+    );
+
+    print_table(root, 0);
+
+    print_string_tables(resource_data);
+    print_message_tables(resource_data);
+    print_dialogs(resource_data);
+    print_menus(resource_data);
+    print_accelerators(resource_data);
+
+    Ok(())
+}
+
+fn print_string_tables(resource_data: &ResourceContainer) {
+    let blocks = resource_data.string_tables();
+    if blocks.is_empty() {
+        return;
+    }
+
+    println!("\nSTRING TABLE");
+    for block in blocks.iter() {
+        let block = match block {
+            Ok(block) => block,
+            Err(e) => {
+                println!("    {}", e);
+                continue;
+            }
+        };
+
+        for entry in block.entries() {
+            println!("    {:>5} [{:#06X}] : {:?}", entry.id(), block.language(), entry.value());
+        }
+    }
+}
+
+fn print_message_tables(resource_data: &ResourceContainer) {
+    let tables = resource_data.message_tables();
+    if tables.is_empty() {
+        return;
+    }
+
+    println!("\nMESSAGE TABLE");
+    for table in tables.iter() {
+        let table = match table {
+            Ok(table) => table,
+            Err(e) => {
+                println!("    {}", e);
+                continue;
+            }
+        };
+
+        for entry in table.entries() {
+            let encoding = if entry.is_unicode() { "Unicode" } else { "ANSI" };
+            println!("    {:#010X} [{:#06X}] ({}) : {:?}", entry.id(), table.language(), encoding, entry.text());
+        }
+    }
+}
+
+fn print_dialogs(resource_data: &ResourceContainer) {
+    let dialogs = resource_data.dialogs();
+    if dialogs.is_empty() {
+        return;
+    }
+
+    println!("\nDIALOGS");
+    for dialog in dialogs.iter() {
+        let dialog = match dialog {
+            Ok(dialog) => dialog,
+            Err(e) => {
+                println!("    {}", e);
+                continue;
+            }
+        };
+
+        let kind = if dialog.is_extended() { "DIALOGEX" } else { "DIALOG" };
+        println!("    {} {} [{:#06X}] : {}, {}, {}, {}", dialog.name(), kind, dialog.language(), dialog.x(), dialog.y(), dialog.cx(), dialog.cy());
+        println!("        style          : {:#010X}", dialog.style());
+        println!("        extended style : {:#010X}", dialog.extended_style());
+        println!("        caption        : {:?}", dialog.title());
+
+        if let Some(menu) = dialog.menu() {
+            println!("        menu           : {}", menu);
+        }
+        if let Some(class) = dialog.class() {
+            println!("        class          : {}", class);
+        }
+        if let Some(font) = dialog.font() {
+            println!(
+                "        font           : {}, {:?}, {}, {}, {:#04X}",
+                font.point_size(),
+                font.typeface(),
+                font.weight(),
+                font.is_italic(),
+                font.charset()
+            );
+        }
+
+        for item in dialog.items() {
+            // IDC_STATIC is -1, a WORD in DIALOG and a DWORD in DIALOGEX
+            let id = if dialog.is_extended() { item.id() as i32 } else { item.id() as u16 as i16 as i32 };
+            let title = match item.title() {
+                Some(ResourceName::Name(name)) => format!("{:?}", name),
+                Some(name) => name.to_string(),
+                None => "\"\"".to_owned(),
+            };
+
+            println!(
+                "        CONTROL {}, {}, {}, {:#010X}, {}, {}, {}, {}",
+                title,
+                id,
+                item.class_name().unwrap_or_default(),
+                item.style(),
+                item.x(),
+                item.y(),
+                item.cx(),
+                item.cy()
+            );
+        }
+    }
+}
+
+fn print_menus(resource_data: &ResourceContainer) {
+    let menus = resource_data.menus();
+    if menus.is_empty() {
+        return;
+    }
+
+    println!("\nMENUS");
+    for menu in menus.iter() {
+        let menu = match menu {
+            Ok(menu) => menu,
+            Err(e) => {
+                println!("    {}", e);
+                continue;
+            }
+        };
+
+        let kind = if menu.is_extended() { "MENUEX" } else { "MENU" };
+        println!("    {} {} [{:#06X}]", menu.name(), kind, menu.language());
+        print_menu_items(&menu.items(), 2);
+    }
+}
+
+fn print_menu_items(items: &[&MenuItem], depth: usize) {
+    let indent = "    ".repeat(depth);
+
+    for item in items.iter() {
+        if item.is_popup() {
+            println!("{}POPUP {:?}", indent, item.text());
+            print_menu_items(&item.children(), depth + 1);
+        } else if item.text().is_empty() && item.id() == 0 {
+            println!("{}MENUITEM SEPARATOR", indent);
+        } else {
+            println!("{}MENUITEM {:?}, {} (flags {:#X})", indent, item.text(), item.id(), item.flags());
+        }
+    }
+}
+
+fn print_accelerators(resource_data: &ResourceContainer) {
+    let tables = resource_data.accelerator_tables();
+    if tables.is_empty() {
+        return;
+    }
+
+    fn add_if_includes(flags: u16, flag: u16, vector: &mut Vec<String>, text: &str) {
+        if flags & flag == flag {
+            vector.push(text.to_owned());
+        }
+    }
+
+    println!("\nACCELERATORS");
+    for table in tables.iter() {
+        let table = match table {
+            Ok(table) => table,
+            Err(e) => {
+                println!("    {}", e);
+                continue;
+            }
+        };
+
+        println!("    {} [{:#06X}]", table.name(), table.language());
+
+        for entry in table.entries() {
+            let mut modifiers: Vec<String> = Vec::new();
+            add_if_includes(entry.flags(), 0x01, &mut modifiers, "VIRTKEY");
+            add_if_includes(entry.flags(), 0x02, &mut modifiers, "NOINVERT");
+            add_if_includes(entry.flags(), 0x04, &mut modifiers, "SHIFT");
+            add_if_includes(entry.flags(), 0x08, &mut modifiers, "CONTROL");
+            add_if_includes(entry.flags(), 0x10, &mut modifiers, "ALT");
+
+            let key = if entry.flags() & 0x01 != 0 {
+                format!("{:#04X}", entry.key())
+            } else {
+                match std::char::from_u32(entry.key() as u32) {
+                    Some(c) if !c.is_control() => format!("{:?}", c.to_string()),
+                    _ => format!("{}", entry.key()),
+                }
+            };
+
+            println!("        {}, {}, {}", key, entry.id(), modifiers.join(", "));
+        }
+    }
+}
+
+fn print_table(table: &ResourceTable, depth: usize) {
+    let indent = "    ".repeat(depth + 1);
+
+    for entry in table.entries() {
+        let label = match depth {
+            0 => format!("TYPE {}", type_name(entry.name())),
+            1 => format!("NAME {}", entry.name()),
+            _ => format!("LANGUAGE {}", entry.name()),
+        };
+
+        match entry.data() {
+            Some(data) => {
+                println!("{}{} : RVA {:#010X} / size {:#010X} / code page {}", indent, label, data.rva(), data.size(), data.code_page());
+                if let Some(error) = data.error() {
+                    println!("{}    {}", indent, error);
+                }
+            }
+            None => println!("{}{}", indent, label),
+        }
+
+        if let Some(error) = entry.error() {
+            println!("{}    {}", indent, error);
+        }
+
+        if let Some(table) = entry.table() {
+            print_table(table, depth + 1);
+        }
+    }
+
+    if let Some(error) = table.error() {
+        println!("{}{}", indent, error);
+    }
+}
+
+fn type_name(name: &ResourceName) -> String {
+    let id = match name.id() {
+        Some(id) => id,
+        None => return name.to_string(),
+    };
+
+    let r#type = match id {
+        1 => "RT_CURSOR",
+        2 => "RT_BITMAP",
+        3 => "RT_ICON",
+        4 => "RT_MENU",
+        5 => "RT_DIALOG",
+        6 => "RT_STRING",
+        7 => "RT_FONTDIR",
+        8 => "RT_FONT",
+        9 => "RT_ACCELERATOR",
+        10 => "RT_RCDATA",
+        11 => "RT_MESSAGETABLE",
+        12 => "RT_GROUP_CURSOR",
+        14 => "RT_GROUP_ICON",
+        16 => "RT_VERSION",
+        17 => "RT_DLGINCLUDE",
+        19 => "RT_PLUGPLAY",
+        20 => "RT_VXD",
+        21 => "RT_ANICURSOR",
+        22 => "RT_ANIICON",
+        23 => "RT_HTML",
+        24 => "RT_MANIFEST",
+        _ => return name.to_string(),
+    };
+
+    format!("{} ({})", name, r#type)
+}
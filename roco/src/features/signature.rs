@@ -0,0 +1,133 @@
+use roki::authenticode::{algorithm_name, verify_image_hash, AuthenticodeSignature, TimestampKind, X509Certificate};
+use roki::Executable;
+
+pub fn print(executable: &Executable) -> Result<(), failure::Error> {
+    let security_data = match executable.security_data() {
+        Some(security_data) => security_data,
+        None => {
+            println!("\nThe image is not signed");
+            return Ok(());
+        }
+    };
+
+    for certificate in security_data.certificates() {
+        println!(
+            "\nWIN_CERTIFICATE at {:#010X}
+    length           : {:#010X}
+    revision         : {:#06X}
+    certificate type : {:#06X}",
+            certificate.offset(),
+            certificate.header().length(),
+            certificate.revision(),
+            certificate.certificate_type()
+        );
+    }
+
+    if let Some(error) = security_data.error() {
+        println!("\n{}", error);
+    }
+
+    for (i, signature) in security_data.authenticode_signatures()?.iter().enumerate() {
+        print_signature(executable, signature, &format!("SIGNATURE #{}", i + 1), 0);
+    }
+
+    Ok(())
+}
+
+fn print_signature(executable: &Executable, signature: &AuthenticodeSignature, title: &str, depth: usize) {
+    let indent = "    ".repeat(depth);
+    let signer = signature.signer();
+
+    println!("\n{}{}", indent, title);
+    println!("{}    digest algorithm : {}", indent, signature.digest_algorithm());
+    println!("{}    digest           : {}", indent, hex(signature.digest()));
+
+    match verify_image_hash(executable, signature) {
+        Ok(verification) => {
+            if verification.is_image_hash_match() {
+                println!("{}    image hash       : {} (matches)", indent, hex(verification.computed()));
+            } else {
+                println!("{}    image hash       : {} (does not match, the image was modified)", indent, hex(verification.computed()));
+            }
+
+            match (verification.message_digest(), verification.is_message_digest_match()) {
+                (Some(message_digest), true) => println!("{}    message digest   : {} (matches)", indent, hex(message_digest)),
+                (Some(message_digest), false) => println!("{}    message digest   : {} (does not match, the signed digest was replaced)", indent, hex(message_digest)),
+                (None, _) => println!("{}    message digest   : - (the signer has no authenticated attributes)", indent),
+            }
+
+            match verification.signature_error() {
+                Some(error) => println!("{}    signer signature : not verified, {}", indent, error),
+                None if verification.is_signature_valid() => println!("{}    signer signature : valid (the certificate chain is not validated)", indent),
+                None => println!("{}    signer signature : INVALID, the authenticated attributes were not signed by the signing certificate", indent),
+            }
+
+            let verdict = if verification.is_match() {
+                "intact"
+            } else if verification.signature_error().is_some() && verification.is_image_hash_match() && verification.is_message_digest_match() {
+                "digests consistent, signature not verified"
+            } else {
+                "MODIFIED"
+            };
+            println!("{}    integrity        : {}", indent, verdict);
+        }
+        Err(e) => println!("{}    image hash       : {}", indent, e),
+    }
+
+    println!("{}    signer digest    : {}", indent, signer.digest_algorithm());
+    println!("{}    signature        : {}", indent, name_of(signer.signature_algorithm()));
+
+    if let Some(program_name) = signer.program_name().filter(|w| !w.is_empty()) {
+        println!("{}    program name     : {}", indent, program_name);
+    }
+    if let Some(more_info) = signer.more_info().filter(|w| !w.is_empty()) {
+        println!("{}    more info        : {}", indent, more_info);
+    }
+    if let Some(signing_time) = signer.signing_time() {
+        println!("{}    signing time     : {}", indent, signing_time);
+    }
+
+    println!("\n{}    CERTIFICATE CHAIN", indent);
+    print_chain(&signature.chain(), depth + 2);
+
+    for timestamp in signature.timestamps() {
+        let kind = match timestamp.kind() {
+            TimestampKind::CounterSignature => "COUNTERSIGNATURE",
+            TimestampKind::Rfc3161 => "RFC 3161",
+        };
+
+        println!("\n{}    TIMESTAMP ({})", indent, kind);
+        println!("{}        time             : {}", indent, timestamp.time().unwrap_or("(not specified)"));
+        println!("{}        digest algorithm : {}", indent, timestamp.signer().digest_algorithm());
+        print_chain(&timestamp.chain(), depth + 2);
+    }
+
+    for (i, nested) in signature.nested_signatures().iter().enumerate() {
+        print_signature(executable, nested, &format!("NESTED SIGNATURE #{}", i + 1), depth + 1);
+    }
+}
+
+fn print_chain(chain: &[&X509Certificate], depth: usize) {
+    let indent = "    ".repeat(depth);
+
+    for (i, certificate) in chain.iter().enumerate() {
+        println!("{}[{}] subject    : {}", indent, i, certificate.subject());
+        println!("{}    issuer     : {}", indent, certificate.issuer());
+        println!("{}    serial     : {}", indent, hex(certificate.serial_number()));
+        println!("{}    valid from : {}", indent, certificate.not_before());
+        println!("{}    valid to   : {}", indent, certificate.not_after());
+        println!("{}    algorithm  : {}", indent, name_of(certificate.signature_algorithm()));
+        println!("{}    thumbprint : {}", indent, hex(&certificate.thumbprint()));
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|w| format!("{:02X}", w)).collect()
+}
+
+fn name_of(oid: &str) -> String {
+    match algorithm_name(oid) {
+        Some(name) => name.to_owned(),
+        None => oid.to_owned(),
+    }
+}
@@ -0,0 +1,67 @@
+use roki::Executable;
+
+pub fn print(executable: &Executable) {
+    let tls_data = match executable.tls_data() {
+        Some(tls_data) => tls_data,
+        None => {
+            println!("\nThe image has no TLS directory");
+            return;
+        }
+    };
+
+    let directory = tls_data.directory();
+
+    let is_pe32_plus = executable.optional_header().unwrap().is_pe32_plus();
+    let va = |va: u64| if is_pe32_plus { format!("{:#018X}", va) } else { format!("{:#010X}", va) };
+
+    fn rva(rva: Option<u32>) -> String {
+        match rva {
+            Some(rva) => format!("{:#010X}", rva),
+            None => "-".to_owned(),
+        }
+    }
+
+    println!(
+        "
+TLS DIRECTORY
+    start address of raw data : {} (RVA {})
+    end address of raw data   : {} (RVA {})
+    address of index          : {} (RVA {})
+    address of callbacks      : {} (RVA {})
+    size of zero fill         : {:#010X}
+    characteristics           : {:#010X}
+    alignment                 : {}
+    size of template          : {:#010X} ({:#X} bytes in the file)",
+        va(directory.start_address_of_raw_data()),
+        rva(tls_data.start_address_of_raw_data_rva()),
+        va(directory.end_address_of_raw_data()),
+        rva(tls_data.end_address_of_raw_data_rva()),
+        va(directory.address_of_index()),
+        rva(tls_data.address_of_index_rva()),
+        va(directory.address_of_callbacks()),
+        rva(tls_data.address_of_callbacks_rva()),
+        tls_data.size_of_zero_fill(),
+        directory.characteristics(),
+        directory.alignment().map_or("default".to_owned(), |w| format!("{} bytes", w)),
+        tls_data.template_size(),
+        tls_data.template().len(),
+    );
+
+    if let Some(error) = tls_data.template_error() {
+        println!("    {}", error);
+    }
+
+    let callbacks = tls_data.callbacks();
+    if callbacks.is_empty() && tls_data.callbacks_error().is_none() {
+        return;
+    }
+
+    println!("\n    TLS CALLBACKS");
+    for (i, callback) in callbacks.iter().enumerate() {
+        println!("        [{}] {} (RVA {})", i, va(callback.va()), rva(callback.rva()));
+    }
+
+    if let Some(error) = tls_data.callbacks_error() {
+        println!("        {}", error);
+    }
+}
@@ -0,0 +1,101 @@
+use roki::Executable;
+
+pub fn print(executable: &Executable) -> Result<(), failure::Error> {
+    let version_info = match executable.resource_data() {
+        Some(resource_data) => match resource_data.version_info()? {
+            Some(version_info) => version_info,
+            None => return Ok(()),
+        },
+        None => return Ok(()),
+    };
+
+    println!("\nVERSION INFORMATION");
+
+    if let Some(fixed_file_info) = version_info.fixed_file_info() {
+        fn add_if_includes(flags: u32, flag: u32, vector: &mut Vec<String>, text: &str) {
+            if flags & flag == flag {
+                vector.push(text.to_owned());
+            }
+        }
+
+        let file_flags = fixed_file_info.file_flags() & fixed_file_info.file_flags_mask();
+        let mut flags: Vec<String> = Vec::new();
+        add_if_includes(file_flags, 0x00000001, &mut flags, "VS_FF_DEBUG");
+        add_if_includes(file_flags, 0x00000002, &mut flags, "VS_FF_PRERELEASE");
+        add_if_includes(file_flags, 0x00000004, &mut flags, "VS_FF_PATCHED");
+        add_if_includes(file_flags, 0x00000008, &mut flags, "VS_FF_PRIVATEBUILD");
+        add_if_includes(file_flags, 0x00000010, &mut flags, "VS_FF_INFOINFERRED");
+        add_if_includes(file_flags, 0x00000020, &mut flags, "VS_FF_SPECIALBUILD");
+
+        let os = match fixed_file_info.file_os() {
+            0x00000000 => "Unknown",
+            0x00000001 => "16-bit Windows",
+            0x00000004 => "32-bit Windows",
+            0x00010000 => "MS-DOS",
+            0x00010001 => "16-bit Windows on MS-DOS",
+            0x00010004 => "32-bit Windows on MS-DOS",
+            0x00040000 => "Windows NT",
+            0x00040004 => "32-bit Windows on Windows NT",
+            _ => "Other",
+        };
+        let r#type = match fixed_file_info.file_type() {
+            0x00000001 => "Application",
+            0x00000002 => "DLL",
+            0x00000003 => "Device driver",
+            0x00000004 => "Font",
+            0x00000005 => "Virtual device",
+            0x00000007 => "Static-link library",
+            _ => "Unknown",
+        };
+
+        let file_version = fixed_file_info.file_version();
+        let product_version = fixed_file_info.product_version();
+
+        println!(
+            "    file version    : {}.{}.{}.{}
+    product version : {}.{}.{}.{}
+    file flags      : {:#010X}\
+    ",
+            file_version[0], file_version[1], file_version[2], file_version[3], product_version[0], product_version[1], product_version[2], product_version[3], file_flags,
+        );
+
+        for flag in flags {
+            println!("        {}", flag);
+        }
+
+        println!(
+            "    file OS         : {:#010X} ({})
+    file type       : {:#010X} ({})
+    file subtype    : {:#010X}
+    file date       : {:#018X}\
+    ",
+            fixed_file_info.file_os(),
+            os,
+            fixed_file_info.file_type(),
+            r#type,
+            fixed_file_info.file_subtype(),
+            fixed_file_info.file_date(),
+        );
+    }
+
+    for table in version_info.string_tables() {
+        println!("\n    STRING FILE INFO {} (language {:#06X}, code page {})", table.key(), table.language(), table.code_page());
+
+        let strings = table.strings();
+        let the_longest_key_length = strings.iter().map(|w| w.0.len()).max().unwrap_or(0);
+
+        for (key, value) in strings {
+            println!("        {:width$} : {}", key, value, width = the_longest_key_length);
+        }
+    }
+
+    if !version_info.translations().is_empty() {
+        println!("\n    VAR FILE INFO");
+    }
+
+    for translation in version_info.translations() {
+        println!("        translation : language {:#06X}, code page {}", translation.language(), translation.code_page());
+    }
+
+    Ok(())
+}
@@ -29,24 +29,63 @@ fn run(matches: ArgMatches<'static>) -> Result<(), failure::Error> {
     let mut executable = Executable::new(path)?;
     executable.parse()?;
 
+    let api_set_schema = match matches.value_of("apiset_schema") {
+        Some(schema) => Some(load_api_set_schema(Path::new(schema))?),
+        None => None,
+    };
+
     if matches.is_present("exports") {
         print_exports(&executable);
     }
     if matches.is_present("debug") {
         print_debug_directory(&executable);
     }
+    if matches.is_present("dependents") {
+        let search_paths: Vec<&Path> = matches.values_of("search_path").map(|w| w.map(Path::new).collect()).unwrap_or_default();
+        print_dependents(&executable, &search_paths, api_set_schema.as_ref())?;
+    }
     if matches.is_present("headers") {
         print_headers(&executable);
     }
     if matches.is_present("imports") {
-        print_imports(&executable);
+        print_imports(&executable, api_set_schema.as_ref());
     }
     if matches.is_present("load_config") {
         print_load_config(&executable);
     }
+    if matches.is_present("relocations") {
+        print_relocations(&executable);
+    }
+    if matches.is_present("resources") {
+        print_resources(&executable)?;
+    }
+    if matches.is_present("version_info") {
+        print_version_info(&executable)?;
+    }
+    if matches.is_present("manifest") {
+        print_manifest(&executable)?;
+    }
+    if let Some(directory) = matches.value_of("extract_icons") {
+        extract_icons(&executable, Path::new(directory))?;
+    }
+    if matches.is_present("tls") {
+        print_tls(&executable);
+    }
+    if matches.is_present("signature") {
+        print_signature(&executable)?;
+    }
     if matches.is_present("clr_header") {
         print_clr_header(&executable);
     }
+    if matches.is_present("clr_container") {
+        print_clr(&executable)?;
+    }
+    if let Some(directory) = matches.value_of("extract_managed_resources") {
+        extract_managed_resources(&executable, Path::new(directory))?;
+    }
+    if let Some(method) = matches.value_of("il") {
+        print_il(&executable, method)?;
+    }
 
     Ok(())
 }
@@ -0,0 +1,386 @@
+use std::collections::HashMap;
+
+use scroll::{Pread, LE};
+
+use crate::strings::gread_utf16;
+use crate::Executable;
+
+// the schema is undocumented, layouts follow
+// https://www.geoffchappell.com/studies/windows/win32/apisetschema/index.htm
+const MAX_CONTRACTS: u32 = 0x10000;
+const MAX_VALUES: u32 = 0x100;
+
+const API_SET_SCHEMA_ENTRY_FLAGS_SEALED: u32 = 0x0000_0001;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+struct ApiSetNamespaceEntryV2 {
+    name_offset: u32,
+    name_length: u32,
+    data_offset: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+struct ApiSetValueEntryV2 {
+    name_offset: u32,
+    name_length: u32,
+    value_offset: u32,
+    value_length: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+struct ApiSetNamespaceEntryV4 {
+    flags: u32,
+    name_offset: u32,
+    name_length: u32,
+    alias_offset: u32,
+    alias_length: u32,
+    data_offset: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+struct ApiSetValueEntryV4 {
+    flags: u32,
+    name_offset: u32,
+    name_length: u32,
+    value_offset: u32,
+    value_length: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+struct ApiSetNamespaceEntryV6 {
+    flags: u32,
+    name_offset: u32,
+    name_length: u32,
+    hashed_length: u32,
+    value_offset: u32,
+    value_count: u32,
+}
+
+#[derive(Debug)]
+pub struct ApiSetValue {
+    flags: u32,
+    importer: String,
+    host: String,
+}
+
+impl ApiSetValue {
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    // empty for the default host
+    pub fn importer(&self) -> &str {
+        &self.importer
+    }
+
+    // empty if the contract has no implementation
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    pub fn is_exception(&self) -> bool {
+        !self.importer.is_empty()
+    }
+}
+
+#[derive(Debug)]
+pub struct ApiSetContract {
+    name: String,
+    // in characters, the prefix of the name that the loader compares
+    hashed_length: usize,
+    alias: Option<String>,
+    flags: u32,
+    values: Vec<ApiSetValue>,
+}
+
+impl ApiSetContract {
+    // version 4 only
+    pub fn alias(&self) -> Option<&str> {
+        self.alias.as_deref()
+    }
+
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    pub fn is_sealed(&self) -> bool {
+        self.flags & API_SET_SCHEMA_ENTRY_FLAGS_SEALED != 0
+    }
+
+    // full contract name without the .dll extension, e.g. api-ms-win-core-file-l1-1-0
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn values(&self) -> Vec<&ApiSetValue> {
+        self.values.iter().collect()
+    }
+
+    // the exception for the importing module if any, otherwise the default host
+    pub fn host_for(&self, importer: Option<&str>) -> Option<&str> {
+        if let Some(importer) = importer {
+            let exception = self.values.iter().filter(|w| w.is_exception()).find(|w| w.importer.eq_ignore_ascii_case(importer));
+            if let Some(value) = exception {
+                return Some(value.host.as_str()).filter(|w| !w.is_empty());
+            }
+        }
+
+        // the loader takes the first value as the default
+        self.values.iter().find(|w| !w.is_exception()).or_else(|| self.values.first()).map(|w| w.host.as_str()).filter(|w| !w.is_empty())
+    }
+}
+
+#[derive(Debug)]
+pub struct ApiSetSchema {
+    version: u32,
+    flags: u32,
+    contracts: Vec<ApiSetContract>,
+    index: HashMap<String, usize>,
+}
+
+impl ApiSetSchema {
+    // parses the .apiset section of apisetschema.dll
+    pub fn parse(executable: &Executable) -> Result<Self, failure::Error> {
+        let section_headers = executable.section_headers().unwrap_or_default();
+        let section = match section_headers.iter().find(|w| w.name().trim_end_matches('\0') == ".apiset") {
+            Some(section) => section,
+            None => return Err(failure::err_msg("The image has no .apiset section")),
+        };
+
+        let start = section.pointer_to_raw_data() as usize;
+        let end = start + section.size_of_raw_data() as usize;
+        let data = match executable.buffer().get(start..end) {
+            Some(data) => data,
+            None => return Err(failure::err_msg("Failed to read the .apiset section")),
+        };
+
+        ApiSetSchema::parse_namespace(data)
+    }
+
+    pub fn parse_namespace(data: &[u8]) -> Result<Self, failure::Error> {
+        let version = data.pread_with::<u32>(0, LE).map_err(|_| failure::err_msg("Failed to read the API_SET_NAMESPACE"))?;
+
+        let (flags, contracts) = match version {
+            2 => (0, ApiSetSchema::parse_v2(data)?),
+            4 => ApiSetSchema::parse_v4(data)?,
+            6 => ApiSetSchema::parse_v6(data)?,
+            _ => {
+                let msg = format!("Unsupported API set schema version {}", version);
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        let mut index: HashMap<String, usize> = HashMap::new();
+        for (i, contract) in contracts.iter().enumerate() {
+            let key: String = contract.name.chars().take(contract.hashed_length).collect();
+            index.entry(key.to_ascii_lowercase()).or_insert(i);
+        }
+
+        Ok(ApiSetSchema { version, flags, contracts, index })
+    }
+
+    fn parse_v2(data: &[u8]) -> Result<Vec<ApiSetContract>, failure::Error> {
+        // API_SET_NAMESPACE_ARRAY_V2 { Version, Count, Array[] }
+        let mut offset = 4;
+        let count = read_count(data, &mut offset, MAX_CONTRACTS)?;
+
+        let mut contracts: Vec<ApiSetContract> = Vec::new();
+        for _ in 0..count {
+            let entry = gread::<ApiSetNamespaceEntryV2>(data, &mut offset, "API_SET_NAMESPACE_ENTRY")?;
+            let name = contract_name(&read_string(data, entry.name_offset, entry.name_length)?);
+
+            // API_SET_VALUE_ARRAY_V2 { Count, Array[] }
+            let mut value_offset = entry.data_offset as usize;
+            let value_count = read_count(data, &mut value_offset, MAX_VALUES)?;
+
+            let mut values: Vec<ApiSetValue> = Vec::new();
+            for _ in 0..value_count {
+                let value = gread::<ApiSetValueEntryV2>(data, &mut value_offset, "API_SET_VALUE_ENTRY")?;
+                values.push(ApiSetValue {
+                    flags: 0,
+                    importer: read_string(data, value.name_offset, value.name_length)?,
+                    host: read_string(data, value.value_offset, value.value_length)?,
+                });
+            }
+
+            contracts.push(ApiSetContract {
+                hashed_length: name.chars().count(),
+                name,
+                alias: None,
+                flags: 0,
+                values,
+            });
+        }
+
+        Ok(contracts)
+    }
+
+    fn parse_v4(data: &[u8]) -> Result<(u32, Vec<ApiSetContract>), failure::Error> {
+        // API_SET_NAMESPACE_ARRAY_V4 { Version, Size, Flags, Count, Array[] }
+        let mut offset = 8;
+        let flags = gread::<u32>(data, &mut offset, "API_SET_NAMESPACE")?;
+        let count = read_count(data, &mut offset, MAX_CONTRACTS)?;
+
+        let mut contracts: Vec<ApiSetContract> = Vec::new();
+        for _ in 0..count {
+            let entry = gread::<ApiSetNamespaceEntryV4>(data, &mut offset, "API_SET_NAMESPACE_ENTRY")?;
+            let name = contract_name(&read_string(data, entry.name_offset, entry.name_length)?);
+
+            // API_SET_VALUE_ARRAY_V4 { Flags, Count, Array[] }
+            let mut value_offset = entry.data_offset as usize + 4;
+            let value_count = read_count(data, &mut value_offset, MAX_VALUES)?;
+
+            let values = read_values(data, value_offset, value_count)?;
+            let alias = match entry.alias_length {
+                0 => None,
+                length => Some(read_string(data, entry.alias_offset, length)?),
+            };
+
+            contracts.push(ApiSetContract {
+                hashed_length: name.chars().count(),
+                name,
+                alias,
+                flags: entry.flags,
+                values,
+            });
+        }
+
+        Ok((flags, contracts))
+    }
+
+    fn parse_v6(data: &[u8]) -> Result<(u32, Vec<ApiSetContract>), failure::Error> {
+        // API_SET_NAMESPACE { Version, Size, Flags, Count, EntryOffset, HashOffset, HashFactor }
+        let mut offset = 8;
+        let flags = gread::<u32>(data, &mut offset, "API_SET_NAMESPACE")?;
+        let count = read_count(data, &mut offset, MAX_CONTRACTS)?;
+        let mut offset = gread::<u32>(data, &mut offset, "API_SET_NAMESPACE")? as usize;
+
+        let mut contracts: Vec<ApiSetContract> = Vec::new();
+        for _ in 0..count {
+            let entry = gread::<ApiSetNamespaceEntryV6>(data, &mut offset, "API_SET_NAMESPACE_ENTRY")?;
+            let name = contract_name(&read_string(data, entry.name_offset, entry.name_length)?);
+
+            if entry.value_count > MAX_VALUES {
+                let msg = format!("Too many values for the API set contract {}", name);
+                return Err(failure::err_msg(msg));
+            }
+
+            contracts.push(ApiSetContract {
+                name,
+                hashed_length: entry.hashed_length as usize / 2,
+                alias: None,
+                flags: entry.flags,
+                values: read_values(data, entry.value_offset as usize, entry.value_count)?,
+            });
+        }
+
+        Ok((flags, contracts))
+    }
+
+    // version 6 resolves contracts regardless of the minor version after the last hyphen
+    fn key(version: u32, name: &str) -> String {
+        let name = name.to_ascii_lowercase();
+        let name = name.trim_end_matches(".dll");
+
+        match name.rfind('-') {
+            Some(position) if version >= 6 => name[..position].to_owned(),
+            _ => name.to_owned(),
+        }
+    }
+
+    pub fn contracts(&self) -> Vec<&ApiSetContract> {
+        self.contracts.iter().collect()
+    }
+
+    // accepts the DLL name as written in the import table, e.g. API-MS-Win-Core-File-L1-2-0.dll
+    pub fn contract_of(&self, name: &str) -> Option<&ApiSetContract> {
+        if !is_api_set_name(name) {
+            return None;
+        }
+
+        self.index.get(&ApiSetSchema::key(self.version, name)).map(|w| &self.contracts[*w])
+    }
+
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    // the host DLL of the contract, `importer` is the file name of the importing module
+    pub fn resolve(&self, name: &str, importer: Option<&str>) -> Option<&str> {
+        self.contract_of(name).and_then(|w| w.host_for(importer))
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+// api- and ext- prefixed names are redirected by the loader
+pub fn is_api_set_name(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.starts_with("api-") || name.starts_with("ext-")
+}
+
+// versions 2 and 4 store the names without the api- prefix
+fn contract_name(name: &str) -> String {
+    if is_api_set_name(name) {
+        name.to_owned()
+    } else {
+        format!("api-{}", name)
+    }
+}
+
+fn gread<'a, T: scroll::ctx::TryFromCtx<'a, scroll::Endian, Error = scroll::Error>>(data: &'a [u8], offset: &mut usize, name: &str) -> Result<T, failure::Error> {
+    let address = *offset;
+    data.gread_with::<T>(offset, LE).map_err(|_| {
+        let msg = format!("Failed to read the {} at {:#X}", name, address);
+        failure::err_msg(msg)
+    })
+}
+
+// API_SET_VALUE_ENTRY of versions 4 and 6 share the layout
+fn read_values(data: &[u8], mut offset: usize, count: u32) -> Result<Vec<ApiSetValue>, failure::Error> {
+    if count > MAX_VALUES {
+        let msg = format!("Too many API set values ({}) at {:#X}", count, offset);
+        return Err(failure::err_msg(msg));
+    }
+
+    let mut values: Vec<ApiSetValue> = Vec::new();
+    for _ in 0..count {
+        let value = gread::<ApiSetValueEntryV4>(data, &mut offset, "API_SET_VALUE_ENTRY")?;
+        values.push(ApiSetValue {
+            flags: value.flags,
+            importer: read_string(data, value.name_offset, value.name_length)?,
+            host: read_string(data, value.value_offset, value.value_length)?,
+        });
+    }
+
+    Ok(values)
+}
+
+fn read_count(data: &[u8], offset: &mut usize, max: u32) -> Result<u32, failure::Error> {
+    let count = gread::<u32>(data, offset, "API set count")?;
+    if count > max {
+        let msg = format!("Too many API set entries ({}) at {:#X}", count, *offset - 4);
+        return Err(failure::err_msg(msg));
+    }
+
+    Ok(count)
+}
+
+// offsets are relative to the beginning of the namespace, lengths are in bytes
+fn read_string(data: &[u8], offset: u32, length: u32) -> Result<String, failure::Error> {
+    if length == 0 {
+        return Ok(String::new());
+    }
+
+    gread_utf16(data, &mut (offset as usize), length as usize / 2).map_err(|_| {
+        let msg = format!("Failed to read the API set string at {:#X}", offset);
+        failure::err_msg(msg)
+    })
+}
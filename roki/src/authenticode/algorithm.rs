@@ -0,0 +1,61 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DigestAlgorithm {
+    Md5,
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+    Unknown(String),
+}
+
+impl DigestAlgorithm {
+    pub fn from_oid(oid: &str) -> Self {
+        match oid {
+            "1.2.840.113549.2.5" => DigestAlgorithm::Md5,
+            "1.3.14.3.2.26" => DigestAlgorithm::Sha1,
+            "2.16.840.1.101.3.4.2.1" => DigestAlgorithm::Sha256,
+            "2.16.840.1.101.3.4.2.2" => DigestAlgorithm::Sha384,
+            "2.16.840.1.101.3.4.2.3" => DigestAlgorithm::Sha512,
+            _ => DigestAlgorithm::Unknown(oid.to_owned()),
+        }
+    }
+}
+
+impl Display for DigestAlgorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DigestAlgorithm::Md5 => write!(f, "MD5"),
+            DigestAlgorithm::Sha1 => write!(f, "SHA-1"),
+            DigestAlgorithm::Sha256 => write!(f, "SHA-256"),
+            DigestAlgorithm::Sha384 => write!(f, "SHA-384"),
+            DigestAlgorithm::Sha512 => write!(f, "SHA-512"),
+            DigestAlgorithm::Unknown(oid) => write!(f, "{}", oid),
+        }
+    }
+}
+
+// human readable names of signature and public key algorithms
+pub fn algorithm_name(oid: &str) -> Option<&'static str> {
+    let name = match oid {
+        "1.2.840.113549.1.1.1" => "rsaEncryption",
+        "1.2.840.113549.1.1.4" => "md5WithRSAEncryption",
+        "1.2.840.113549.1.1.5" => "sha1WithRSAEncryption",
+        "1.2.840.113549.1.1.10" => "RSASSA-PSS",
+        "1.2.840.113549.1.1.11" => "sha256WithRSAEncryption",
+        "1.2.840.113549.1.1.12" => "sha384WithRSAEncryption",
+        "1.2.840.113549.1.1.13" => "sha512WithRSAEncryption",
+        "1.2.840.10040.4.1" => "dsa",
+        "1.2.840.10040.4.3" => "dsaWithSHA1",
+        "1.2.840.10045.2.1" => "ecPublicKey",
+        "1.2.840.10045.4.1" => "ecdsa-with-SHA1",
+        "1.2.840.10045.4.3.2" => "ecdsa-with-SHA256",
+        "1.2.840.10045.4.3.3" => "ecdsa-with-SHA384",
+        "1.2.840.10045.4.3.4" => "ecdsa-with-SHA512",
+        "1.3.101.112" => "Ed25519",
+        _ => return None,
+    };
+
+    Some(name)
+}
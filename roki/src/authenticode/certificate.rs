@@ -0,0 +1,210 @@
+use sha1::{Digest, Sha1};
+use sha2::Sha256;
+
+use super::der::{Reader, Tlv, TAG_BIT_STRING, TAG_INTEGER, TAG_OBJECT_IDENTIFIER, TAG_SEQUENCE, TAG_SET};
+
+#[derive(Clone, Debug)]
+pub struct DistinguishedName {
+    raw: Vec<u8>,
+    attributes: Vec<(String, String)>,
+}
+
+impl DistinguishedName {
+    pub(in crate) fn parse(tlv: &Tlv) -> Result<Self, failure::Error> {
+        let tlv = tlv.expect(TAG_SEQUENCE, "Name")?;
+        let mut attributes: Vec<(String, String)> = Vec::new();
+        let mut rdns = tlv.reader();
+
+        while !rdns.is_empty() {
+            let mut rdn = rdns.read_expected(TAG_SET, "RelativeDistinguishedName")?.reader();
+
+            while !rdn.is_empty() {
+                let mut attribute = rdn.read_expected(TAG_SEQUENCE, "AttributeTypeAndValue")?.reader();
+                let oid = attribute.read_expected(TAG_OBJECT_IDENTIFIER, "AttributeType")?.object_identifier()?;
+                let value = attribute.read()?;
+
+                let key = match DistinguishedName::short_name(&oid) {
+                    Some(name) => name.to_owned(),
+                    None => oid,
+                };
+                let value = value.string().unwrap_or_else(|_| DistinguishedName::hex(value.contents()));
+
+                attributes.push((key, value));
+            }
+        }
+
+        Ok(DistinguishedName { raw: tlv.raw().to_vec(), attributes })
+    }
+
+    // (type, value) pairs in the encoded order, e.g. ("CN", "Contoso")
+    pub fn attributes(&self) -> Vec<(&str, &str)> {
+        self.attributes.iter().map(|w| (w.0.as_str(), w.1.as_str())).collect()
+    }
+
+    pub fn common_name(&self) -> Option<&str> {
+        self.get("CN")
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.attributes.iter().find(|w| w.0 == key).map(|w| w.1.as_str())
+    }
+
+    // DER encoding, for comparing names exactly
+    pub fn raw(&self) -> &[u8] {
+        &self.raw
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|w| format!("{:02X}", w)).collect()
+    }
+
+    fn short_name(oid: &str) -> Option<&'static str> {
+        let name = match oid {
+            "2.5.4.3" => "CN",
+            "2.5.4.4" => "SN",
+            "2.5.4.5" => "SERIALNUMBER",
+            "2.5.4.6" => "C",
+            "2.5.4.7" => "L",
+            "2.5.4.8" => "ST",
+            "2.5.4.9" => "STREET",
+            "2.5.4.10" => "O",
+            "2.5.4.11" => "OU",
+            "2.5.4.12" => "T",
+            "2.5.4.15" => "businessCategory",
+            "2.5.4.17" => "postalCode",
+            "2.5.4.42" => "GN",
+            "1.2.840.113549.1.9.1" => "E",
+            "0.9.2342.19200300.100.1.25" => "DC",
+            "1.3.6.1.4.1.311.60.2.1.1" => "jurisdictionL",
+            "1.3.6.1.4.1.311.60.2.1.2" => "jurisdictionST",
+            "1.3.6.1.4.1.311.60.2.1.3" => "jurisdictionC",
+            _ => return None,
+        };
+
+        Some(name)
+    }
+}
+
+impl std::fmt::Display for DistinguishedName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text: Vec<String> = self.attributes.iter().map(|w| format!("{}={}", w.0, w.1)).collect();
+        write!(f, "{}", text.join(", "))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct X509Certificate {
+    // https://tools.ietf.org/html/rfc5280#section-4.1
+    raw: Vec<u8>,
+    version: u32,
+    serial_number: Vec<u8>,
+    signature_algorithm: String,
+    issuer: DistinguishedName,
+    not_before: String,
+    not_after: String,
+    subject: DistinguishedName,
+    public_key_algorithm: String,
+    public_key: Vec<u8>,
+}
+
+impl X509Certificate {
+    pub(in crate) fn parse(tlv: &Tlv) -> Result<Self, failure::Error> {
+        let mut certificate = tlv.expect(TAG_SEQUENCE, "Certificate")?.reader();
+        let mut tbs = certificate.read_expected(TAG_SEQUENCE, "TBSCertificate")?.reader();
+
+        // [0] EXPLICIT Version DEFAULT v1
+        let version = match tbs.read_optional(0xA0)? {
+            Some(version) => version.reader().read()?.small_integer()? as u32 + 1,
+            None => 1,
+        };
+
+        let serial_number = tbs.read_expected(TAG_INTEGER, "CertificateSerialNumber")?.integer()?.to_vec();
+        let signature_algorithm = read_algorithm(&mut tbs)?;
+        let issuer = DistinguishedName::parse(&tbs.read()?)?;
+
+        let mut validity = tbs.read_expected(TAG_SEQUENCE, "Validity")?.reader();
+        let not_before = validity.read()?.time()?;
+        let not_after = validity.read()?.time()?;
+
+        let subject = DistinguishedName::parse(&tbs.read()?)?;
+
+        let mut public_key_info = tbs.read_expected(TAG_SEQUENCE, "SubjectPublicKeyInfo")?.reader();
+        let public_key_algorithm = read_algorithm(&mut public_key_info)?;
+        let public_key = public_key_info.read_expected(TAG_BIT_STRING, "subjectPublicKey")?.bit_string()?.to_vec();
+
+        Ok(X509Certificate {
+            raw: tlv.raw().to_vec(),
+            version,
+            serial_number,
+            signature_algorithm,
+            issuer,
+            not_before,
+            not_after,
+            subject,
+            public_key_algorithm,
+            public_key,
+        })
+    }
+
+    pub fn is_self_signed(&self) -> bool {
+        self.issuer.raw == self.subject.raw
+    }
+
+    pub fn issuer(&self) -> &DistinguishedName {
+        &self.issuer
+    }
+
+    pub fn not_after(&self) -> &str {
+        &self.not_after
+    }
+
+    pub fn not_before(&self) -> &str {
+        &self.not_before
+    }
+
+    // subjectPublicKey, e.g. DER encoded RSAPublicKey for rsaEncryption
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    pub fn public_key_algorithm(&self) -> &str {
+        &self.public_key_algorithm
+    }
+
+    // DER encoding of the whole certificate
+    pub fn raw(&self) -> &[u8] {
+        &self.raw
+    }
+
+    // big-endian, as encoded
+    pub fn serial_number(&self) -> &[u8] {
+        &self.serial_number
+    }
+
+    pub fn signature_algorithm(&self) -> &str {
+        &self.signature_algorithm
+    }
+
+    pub fn subject(&self) -> &DistinguishedName {
+        &self.subject
+    }
+
+    // SHA-1 of the DER encoding, as shown by Windows
+    pub fn thumbprint(&self) -> Vec<u8> {
+        Sha1::digest(&self.raw).to_vec()
+    }
+
+    pub fn thumbprint_sha256(&self) -> Vec<u8> {
+        Sha256::digest(&self.raw).to_vec()
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+// AlgorithmIdentifier, parameters are ignored
+pub(in crate) fn read_algorithm(reader: &mut Reader) -> Result<String, failure::Error> {
+    let mut algorithm = reader.read_expected(TAG_SEQUENCE, "AlgorithmIdentifier")?.reader();
+    algorithm.read_expected(TAG_OBJECT_IDENTIFIER, "algorithm")?.object_identifier()
+}
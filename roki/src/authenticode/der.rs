@@ -0,0 +1,259 @@
+// a minimal reader for the subset of BER / DER used by PKCS#7 and X.509
+
+pub(in crate) const TAG_INTEGER: u8 = 0x02;
+pub(in crate) const TAG_BIT_STRING: u8 = 0x03;
+pub(in crate) const TAG_OCTET_STRING: u8 = 0x04;
+pub(in crate) const TAG_OBJECT_IDENTIFIER: u8 = 0x06;
+pub(in crate) const TAG_UTF8_STRING: u8 = 0x0C;
+pub(in crate) const TAG_PRINTABLE_STRING: u8 = 0x13;
+pub(in crate) const TAG_T61_STRING: u8 = 0x14;
+pub(in crate) const TAG_IA5_STRING: u8 = 0x16;
+pub(in crate) const TAG_UTC_TIME: u8 = 0x17;
+pub(in crate) const TAG_GENERALIZED_TIME: u8 = 0x18;
+pub(in crate) const TAG_VISIBLE_STRING: u8 = 0x1A;
+pub(in crate) const TAG_UNIVERSAL_STRING: u8 = 0x1C;
+pub(in crate) const TAG_BMP_STRING: u8 = 0x1E;
+pub(in crate) const TAG_SEQUENCE: u8 = 0x30;
+pub(in crate) const TAG_SET: u8 = 0x31;
+
+const MAX_NESTING_DEPTH: u32 = 64;
+
+#[derive(Clone, Copy, Debug)]
+pub(in crate) struct Tlv<'a> {
+    tag: u8,
+    contents: &'a [u8],
+    raw: &'a [u8],
+}
+
+impl<'a> Tlv<'a> {
+    pub(in crate) fn contents(&self) -> &'a [u8] {
+        self.contents
+    }
+
+    // the whole encoding including the identifier and length octets
+    pub(in crate) fn raw(&self) -> &'a [u8] {
+        self.raw
+    }
+
+    pub(in crate) fn tag(&self) -> u8 {
+        self.tag
+    }
+
+    pub(in crate) fn is_context(&self, number: u8) -> bool {
+        self.tag & 0xC0 == 0x80 && self.tag & 0x1F == number
+    }
+
+    pub(in crate) fn reader(&self) -> Reader<'a> {
+        Reader::new(self.contents)
+    }
+
+    pub(in crate) fn expect(self, tag: u8, name: &str) -> Result<Self, failure::Error> {
+        if self.tag != tag {
+            let msg = format!("Unexpected ASN.1 tag {:#04X} for {}, expected {:#04X}", self.tag, name, tag);
+            return Err(failure::err_msg(msg));
+        }
+
+        Ok(self)
+    }
+
+    // the unused-bits octet is dropped, only octet-aligned bit strings are used here
+    pub(in crate) fn bit_string(&self) -> Result<&'a [u8], failure::Error> {
+        match self.contents.split_first() {
+            Some((_, bits)) if self.tag == TAG_BIT_STRING => Ok(bits),
+            _ => Err(failure::err_msg("Invalid ASN.1 BIT STRING")),
+        }
+    }
+
+    // big-endian two's complement, leading zero octets are kept
+    pub(in crate) fn integer(&self) -> Result<&'a [u8], failure::Error> {
+        if self.tag != TAG_INTEGER || self.contents.is_empty() {
+            return Err(failure::err_msg("Invalid ASN.1 INTEGER"));
+        }
+
+        Ok(self.contents)
+    }
+
+    pub(in crate) fn small_integer(&self) -> Result<i64, failure::Error> {
+        let bytes = self.integer()?;
+        if bytes.len() > 8 {
+            return Err(failure::err_msg("ASN.1 INTEGER is too large"));
+        }
+
+        let initial = if bytes[0] & 0x80 != 0 { -1 } else { 0 };
+        Ok(bytes.iter().fold(initial, |value, w| value << 8 | *w as i64))
+    }
+
+    pub(in crate) fn object_identifier(&self) -> Result<String, failure::Error> {
+        if self.tag != TAG_OBJECT_IDENTIFIER || self.contents.is_empty() {
+            return Err(failure::err_msg("Invalid ASN.1 OBJECT IDENTIFIER"));
+        }
+
+        let mut arcs: Vec<u64> = Vec::new();
+        let mut value: u64 = 0;
+
+        for w in self.contents.iter() {
+            if value > u64::MAX >> 7 {
+                return Err(failure::err_msg("ASN.1 OBJECT IDENTIFIER arc is too large"));
+            }
+
+            value = value << 7 | (*w & 0x7F) as u64;
+            if *w & 0x80 == 0 {
+                if arcs.is_empty() {
+                    let first = (value / 40).min(2);
+                    arcs.push(first);
+                    arcs.push(value - first * 40);
+                } else {
+                    arcs.push(value);
+                }
+                value = 0;
+            }
+        }
+
+        Ok(arcs.iter().map(|w| w.to_string()).collect::<Vec<String>>().join("."))
+    }
+
+    pub(in crate) fn string(&self) -> Result<String, failure::Error> {
+        match self.tag {
+            TAG_UTF8_STRING | TAG_PRINTABLE_STRING | TAG_IA5_STRING | TAG_VISIBLE_STRING => Ok(String::from_utf8_lossy(self.contents).into_owned()),
+            TAG_T61_STRING => Ok(self.contents.iter().map(|w| *w as char).collect()), // treated as Latin-1
+            TAG_BMP_STRING => {
+                let units: Vec<u16> = self.contents.chunks_exact(2).map(|w| u16::from_be_bytes([w[0], w[1]])).collect();
+                Ok(String::from_utf16_lossy(&units))
+            }
+            TAG_UNIVERSAL_STRING => Ok(self
+                .contents
+                .chunks_exact(4)
+                .map(|w| std::char::from_u32(u32::from_be_bytes([w[0], w[1], w[2], w[3]])).unwrap_or(std::char::REPLACEMENT_CHARACTER))
+                .collect()),
+            _ => {
+                let msg = format!("Unsupported ASN.1 string tag {:#04X}", self.tag);
+                Err(failure::err_msg(msg))
+            }
+        }
+    }
+
+    // UTCTime or GeneralizedTime, formatted as `YYYY-MM-DD hh:mm:ss UTC`
+    pub(in crate) fn time(&self) -> Result<String, failure::Error> {
+        if !self.contents.is_ascii() {
+            return Err(failure::err_msg("Invalid ASN.1 time"));
+        }
+
+        let text = String::from_utf8_lossy(self.contents);
+        let text = text.trim_end_matches('Z');
+        let (year, rest) = match self.tag {
+            TAG_UTC_TIME if text.len() >= 10 => {
+                let year: u32 = text[0..2].parse()?;
+                (if year >= 50 { 1900 + year } else { 2000 + year }, &text[2..])
+            }
+            TAG_GENERALIZED_TIME if text.len() >= 12 => (text[0..4].parse()?, &text[4..]),
+            _ => return Err(failure::err_msg("Invalid ASN.1 time")),
+        };
+
+        // seconds are optional in UTCTime, fractions are allowed in GeneralizedTime
+        let seconds = rest.get(8..10).unwrap_or("00");
+        let fraction = rest.get(10..).filter(|w| w.starts_with('.')).unwrap_or("");
+
+        Ok(format!("{:04}-{}-{} {}:{}:{}{} UTC", year, &rest[0..2], &rest[2..4], &rest[4..6], &rest[6..8], seconds, fraction))
+    }
+}
+
+pub(in crate) struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub(in crate) fn new(data: &'a [u8]) -> Self {
+        Reader { data, offset: 0 }
+    }
+
+    pub(in crate) fn is_empty(&self) -> bool {
+        self.offset >= self.data.len()
+    }
+
+    pub(in crate) fn peek_tag(&self) -> Option<u8> {
+        self.data.get(self.offset).copied()
+    }
+
+    pub(in crate) fn read(&mut self) -> Result<Tlv<'a>, failure::Error> {
+        let (tlv, length) = Reader::read_at(self.data, self.offset, 0)?;
+        self.offset += length;
+
+        Ok(tlv)
+    }
+
+    pub(in crate) fn read_expected(&mut self, tag: u8, name: &str) -> Result<Tlv<'a>, failure::Error> {
+        self.read()?.expect(tag, name)
+    }
+
+    // reads the element only if its tag matches
+    pub(in crate) fn read_optional(&mut self, tag: u8) -> Result<Option<Tlv<'a>>, failure::Error> {
+        if self.peek_tag() == Some(tag) {
+            Ok(Some(self.read()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn read_at(data: &'a [u8], offset: usize, depth: u32) -> Result<(Tlv<'a>, usize), failure::Error> {
+        if depth > MAX_NESTING_DEPTH {
+            return Err(failure::err_msg("ASN.1 element is nested too deeply"));
+        }
+
+        let truncated = || {
+            let msg = format!("Truncated ASN.1 element at {:#X}", offset);
+            failure::err_msg(msg)
+        };
+
+        let tag = *data.get(offset).ok_or_else(truncated)?;
+        if tag & 0x1F == 0x1F {
+            let msg = format!("Unsupported high tag number form at {:#X}", offset);
+            return Err(failure::err_msg(msg));
+        }
+
+        let first = *data.get(offset + 1).ok_or_else(truncated)?;
+        let mut position = offset + 2;
+
+        let length = if first & 0x80 == 0 {
+            first as usize
+        } else if first == 0x80 {
+            // BER indefinite length, the contents end with two zero octets
+            if tag & 0x20 == 0 {
+                let msg = format!("Indefinite length primitive ASN.1 element at {:#X}", offset);
+                return Err(failure::err_msg(msg));
+            }
+
+            let start = position;
+            while data.get(position..position + 2).ok_or_else(truncated)? != [0, 0] {
+                let (_, length) = Reader::read_at(data, position, depth + 1)?;
+                position += length;
+            }
+
+            let tlv = Tlv {
+                tag,
+                contents: &data[start..position],
+                raw: &data[offset..position + 2],
+            };
+            return Ok((tlv, position + 2 - offset));
+        } else {
+            let octets = (first & 0x7F) as usize;
+            if octets > 4 {
+                let msg = format!("ASN.1 element at {:#X} is too long", offset);
+                return Err(failure::err_msg(msg));
+            }
+
+            let bytes = data.get(position..position + octets).ok_or_else(truncated)?;
+            position += octets;
+            bytes.iter().fold(0, |value, w| value << 8 | *w as usize)
+        };
+
+        let contents = data.get(position..position + length).ok_or_else(truncated)?;
+        let tlv = Tlv {
+            tag,
+            contents,
+            raw: &data[offset..position + length],
+        };
+
+        Ok((tlv, position + length - offset))
+    }
+}
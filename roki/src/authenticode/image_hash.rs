@@ -0,0 +1,240 @@
+use sha2::digest::DynDigest;
+
+use super::{AuthenticodeSignature, DigestAlgorithm, RsaPublicKey};
+use crate::constant::IMAGE_DIRECTORY_ENTRY_SECURITY;
+use crate::Executable;
+
+// offsets from the beginning of the optional header
+const CHECKSUM_OFFSET: usize = 64;
+const DATA_DIRECTORIES_OFFSET_32: usize = 96;
+const DATA_DIRECTORIES_OFFSET_64: usize = 112;
+
+const OID_RSA_ENCRYPTION: &str = "1.2.840.113549.1.1.1";
+
+#[derive(Clone, Debug)]
+pub struct ImageHashVerification {
+    algorithm: DigestAlgorithm,
+    expected: Vec<u8>,
+    computed: Vec<u8>,
+    message_digest: Option<Vec<u8>>,
+    indirect_data_digest: Option<Vec<u8>>,
+    // whether the encryptedDigest of the signer was made over the authenticated attributes with the key of the signing certificate
+    signer_signature: Result<bool, String>,
+}
+
+impl ImageHashVerification {
+    pub fn algorithm(&self) -> &DigestAlgorithm {
+        &self.algorithm
+    }
+
+    pub fn computed(&self) -> &[u8] {
+        &self.computed
+    }
+
+    // digest in SpcIndirectDataContent
+    pub fn expected(&self) -> &[u8] {
+        &self.expected
+    }
+
+    // SpcIndirectDataContent hashed with the digest algorithm of the signer, None if the algorithm is not supported
+    pub fn indirect_data_digest(&self) -> Option<&[u8]> {
+        self.indirect_data_digest.as_deref()
+    }
+
+    // the image hash is the one in SpcIndirectDataContent
+    pub fn is_image_hash_match(&self) -> bool {
+        self.expected == self.computed
+    }
+
+    // the image, SpcIndirectDataContent and the authenticated attributes are the ones signed with the key of the signing certificate,
+    // whether that certificate is trusted is not checked
+    pub fn is_match(&self) -> bool {
+        self.is_image_hash_match() && self.is_message_digest_match() && self.is_signature_valid()
+    }
+
+    // SpcIndirectDataContent is the one that the authenticated messageDigest attribute covers
+    pub fn is_message_digest_match(&self) -> bool {
+        self.message_digest.is_some() && self.message_digest == self.indirect_data_digest
+    }
+
+    // the encryptedDigest of the signer verifies with the public key of the signing certificate
+    pub fn is_signature_valid(&self) -> bool {
+        self.signer_signature == Ok(true)
+    }
+
+    // the messageDigest attribute of the signer, None if the signer has no authenticated attributes
+    pub fn message_digest(&self) -> Option<&[u8]> {
+        self.message_digest.as_deref()
+    }
+
+    // why the encryptedDigest of the signer could not be verified, e.g. the key is not RSA
+    pub fn signature_error(&self) -> Option<&str> {
+        self.signer_signature.as_ref().err().map(|w| w.as_str())
+    }
+}
+
+// file offsets of the CheckSum and the certificate table entry, left out of both the image hash and the strong name hash
+#[derive(Clone, Copy, Debug)]
+pub(in crate) struct HeaderExclusions {
+    pub(in crate) checksum: usize,
+    // None if the optional header has no certificate table entry
+    pub(in crate) security_entry: Option<usize>,
+}
+
+// both fields are checked to lie within the first `end_of_headers` bytes of the file
+pub(in crate) fn header_exclusions(executable: &Executable, end_of_headers: usize) -> Result<HeaderExclusions, failure::Error> {
+    let optional_header = match executable.optional_header() {
+        Some(optional_header) => optional_header,
+        None => return Err(failure::err_msg("The image hash requires the OPTIONAL_HEADER")),
+    };
+
+    // IMAGE_NT_SIGNATURE + IMAGE_FILE_HEADER
+    let optional_header_offset = executable.dos_header().unwrap().addr_of_nt_header() as usize + 4 + 20;
+    let checksum = optional_header_offset + CHECKSUM_OFFSET;
+    let data_directories_offset = optional_header_offset + if optional_header.is_pe32_plus() { DATA_DIRECTORIES_OFFSET_64 } else { DATA_DIRECTORIES_OFFSET_32 };
+
+    let security_entry = if optional_header.number_of_rva_and_sizes() > IMAGE_DIRECTORY_ENTRY_SECURITY {
+        Some(data_directories_offset + IMAGE_DIRECTORY_ENTRY_SECURITY as usize * 8)
+    } else {
+        None
+    };
+
+    let end_of_exclusions = security_entry.map_or(checksum + 4, |w| w + 8);
+    if end_of_headers > executable.buffer().len() || end_of_exclusions > end_of_headers {
+        let msg = format!("The headers of the image end at {:#X}, before the CheckSum and the certificate table entry", end_of_headers);
+        return Err(failure::err_msg(msg));
+    }
+
+    Ok(HeaderExclusions { checksum, security_entry })
+}
+
+pub(in crate) fn new_hasher(algorithm: &DigestAlgorithm) -> Option<Box<dyn DynDigest>> {
+    match algorithm {
+        DigestAlgorithm::Sha1 => Some(Box::new(sha1::Sha1::default())),
+        DigestAlgorithm::Sha256 => Some(Box::new(sha2::Sha256::default())),
+        DigestAlgorithm::Sha384 => Some(Box::new(sha2::Sha384::default())),
+        DigestAlgorithm::Sha512 => Some(Box::new(sha2::Sha512::default())),
+        _ => None,
+    }
+}
+
+// https://download.microsoft.com/download/9/c/5/9c5b2167-8017-4bae-9fde-d599bac8184a/Authenticode_PE.docx
+// "Calculating the PE Image Hash"
+pub fn image_hash(executable: &Executable, algorithm: &DigestAlgorithm) -> Result<Vec<u8>, failure::Error> {
+    let mut hasher = match new_hasher(algorithm) {
+        Some(hasher) => hasher,
+        None => {
+            let msg = format!("Unsupported image hash algorithm {}", algorithm);
+            return Err(failure::err_msg(msg));
+        }
+    };
+
+    let buffer = executable.buffer();
+    let size_of_headers = match executable.optional_header() {
+        Some(optional_header) => optional_header.size_of_headers() as usize,
+        None => return Err(failure::err_msg("The image hash requires the OPTIONAL_HEADER")),
+    };
+
+    let exclusions = header_exclusions(executable, size_of_headers)?;
+
+    // headers, excluding the CheckSum and the certificate table entry
+    hasher.update(&buffer[..exclusions.checksum]);
+    match exclusions.security_entry {
+        Some(security_entry) => {
+            hasher.update(&buffer[exclusions.checksum + 4..security_entry]);
+            hasher.update(&buffer[security_entry + 8..size_of_headers]);
+        }
+        None => hasher.update(&buffer[exclusions.checksum + 4..size_of_headers]),
+    }
+
+    // sections in ascending order of PointerToRawData
+    let mut sections = executable.section_headers().unwrap_or_default();
+    sections.retain(|w| w.size_of_raw_data() != 0);
+    sections.sort_by_key(|w| w.pointer_to_raw_data());
+
+    let mut end_of_sections = size_of_headers;
+    for section in sections {
+        let start = section.pointer_to_raw_data() as usize;
+        let end = start + section.size_of_raw_data() as usize;
+
+        let data = buffer.get(start..end).ok_or_else(|| {
+            let msg = format!("Raw data of the section {} exceeds the end of file", section.name());
+            failure::err_msg(msg)
+        })?;
+
+        hasher.update(data);
+        end_of_sections = end_of_sections.max(end);
+    }
+
+    // the remaining data (e.g. debug information) up to the certificate table
+    let end_of_data = match executable.security_data() {
+        Some(security_data) => security_data.offset(),
+        None => buffer.len(),
+    };
+
+    if end_of_data > end_of_sections {
+        hasher.update(&buffer[end_of_sections..end_of_data]);
+    }
+
+    // an unsigned image is zero padded to 8 bytes when the certificate table is appended
+    if executable.security_data().is_none() && !buffer.len().is_multiple_of(8) {
+        hasher.update(&[0; 8][..8 - buffer.len() % 8]);
+    }
+
+    Ok(hasher.finalize().into_vec())
+}
+
+pub fn verify_image_hash(executable: &Executable, signature: &AuthenticodeSignature) -> Result<ImageHashVerification, failure::Error> {
+    let algorithm = signature.digest_algorithm().clone();
+    let computed = image_hash(executable, &algorithm)?;
+
+    // the signature covers the authenticated attributes, which pin SpcIndirectDataContent through messageDigest
+    let indirect_data_digest = new_hasher(signature.signer().digest_algorithm()).map(|mut hasher| {
+        hasher.update(signature.indirect_data());
+        hasher.finalize().into_vec()
+    });
+
+    Ok(ImageHashVerification {
+        algorithm,
+        expected: signature.digest().to_vec(),
+        computed,
+        message_digest: signature.signer().message_digest().map(|w| w.to_vec()),
+        indirect_data_digest,
+        signer_signature: verify_signer(signature).map_err(|e| e.to_string()),
+    })
+}
+
+// RFC 2315 9.3, the encryptedDigest is over the authenticated attributes, or over the content when there are none
+fn verify_signer(signature: &AuthenticodeSignature) -> Result<bool, failure::Error> {
+    let signer = signature.signer();
+    let certificate = match signature.chain().first() {
+        Some(certificate) => *certificate,
+        None => return Err(failure::err_msg("The signing certificate is not in the certificate bag")),
+    };
+
+    if certificate.public_key_algorithm() != OID_RSA_ENCRYPTION {
+        let msg = format!("Unsupported public key algorithm {}", certificate.public_key_algorithm());
+        return Err(failure::err_msg(msg));
+    }
+
+    // rsaEncryption or one of the PKCS#1 v1.5 *WithRSAEncryption, RSASSA-PSS has parameters that are not read
+    let signature_algorithm = signer.signature_algorithm();
+    if !signature_algorithm.starts_with("1.2.840.113549.1.1.") || signature_algorithm == "1.2.840.113549.1.1.10" {
+        let msg = format!("Unsupported signature algorithm {}", signature_algorithm);
+        return Err(failure::err_msg(msg));
+    }
+
+    let mut hasher = match new_hasher(signer.digest_algorithm()) {
+        Some(hasher) => hasher,
+        None => {
+            let msg = format!("Unsupported signer digest algorithm {}", signer.digest_algorithm());
+            return Err(failure::err_msg(msg));
+        }
+    };
+
+    hasher.update(signer.authenticated_attributes().unwrap_or_else(|| signature.indirect_data()));
+    let digest = hasher.finalize().into_vec();
+
+    let key = RsaPublicKey::parse(certificate.public_key())?;
+    Ok(key.signed_digest(signer.signature(), signer.digest_algorithm()).as_deref() == Some(digest.as_slice()))
+}
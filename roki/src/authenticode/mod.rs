@@ -0,0 +1,12 @@
+mod algorithm;
+mod certificate;
+mod der;
+mod image_hash;
+mod rsa;
+mod signature;
+
+pub use algorithm::*;
+pub use certificate::*;
+pub use image_hash::*;
+pub(in crate) use rsa::RsaPublicKey;
+pub use signature::*;
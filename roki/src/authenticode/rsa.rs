@@ -0,0 +1,82 @@
+use num_bigint::BigUint;
+
+use super::der::{Reader, TAG_INTEGER, TAG_SEQUENCE};
+use super::DigestAlgorithm;
+
+// DER encoded DigestInfo up to the digest, see RFC 8017 9.2
+const DIGEST_INFO_SHA1: [u8; 15] = [0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2B, 0x0E, 0x03, 0x02, 0x1A, 0x05, 0x00, 0x04, 0x14];
+const DIGEST_INFO_SHA256: [u8; 19] = [0x30, 0x31, 0x30, 0x0D, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05, 0x00, 0x04, 0x20];
+const DIGEST_INFO_SHA384: [u8; 19] = [0x30, 0x41, 0x30, 0x0D, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02, 0x05, 0x00, 0x04, 0x30];
+const DIGEST_INFO_SHA512: [u8; 19] = [0x30, 0x51, 0x30, 0x0D, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03, 0x05, 0x00, 0x04, 0x40];
+
+// big-endian, without leading zero octets
+#[derive(Debug)]
+pub(in crate) struct RsaPublicKey {
+    pub(in crate) modulus: Vec<u8>,
+    pub(in crate) exponent: Vec<u8>,
+}
+
+impl RsaPublicKey {
+    // RSAPublicKey ::= SEQUENCE { modulus INTEGER, publicExponent INTEGER }, see RFC 8017 A.1.1
+    pub(in crate) fn parse(der: &[u8]) -> Result<Self, failure::Error> {
+        let mut key = Reader::new(der).read_expected(TAG_SEQUENCE, "RSAPublicKey")?.reader();
+        let modulus = key.read_expected(TAG_INTEGER, "modulus")?.integer()?;
+        let exponent = key.read_expected(TAG_INTEGER, "publicExponent")?.integer()?;
+
+        Ok(RsaPublicKey {
+            modulus: trim_leading_zeros(modulus).to_vec(),
+            exponent: trim_leading_zeros(exponent).to_vec(),
+        })
+    }
+
+    // RSASSA-PKCS1-v1_5, the digest that the big-endian signature carries, None if it is not a valid encoding
+    pub(in crate) fn signed_digest(&self, signature: &[u8], algorithm: &DigestAlgorithm) -> Option<Vec<u8>> {
+        if signature.len() != self.modulus.len() {
+            return None;
+        }
+
+        let modulus = BigUint::from_bytes_be(&self.modulus);
+        let message = BigUint::from_bytes_be(signature).modpow(&BigUint::from_bytes_be(&self.exponent), &modulus);
+
+        let mut encoded = message.to_bytes_be();
+        if encoded.len() < self.modulus.len() {
+            let mut padded = vec![0; self.modulus.len() - encoded.len()];
+            padded.extend_from_slice(&encoded);
+            encoded = padded;
+        }
+
+        decode_pkcs1(&encoded, algorithm).map(|w| w.to_vec())
+    }
+}
+
+// EMSA-PKCS1-v1_5, 00 01 FF .. FF 00 DigestInfo
+fn decode_pkcs1<'a>(encoded: &'a [u8], algorithm: &DigestAlgorithm) -> Option<&'a [u8]> {
+    let digest_info: &[u8] = match algorithm {
+        DigestAlgorithm::Sha1 => &DIGEST_INFO_SHA1,
+        DigestAlgorithm::Sha256 => &DIGEST_INFO_SHA256,
+        DigestAlgorithm::Sha384 => &DIGEST_INFO_SHA384,
+        DigestAlgorithm::Sha512 => &DIGEST_INFO_SHA512,
+        _ => return None,
+    };
+
+    if encoded.get(..2)? != [0x00, 0x01] {
+        return None;
+    }
+
+    let padding = encoded[2..].iter().take_while(|w| **w == 0xFF).count();
+    if padding < 8 {
+        return None;
+    }
+
+    let rest = &encoded[2 + padding..];
+    if rest.first() != Some(&0x00) || !rest[1..].starts_with(digest_info) {
+        return None;
+    }
+
+    Some(&rest[1 + digest_info.len()..])
+}
+
+fn trim_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let zeros = bytes.iter().take_while(|w| **w == 0).count();
+    &bytes[zeros..]
+}
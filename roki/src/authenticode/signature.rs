@@ -0,0 +1,461 @@
+use super::certificate::{read_algorithm, DistinguishedName, X509Certificate};
+use super::der::{Reader, Tlv, TAG_INTEGER, TAG_OBJECT_IDENTIFIER, TAG_OCTET_STRING, TAG_SEQUENCE, TAG_SET};
+use super::DigestAlgorithm;
+
+const OID_SIGNED_DATA: &str = "1.2.840.113549.1.7.2";
+const OID_CONTENT_TYPE: &str = "1.2.840.113549.1.9.3";
+const OID_MESSAGE_DIGEST: &str = "1.2.840.113549.1.9.4";
+const OID_SIGNING_TIME: &str = "1.2.840.113549.1.9.5";
+const OID_COUNTER_SIGNATURE: &str = "1.2.840.113549.1.9.6";
+const OID_TST_INFO: &str = "1.2.840.113549.1.9.16.1.4";
+const OID_SPC_INDIRECT_DATA: &str = "1.3.6.1.4.1.311.2.1.4";
+const OID_SPC_SP_OPUS_INFO: &str = "1.3.6.1.4.1.311.2.1.12";
+const OID_SPC_NESTED_SIGNATURE: &str = "1.3.6.1.4.1.311.2.4.1";
+const OID_RFC3161_TIMESTAMP: &str = "1.3.6.1.4.1.311.3.3.1";
+const MAX_NESTED_SIGNATURE_DEPTH: u32 = 8;
+
+#[derive(Clone, Debug)]
+pub struct SignerInfo {
+    // https://tools.ietf.org/html/rfc2315#section-9.2
+    version: i64,
+    issuer: Option<DistinguishedName>,
+    serial_number: Vec<u8>,
+    subject_key_identifier: Option<Vec<u8>>,
+    digest_algorithm: DigestAlgorithm,
+    signature_algorithm: String,
+    signature: Vec<u8>,
+    authenticated_attributes: Option<Vec<u8>>,
+    content_type: Option<String>,
+    message_digest: Option<Vec<u8>>,
+    signing_time: Option<String>,
+    program_name: Option<String>,
+    more_info: Option<String>,
+}
+
+impl SignerInfo {
+    // returns the unauthenticated attributes too, they are interpreted by the owner
+    fn parse<'a>(tlv: &Tlv<'a>) -> Result<(Self, Vec<(String, Tlv<'a>)>), failure::Error> {
+        let mut reader = tlv.expect(TAG_SEQUENCE, "SignerInfo")?.reader();
+        let version = reader.read_expected(TAG_INTEGER, "version")?.small_integer()?;
+
+        // IssuerAndSerialNumber, or [0] SubjectKeyIdentifier in CMS
+        let identifier = reader.read()?;
+        let (issuer, serial_number, subject_key_identifier) = if identifier.tag() == TAG_SEQUENCE {
+            let mut identifier = identifier.reader();
+            let issuer = DistinguishedName::parse(&identifier.read()?)?;
+            let serial_number = identifier.read_expected(TAG_INTEGER, "serialNumber")?.integer()?.to_vec();
+            (Some(issuer), serial_number, None)
+        } else {
+            (None, Vec::new(), Some(identifier.contents().to_vec()))
+        };
+
+        let digest_algorithm = DigestAlgorithm::from_oid(&read_algorithm(&mut reader)?);
+        let authenticated_attributes = reader.read_optional(0xA0)?;
+        let signature_algorithm = read_algorithm(&mut reader)?;
+        let signature = reader.read_expected(TAG_OCTET_STRING, "encryptedDigest")?.contents().to_vec();
+        let unauthenticated_attributes = match reader.read_optional(0xA1)? {
+            Some(attributes) => SignerInfo::parse_attributes(&attributes)?,
+            None => Vec::new(),
+        };
+
+        let mut signer = SignerInfo {
+            version,
+            issuer,
+            serial_number,
+            subject_key_identifier,
+            digest_algorithm,
+            signature_algorithm,
+            signature,
+            authenticated_attributes: None,
+            content_type: None,
+            message_digest: None,
+            signing_time: None,
+            program_name: None,
+            more_info: None,
+        };
+
+        if let Some(attributes) = authenticated_attributes {
+            for (oid, value) in SignerInfo::parse_attributes(&attributes)? {
+                match oid.as_str() {
+                    OID_CONTENT_TYPE => signer.content_type = Some(value.object_identifier()?),
+                    OID_MESSAGE_DIGEST => signer.message_digest = Some(value.expect(TAG_OCTET_STRING, "messageDigest")?.contents().to_vec()),
+                    OID_SIGNING_TIME => signer.signing_time = Some(value.time()?),
+                    OID_SPC_SP_OPUS_INFO => signer.parse_opus_info(&value)?,
+                    _ => continue,
+                }
+            }
+
+            // the signature covers the DER encoding with the universal SET tag instead of [0] IMPLICIT
+            let mut raw = attributes.raw().to_vec();
+            raw[0] = TAG_SET;
+            signer.authenticated_attributes = Some(raw);
+        }
+
+        Ok((signer, unauthenticated_attributes))
+    }
+
+    // Attribute ::= SEQUENCE { type OBJECT IDENTIFIER, values SET OF ANY }, flattened for each value
+    fn parse_attributes<'a>(tlv: &Tlv<'a>) -> Result<Vec<(String, Tlv<'a>)>, failure::Error> {
+        let mut attributes: Vec<(String, Tlv<'a>)> = Vec::new();
+        let mut reader = tlv.reader();
+
+        while !reader.is_empty() {
+            let mut attribute = reader.read_expected(TAG_SEQUENCE, "Attribute")?.reader();
+            let oid = attribute.read_expected(TAG_OBJECT_IDENTIFIER, "attrType")?.object_identifier()?;
+            let mut values = attribute.read_expected(TAG_SET, "attrValues")?.reader();
+
+            while !values.is_empty() {
+                attributes.push((oid.to_owned(), values.read()?));
+            }
+        }
+
+        Ok(attributes)
+    }
+
+    // SpcSpOpusInfo ::= SEQUENCE { programName [0] EXPLICIT SpcString OPTIONAL, moreInfo [1] EXPLICIT SpcLink OPTIONAL }
+    fn parse_opus_info(&mut self, tlv: &Tlv) -> Result<(), failure::Error> {
+        let mut reader = tlv.expect(TAG_SEQUENCE, "SpcSpOpusInfo")?.reader();
+
+        while !reader.is_empty() {
+            let field = reader.read()?;
+            let choice = field.reader().read()?;
+
+            // SpcString is [0] IMPLICIT BMPString or [1] IMPLICIT IA5String, SpcLink url is [0] IMPLICIT IA5String
+            let text = match choice.tag() {
+                0x80 if field.is_context(0) => {
+                    let units: Vec<u16> = choice.contents().chunks_exact(2).map(|w| u16::from_be_bytes([w[0], w[1]])).collect();
+                    String::from_utf16_lossy(&units)
+                }
+                0x80 | 0x81 => String::from_utf8_lossy(choice.contents()).into_owned(),
+                _ => continue,
+            };
+
+            if field.is_context(0) {
+                self.program_name = Some(text);
+            } else if field.is_context(1) {
+                self.more_info = Some(text);
+            }
+        }
+
+        Ok(())
+    }
+
+    // the DER encoded authenticated attributes that the signature covers
+    pub fn authenticated_attributes(&self) -> Option<&[u8]> {
+        self.authenticated_attributes.as_deref()
+    }
+
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    pub fn digest_algorithm(&self) -> &DigestAlgorithm {
+        &self.digest_algorithm
+    }
+
+    pub fn issuer(&self) -> Option<&DistinguishedName> {
+        self.issuer.as_ref()
+    }
+
+    pub fn message_digest(&self) -> Option<&[u8]> {
+        self.message_digest.as_deref()
+    }
+
+    // URL in SpcSpOpusInfo
+    pub fn more_info(&self) -> Option<&str> {
+        self.more_info.as_deref()
+    }
+
+    // description in SpcSpOpusInfo
+    pub fn program_name(&self) -> Option<&str> {
+        self.program_name.as_deref()
+    }
+
+    pub fn serial_number(&self) -> &[u8] {
+        &self.serial_number
+    }
+
+    // a.k.a encryptedDigest
+    pub fn signature(&self) -> &[u8] {
+        &self.signature
+    }
+
+    pub fn signature_algorithm(&self) -> &str {
+        &self.signature_algorithm
+    }
+
+    pub fn signing_time(&self) -> Option<&str> {
+        self.signing_time.as_deref()
+    }
+
+    pub fn subject_key_identifier(&self) -> Option<&[u8]> {
+        self.subject_key_identifier.as_deref()
+    }
+
+    pub fn version(&self) -> i64 {
+        self.version
+    }
+
+    // the certificate that issued the signature, followed by its issuers
+    fn chain<'a>(&self, certificates: &'a [X509Certificate]) -> Vec<&'a X509Certificate> {
+        let mut chain: Vec<&X509Certificate> = Vec::new();
+
+        let mut current = match &self.issuer {
+            Some(issuer) => certificates.iter().find(|w| w.issuer().raw() == issuer.raw() && w.serial_number() == self.serial_number.as_slice()),
+            None => None,
+        };
+
+        // each certificate appears once at most, a bag may contain loops
+        while let Some(certificate) = current {
+            if chain.iter().any(|w| w.raw() == certificate.raw()) {
+                break;
+            }
+
+            chain.push(certificate);
+            if certificate.is_self_signed() {
+                break;
+            }
+
+            current = certificates.iter().find(|w| w.subject().raw() == certificate.issuer().raw());
+        }
+
+        chain
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimestampKind {
+    CounterSignature, // PKCS#9 countersignature, a.k.a Authenticode timestamp
+    Rfc3161,
+}
+
+#[derive(Clone, Debug)]
+pub struct Timestamp {
+    kind: TimestampKind,
+    time: Option<String>,
+    signer: SignerInfo,
+    certificates: Vec<X509Certificate>,
+}
+
+impl Timestamp {
+    // certificates of the time stamping authority, or of the whole signature for countersignatures
+    pub fn certificates(&self) -> Vec<&X509Certificate> {
+        self.certificates.iter().collect()
+    }
+
+    pub fn chain(&self) -> Vec<&X509Certificate> {
+        self.signer.chain(&self.certificates)
+    }
+
+    pub fn kind(&self) -> TimestampKind {
+        self.kind
+    }
+
+    pub fn signer(&self) -> &SignerInfo {
+        &self.signer
+    }
+
+    // genTime of TSTInfo or signingTime of the countersignature
+    pub fn time(&self) -> Option<&str> {
+        self.time.as_deref()
+    }
+}
+
+struct SignedData<'a> {
+    content_type: String,
+    content: Option<Tlv<'a>>,
+    certificates: Vec<X509Certificate>,
+    signer_infos: Vec<Tlv<'a>>,
+}
+
+impl<'a> SignedData<'a> {
+    // ContentInfo ::= SEQUENCE { contentType OBJECT IDENTIFIER, content [0] EXPLICIT SignedData }
+    fn parse(tlv: &Tlv<'a>) -> Result<Self, failure::Error> {
+        let mut content_info = tlv.expect(TAG_SEQUENCE, "ContentInfo")?.reader();
+        let content_type = content_info.read_expected(TAG_OBJECT_IDENTIFIER, "contentType")?.object_identifier()?;
+        if content_type != OID_SIGNED_DATA {
+            let msg = format!("Unexpected content type {}, expected SignedData", content_type);
+            return Err(failure::err_msg(msg));
+        }
+
+        let content = content_info.read_expected(0xA0, "content")?;
+        let mut signed_data = content.reader().read_expected(TAG_SEQUENCE, "SignedData")?.reader();
+        signed_data.read_expected(TAG_INTEGER, "version")?;
+        signed_data.read_expected(TAG_SET, "digestAlgorithms")?;
+
+        let mut encapsulated = signed_data.read_expected(TAG_SEQUENCE, "contentInfo")?.reader();
+        let content_type = encapsulated.read_expected(TAG_OBJECT_IDENTIFIER, "contentType")?.object_identifier()?;
+        let content = match encapsulated.read_optional(0xA0)? {
+            Some(content) => Some(content.reader().read()?),
+            None => None,
+        };
+
+        // other certificate formats in the bag are not used by Authenticode
+        let mut certificates: Vec<X509Certificate> = Vec::new();
+        if let Some(bag) = signed_data.read_optional(0xA0)? {
+            let mut bag = bag.reader();
+            while !bag.is_empty() {
+                let certificate = bag.read()?;
+                if certificate.tag() == TAG_SEQUENCE {
+                    certificates.push(X509Certificate::parse(&certificate)?);
+                }
+            }
+        }
+
+        signed_data.read_optional(0xA1)?; // crls
+
+        let mut signer_infos: Vec<Tlv> = Vec::new();
+        let mut reader = signed_data.read_expected(TAG_SET, "signerInfos")?.reader();
+        while !reader.is_empty() {
+            signer_infos.push(reader.read()?);
+        }
+
+        Ok(SignedData {
+            content_type,
+            content,
+            certificates,
+            signer_infos,
+        })
+    }
+
+    fn first_signer_info(&self) -> Result<&Tlv<'a>, failure::Error> {
+        self.signer_infos.first().ok_or_else(|| failure::err_msg("SignedData has no SignerInfo"))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AuthenticodeSignature {
+    // https://download.microsoft.com/download/9/c/5/9c5b2167-8017-4bae-9fde-d599bac8184a/Authenticode_PE.docx
+    digest_algorithm: DigestAlgorithm,
+    digest: Vec<u8>,
+    indirect_data: Vec<u8>,
+    certificates: Vec<X509Certificate>,
+    signer: SignerInfo,
+    timestamps: Vec<Timestamp>,
+    nested_signatures: Vec<AuthenticodeSignature>,
+}
+
+impl AuthenticodeSignature {
+    pub fn parse(data: &[u8]) -> Result<Self, failure::Error> {
+        let tlv = Reader::new(data).read()?;
+        AuthenticodeSignature::parse_signature(&tlv, 0)
+    }
+
+    fn parse_signature(tlv: &Tlv, depth: u32) -> Result<Self, failure::Error> {
+        if depth > MAX_NESTED_SIGNATURE_DEPTH {
+            return Err(failure::err_msg("Authenticode signatures are nested too deeply"));
+        }
+
+        let signed_data = SignedData::parse(tlv)?;
+        if signed_data.content_type != OID_SPC_INDIRECT_DATA {
+            let msg = format!("Unexpected content type {}, expected SpcIndirectDataContent", signed_data.content_type);
+            return Err(failure::err_msg(msg));
+        }
+
+        // SpcIndirectDataContent ::= SEQUENCE { data SpcAttributeTypeAndOptionalValue, messageDigest DigestInfo }
+        let content = signed_data.content.ok_or_else(|| failure::err_msg("SignedData has no SpcIndirectDataContent"))?;
+        let mut indirect_data = content.expect(TAG_SEQUENCE, "SpcIndirectDataContent")?.reader();
+        indirect_data.read_expected(TAG_SEQUENCE, "SpcAttributeTypeAndOptionalValue")?;
+
+        let mut digest_info = indirect_data.read_expected(TAG_SEQUENCE, "DigestInfo")?.reader();
+        let digest_algorithm = DigestAlgorithm::from_oid(&read_algorithm(&mut digest_info)?);
+        let digest = digest_info.read_expected(TAG_OCTET_STRING, "digest")?.contents().to_vec();
+
+        let (signer, attributes) = SignerInfo::parse(signed_data.first_signer_info()?)?;
+
+        let mut timestamps: Vec<Timestamp> = Vec::new();
+        let mut nested_signatures: Vec<AuthenticodeSignature> = Vec::new();
+
+        for (oid, value) in attributes {
+            match oid.as_str() {
+                OID_COUNTER_SIGNATURE => {
+                    let (counter_signer, _) = SignerInfo::parse(&value)?;
+                    timestamps.push(Timestamp {
+                        kind: TimestampKind::CounterSignature,
+                        time: counter_signer.signing_time.clone(),
+                        signer: counter_signer,
+                        certificates: signed_data.certificates.clone(),
+                    });
+                }
+                OID_RFC3161_TIMESTAMP => timestamps.push(AuthenticodeSignature::parse_rfc3161_timestamp(&value)?),
+                OID_SPC_NESTED_SIGNATURE => nested_signatures.push(AuthenticodeSignature::parse_signature(&value, depth + 1)?),
+                _ => continue,
+            }
+        }
+
+        Ok(AuthenticodeSignature {
+            digest_algorithm,
+            digest,
+            indirect_data: content.contents().to_vec(),
+            certificates: signed_data.certificates,
+            signer,
+            timestamps,
+            nested_signatures,
+        })
+    }
+
+    // ContentInfo of SignedData that encapsulates TSTInfo
+    fn parse_rfc3161_timestamp(tlv: &Tlv) -> Result<Timestamp, failure::Error> {
+        let signed_data = SignedData::parse(tlv)?;
+        if signed_data.content_type != OID_TST_INFO {
+            let msg = format!("Unexpected content type {}, expected TSTInfo", signed_data.content_type);
+            return Err(failure::err_msg(msg));
+        }
+
+        // eContent is an OCTET STRING that contains DER encoded TSTInfo
+        let content = signed_data.content.ok_or_else(|| failure::err_msg("SignedData has no TSTInfo"))?;
+        let content = content.expect(TAG_OCTET_STRING, "eContent")?;
+        let mut tst_info = Reader::new(content.contents()).read_expected(TAG_SEQUENCE, "TSTInfo")?.reader();
+        tst_info.read_expected(TAG_INTEGER, "version")?;
+        tst_info.read_expected(TAG_OBJECT_IDENTIFIER, "policy")?;
+        tst_info.read_expected(TAG_SEQUENCE, "messageImprint")?;
+        tst_info.read_expected(TAG_INTEGER, "serialNumber")?;
+        let time = tst_info.read()?.time()?;
+
+        let (signer, _) = SignerInfo::parse(signed_data.first_signer_info()?)?;
+
+        Ok(Timestamp {
+            kind: TimestampKind::Rfc3161,
+            time: Some(time),
+            signer,
+            certificates: signed_data.certificates,
+        })
+    }
+
+    // all certificates in the bag, in the encoded order
+    pub fn certificates(&self) -> Vec<&X509Certificate> {
+        self.certificates.iter().collect()
+    }
+
+    // the signing certificate followed by its issuers, as far as the bag contains
+    pub fn chain(&self) -> Vec<&X509Certificate> {
+        self.signer.chain(&self.certificates)
+    }
+
+    // the image hash that was signed
+    pub fn digest(&self) -> &[u8] {
+        &self.digest
+    }
+
+    pub fn digest_algorithm(&self) -> &DigestAlgorithm {
+        &self.digest_algorithm
+    }
+
+    // contents octets of SpcIndirectDataContent, its digest is the messageDigest attribute
+    pub fn indirect_data(&self) -> &[u8] {
+        &self.indirect_data
+    }
+
+    // signatures in SPC_NESTED_SIGNATURE_OBJID, e.g. dual SHA-1 / SHA-256 signing
+    pub fn nested_signatures(&self) -> Vec<&AuthenticodeSignature> {
+        self.nested_signatures.iter().collect()
+    }
+
+    pub fn signer(&self) -> &SignerInfo {
+        &self.signer
+    }
+
+    pub fn timestamps(&self) -> Vec<&Timestamp> {
+        self.timestamps.iter().collect()
+    }
+}
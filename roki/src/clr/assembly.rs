@@ -0,0 +1,260 @@
+use std::fmt::{Display, Formatter};
+
+use sha1::{Digest, Sha1};
+
+use super::{MetadataRoot, MetadataTables, Token, TABLE_ASSEMBLY, TABLE_MEMBER_REF, TABLE_METHOD_DEF, TABLE_TYPE_REF};
+
+// https://www.ecma-international.org/publications-and-standards/standards/ecma-335/
+// II.23.1.2 AssemblyFlags
+pub const ASSEMBLY_FLAGS_PUBLIC_KEY: u32 = 0x0001;
+pub const ASSEMBLY_FLAGS_RETARGETABLE: u32 = 0x0100;
+pub const ASSEMBLY_FLAGS_WINDOWS_RUNTIME: u32 = 0x0200;
+pub const ASSEMBLY_FLAGS_DISABLE_JIT_COMPILE_OPTIMIZER: u32 = 0x4000;
+pub const ASSEMBLY_FLAGS_ENABLE_JIT_COMPILE_TRACKING: u32 = 0x8000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AssemblyVersion {
+    major: u16,
+    minor: u16,
+    build: u16,
+    revision: u16,
+}
+
+impl AssemblyVersion {
+    pub fn new(major: u16, minor: u16, build: u16, revision: u16) -> Self {
+        AssemblyVersion { major, minor, build, revision }
+    }
+
+    pub fn build(&self) -> u16 {
+        self.build
+    }
+
+    pub fn major(&self) -> u16 {
+        self.major
+    }
+
+    pub fn minor(&self) -> u16 {
+        self.minor
+    }
+
+    pub fn revision(&self) -> u16 {
+        self.revision
+    }
+}
+
+impl Display for AssemblyVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}.{}", self.major, self.minor, self.build, self.revision)
+    }
+}
+
+// II.6.2.1.3, SHA-1 of the public key, the last 8 bytes in reverse order
+pub fn public_key_token(public_key: &[u8]) -> [u8; 8] {
+    let digest = Sha1::digest(public_key);
+
+    let mut token = [0u8; 8];
+    for (i, byte) in digest.iter().rev().take(8).enumerate() {
+        token[i] = *byte;
+    }
+
+    token
+}
+
+fn display_name(name: &str, version: &AssemblyVersion, culture: &str, public_key_token: Option<&[u8]>) -> String {
+    let culture = if culture.is_empty() { "neutral" } else { culture };
+    let public_key_token = match public_key_token {
+        Some(token) => token.iter().map(|w| format!("{:02x}", w)).collect(),
+        None => "null".to_owned(),
+    };
+
+    format!("{}, Version={}, Culture={}, PublicKeyToken={}", name, version, culture, public_key_token)
+}
+
+#[derive(Debug)]
+pub struct AssemblyIdentity {
+    name: String,
+    version: AssemblyVersion,
+    culture: String,
+    flags: u32,
+    hash_alg_id: u32,
+    public_key: Vec<u8>,
+    has_signature_key: bool,
+}
+
+impl AssemblyIdentity {
+    // None for modules that are not assemblies, e.g. .netmodule files
+    pub fn parse(metadata_root: &MetadataRoot, tables: &MetadataTables) -> Result<Option<Self>, failure::Error> {
+        let assembly = match tables.assembly() {
+            Some(assembly) => assembly,
+            None => return Ok(None),
+        };
+
+        Ok(Some(AssemblyIdentity {
+            name: assembly.name().to_owned(),
+            version: AssemblyVersion::new(assembly.major_version(), assembly.minor_version(), assembly.build_number(), assembly.revision_number()),
+            culture: assembly.culture().to_owned(),
+            flags: assembly.flags(),
+            hash_alg_id: assembly.hash_alg_id(),
+            public_key: metadata_root.blobs().get(assembly.public_key())?.to_vec(),
+            has_signature_key: AssemblyIdentity::has_attribute(tables, "System.Reflection", "AssemblySignatureKeyAttribute"),
+        }))
+    }
+
+    // the type of the constructor is either referenced or defined in the assembly itself
+    fn has_attribute(tables: &MetadataTables, namespace: &str, name: &str) -> bool {
+        let assembly = Token::new(TABLE_ASSEMBLY, 1);
+
+        let type_name = |constructor: Token| -> Option<(&str, &str)> {
+            match constructor.table() {
+                TABLE_MEMBER_REF => {
+                    let class = tables.member_ref(constructor.row())?.class();
+                    if class.table() != TABLE_TYPE_REF {
+                        return None;
+                    }
+
+                    let type_ref = tables.type_ref(class.row())?;
+                    Some((type_ref.type_namespace(), type_ref.type_name()))
+                }
+                TABLE_METHOD_DEF => {
+                    let type_def = tables.type_def(tables.owner_of_method(constructor.row())?)?;
+                    Some((type_def.type_namespace(), type_def.type_name()))
+                }
+                _ => None,
+            }
+        };
+
+        tables.custom_attributes().iter().filter(|w| w.parent() == assembly).any(|w| type_name(w.r#type()) == Some((namespace, name)))
+    }
+
+    // empty for the neutral culture
+    pub fn culture(&self) -> &str {
+        &self.culture
+    }
+
+    // e.g. System.Runtime, Version=4.2.2.0, Culture=neutral, PublicKeyToken=b03f5f7f11d50a3a
+    pub fn display_name(&self) -> String {
+        display_name(&self.name, &self.version, &self.culture, self.public_key_token().as_ref().map(|w| &w[..]))
+    }
+
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    // ALG_ID of the hash of the files in the assembly manifest
+    pub fn hash_alg_id(&self) -> u32 {
+        self.hash_alg_id
+    }
+
+    // enhanced strong naming, AssemblySignatureKeyAttribute names a separate key that signs the image
+    pub fn has_signature_key(&self) -> bool {
+        self.has_signature_key
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // empty if the assembly has no strong name
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    pub fn public_key_token(&self) -> Option<[u8; 8]> {
+        if self.public_key.is_empty() {
+            return None;
+        }
+
+        Some(public_key_token(&self.public_key))
+    }
+
+    pub fn version(&self) -> &AssemblyVersion {
+        &self.version
+    }
+}
+
+#[derive(Debug)]
+pub struct AssemblyReference {
+    name: String,
+    version: AssemblyVersion,
+    culture: String,
+    flags: u32,
+    public_key_or_token: Vec<u8>,
+    hash_value: Vec<u8>,
+}
+
+impl AssemblyReference {
+    pub fn parse(metadata_root: &MetadataRoot, tables: &MetadataTables) -> Result<Vec<Self>, failure::Error> {
+        let blobs = metadata_root.blobs();
+
+        let mut references: Vec<AssemblyReference> = Vec::new();
+        for assembly_ref in tables.assembly_refs() {
+            references.push(AssemblyReference {
+                name: assembly_ref.name().to_owned(),
+                version: AssemblyVersion::new(assembly_ref.major_version(), assembly_ref.minor_version(), assembly_ref.build_number(), assembly_ref.revision_number()),
+                culture: assembly_ref.culture().to_owned(),
+                flags: assembly_ref.flags(),
+                public_key_or_token: blobs.get(assembly_ref.public_key_or_token())?.to_vec(),
+                hash_value: blobs.get(assembly_ref.hash_value())?.to_vec(),
+            });
+        }
+
+        Ok(references)
+    }
+
+    // empty for the neutral culture
+    pub fn culture(&self) -> &str {
+        &self.culture
+    }
+
+    pub fn display_name(&self) -> String {
+        display_name(&self.name, &self.version, &self.culture, self.public_key_token().as_ref().map(|w| &w[..]))
+    }
+
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    // the full public key is stored instead of the token
+    pub fn has_public_key(&self) -> bool {
+        self.flags & ASSEMBLY_FLAGS_PUBLIC_KEY != 0
+    }
+
+    pub fn hash_value(&self) -> &[u8] {
+        &self.hash_value
+    }
+
+    pub fn is_retargetable(&self) -> bool {
+        self.flags & ASSEMBLY_FLAGS_RETARGETABLE != 0
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn public_key_or_token(&self) -> &[u8] {
+        &self.public_key_or_token
+    }
+
+    // derived from the public key if the reference stores the full key
+    pub fn public_key_token(&self) -> Option<[u8; 8]> {
+        if self.public_key_or_token.is_empty() {
+            return None;
+        }
+
+        if self.has_public_key() {
+            return Some(public_key_token(&self.public_key_or_token));
+        }
+
+        let mut token = [0u8; 8];
+        if self.public_key_or_token.len() != token.len() {
+            return None;
+        }
+
+        token.copy_from_slice(&self.public_key_or_token);
+        Some(token)
+    }
+
+    pub fn version(&self) -> &AssemblyVersion {
+        &self.version
+    }
+}
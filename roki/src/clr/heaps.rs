@@ -0,0 +1,146 @@
+use scroll::{Pread, LE};
+
+use super::gread_compressed_u32;
+use crate::guid::GUID;
+
+// https://www.ecma-international.org/publications-and-standards/standards/ecma-335/
+// II.24.2.3 - II.24.2.5, indexes are relative to the beginning of each heap
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StringsHeap<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> StringsHeap<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        StringsHeap { data }
+    }
+
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    // null-terminated UTF-8, index 0 is the empty string
+    pub fn get(&self, index: u32) -> Result<&'a str, failure::Error> {
+        let bytes = match self.data.get(index as usize..) {
+            Some(bytes) => bytes,
+            None => {
+                let msg = format!("#Strings index {:#X} is out of bounds", index);
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        let length = match bytes.iter().position(|w| *w == 0) {
+            Some(length) => length,
+            None => {
+                let msg = format!("#Strings entry at {:#X} is not terminated", index);
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        std::str::from_utf8(&bytes[..length]).map_err(|_| {
+            let msg = format!("#Strings entry at {:#X} is not valid UTF-8", index);
+            failure::err_msg(msg)
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UserStringsHeap<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> UserStringsHeap<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        UserStringsHeap { data }
+    }
+
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    // compressed length, UTF-16LE and a trailing byte flagging non-ASCII characters
+    pub fn get(&self, index: u32) -> Result<String, failure::Error> {
+        let blob = read_blob(self.data, index).map_err(|_| {
+            let msg = format!("Failed to read the #US entry at {:#X}", index);
+            failure::err_msg(msg)
+        })?;
+
+        let units: Vec<u16> = blob.chunks_exact(2).map(|w| u16::from_le_bytes([w[0], w[1]])).collect();
+        Ok(String::from_utf16_lossy(&units))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GuidHeap<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> GuidHeap<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        GuidHeap { data }
+    }
+
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    // 1-based, 0 means no GUID
+    pub fn get(&self, index: u32) -> Result<Option<GUID>, failure::Error> {
+        if index == 0 {
+            return Ok(None);
+        }
+
+        let offset = (index as usize - 1) * 16;
+        let guid = self.data.pread_with::<GUID>(offset, LE).map_err(|_| {
+            let msg = format!("#GUID index {} is out of bounds", index);
+            failure::err_msg(msg)
+        })?;
+
+        Ok(Some(guid))
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len() / 16
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BlobHeap<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> BlobHeap<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        BlobHeap { data }
+    }
+
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    // compressed length followed by the bytes, index 0 is the empty blob
+    pub fn get(&self, index: u32) -> Result<&'a [u8], failure::Error> {
+        read_blob(self.data, index).map_err(|_| {
+            let msg = format!("Failed to read the #Blob entry at {:#X}", index);
+            failure::err_msg(msg)
+        })
+    }
+}
+
+fn read_blob(data: &[u8], index: u32) -> Result<&[u8], scroll::Error> {
+    let mut offset = index as usize;
+    let length = gread_compressed_u32(data, &mut offset)? as usize;
+
+    match data.get(offset..offset + length) {
+        Some(blob) => Ok(blob),
+        None => Err(scroll::Error::TooBig {
+            size: length,
+            len: data.len().saturating_sub(offset),
+        }),
+    }
+}
@@ -0,0 +1,136 @@
+use scroll::{Pread, LE};
+
+use super::{MetadataTables, ResourceSet, Token, TABLE_ASSEMBLY_REF, TABLE_FILE};
+use crate::headers::Cor20Header;
+use crate::Executable;
+
+// https://www.ecma-international.org/publications-and-standards/standards/ecma-335/
+// II.23.1.9 ManifestResourceAttributes
+pub const MANIFEST_RESOURCE_VISIBILITY_MASK: u32 = 0x0007;
+pub const MANIFEST_RESOURCE_PUBLIC: u32 = 0x0001;
+pub const MANIFEST_RESOURCE_PRIVATE: u32 = 0x0002;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ManagedResourceLocation {
+    // in the resources of the CLR header of this image
+    Embedded,
+    // in another file of the assembly, the File token
+    File(Token),
+    // in another assembly, the AssemblyRef token
+    Assembly(Token),
+}
+
+#[derive(Debug)]
+pub struct ManagedResource {
+    name: String,
+    flags: u32,
+    offset: u32,
+    location: ManagedResourceLocation,
+    data: Vec<u8>,
+}
+
+impl ManagedResource {
+    // pairs each ManifestResource row with its data in the resources of the CLR header
+    pub fn parse(executable: &Executable, cor20_header: &Cor20Header, tables: &MetadataTables) -> Result<Vec<Self>, failure::Error> {
+        let rows = tables.manifest_resources();
+        if rows.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let directory = cor20_header.resources();
+        let blob = match executable.rva_to_offset(directory.virtual_address()) {
+            Some(start) if directory.virtual_address() != 0 => executable.buffer().get(start..start + directory.size() as usize),
+            _ => None,
+        };
+
+        let mut resources: Vec<ManagedResource> = Vec::new();
+        for row in rows {
+            let implementation = row.implementation();
+            let location = match implementation.table() {
+                _ if implementation.is_null() => ManagedResourceLocation::Embedded,
+                TABLE_FILE => ManagedResourceLocation::File(implementation),
+                TABLE_ASSEMBLY_REF => ManagedResourceLocation::Assembly(implementation),
+                _ => {
+                    let msg = format!("Invalid implementation {} of the managed resource `{}`", implementation, row.name());
+                    return Err(failure::err_msg(msg));
+                }
+            };
+
+            let data = match (location, blob) {
+                (ManagedResourceLocation::Embedded, Some(blob)) => read_resource(blob, row.offset()).map_err(|_| {
+                    let msg = format!("Failed to read the managed resource `{}` at {:#X}", row.name(), row.offset());
+                    failure::err_msg(msg)
+                })?,
+                (ManagedResourceLocation::Embedded, None) => {
+                    let msg = format!("Managed resource `{}` is embedded but the image has no resources", row.name());
+                    return Err(failure::err_msg(msg));
+                }
+                _ => Vec::new(),
+            };
+
+            resources.push(ManagedResource {
+                name: row.name().to_owned(),
+                flags: row.flags(),
+                offset: row.offset(),
+                location,
+                data,
+            });
+        }
+
+        Ok(resources)
+    }
+
+    // empty unless the resource is embedded
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    pub fn is_public(&self) -> bool {
+        self.flags & MANIFEST_RESOURCE_VISIBILITY_MASK == MANIFEST_RESOURCE_PUBLIC
+    }
+
+    pub fn is_private(&self) -> bool {
+        self.flags & MANIFEST_RESOURCE_VISIBILITY_MASK == MANIFEST_RESOURCE_PRIVATE
+    }
+
+    pub fn location(&self) -> ManagedResourceLocation {
+        self.location
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // from the beginning of the resources of the CLR header
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    // Some if the data is in the .resources format of System.Resources.ResourceReader
+    pub fn resource_set(&self) -> Option<Result<ResourceSet, failure::Error>> {
+        if !ResourceSet::is_resource_set(&self.data) {
+            return None;
+        }
+
+        Some(ResourceSet::parse(&self.data))
+    }
+
+    pub fn size(&self) -> u32 {
+        self.data.len() as u32
+    }
+}
+
+// II.24.2.4, each resource is prefixed with its length
+fn read_resource(blob: &[u8], offset: u32) -> Result<Vec<u8>, scroll::Error> {
+    let mut offset = offset as usize;
+    let length = blob.gread_with::<u32>(&mut offset, LE)? as usize;
+
+    match blob.get(offset..offset + length) {
+        Some(data) => Ok(data.to_vec()),
+        None => Err(scroll::Error::TooBig { size: length, len: blob.len() - offset }),
+    }
+}
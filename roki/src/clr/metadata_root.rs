@@ -0,0 +1,223 @@
+use scroll::{Pread, LE};
+
+use super::{BlobHeap, GuidHeap, StringsHeap, UserStringsHeap};
+use crate::Executable;
+
+pub const METADATA_SIGNATURE: u32 = 0x424A_5342; // BSJB
+
+const MAX_VERSION_LENGTH: u32 = 255;
+
+#[derive(Debug)]
+pub struct StreamHeader {
+    // https://www.ecma-international.org/publications-and-standards/standards/ecma-335/
+    // II.24.2.2 Stream header
+    offset: u32, // from the beginning of the metadata root
+    size: u32,
+    name: String,
+}
+
+impl StreamHeader {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+}
+
+#[derive(Debug)]
+pub struct Stream {
+    header: StreamHeader,
+    data: Vec<u8>,
+}
+
+impl Stream {
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn header(&self) -> &StreamHeader {
+        &self.header
+    }
+
+    pub fn name(&self) -> &str {
+        &self.header.name
+    }
+}
+
+#[derive(Debug)]
+pub struct MetadataRoot {
+    // II.24.2.1 Metadata root
+    signature: u32,
+    major_version: u16,
+    minor_version: u16,
+    reserved: u32,
+    version: String,
+    flags: u16,
+    streams: Vec<Stream>,
+}
+
+impl MetadataRoot {
+    pub fn parse(executable: &Executable, rva: u32, size: u32) -> Result<Self, failure::Error> {
+        let start = match executable.rva_to_offset(rva) {
+            Some(start) => start,
+            None => {
+                let msg = format!("Failed to resolve the metadata root at RVA {:#010X}", rva);
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        let data = match executable.buffer().get(start..start + size as usize) {
+            Some(data) => data,
+            None => {
+                let msg = format!("Metadata at {:#X} ({:#X} bytes) is out of bounds", start, size);
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        MetadataRoot::parse_data(data)
+    }
+
+    // `data` starts with the BSJB signature and spans the metadata directory of the CLR header
+    pub fn parse_data(data: &[u8]) -> Result<Self, failure::Error> {
+        let read = |_: scroll::Error| failure::err_msg("Failed to read the metadata root");
+
+        let mut offset = 0;
+        let signature = data.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+        if signature != METADATA_SIGNATURE {
+            let msg = format!("Invalid metadata signature {:#010X}", signature);
+            return Err(failure::err_msg(msg));
+        }
+
+        let major_version = data.gread_with::<u16>(&mut offset, LE).map_err(read)?;
+        let minor_version = data.gread_with::<u16>(&mut offset, LE).map_err(read)?;
+        let reserved = data.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+
+        // null padded to a multiple of 4
+        let length = data.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+        if length > MAX_VERSION_LENGTH {
+            let msg = format!("Metadata version string is too long ({} bytes)", length);
+            return Err(failure::err_msg(msg));
+        }
+
+        let version = match data.get(offset..offset + length as usize) {
+            Some(version) => version.iter().take_while(|w| **w != 0).map(|w| *w as char).collect::<String>(),
+            None => return Err(failure::err_msg("Failed to read the metadata version string")),
+        };
+        offset += length as usize;
+
+        let flags = data.gread_with::<u16>(&mut offset, LE).map_err(read)?;
+        let number_of_streams = data.gread_with::<u16>(&mut offset, LE).map_err(read)?;
+
+        let mut streams: Vec<Stream> = Vec::new();
+        for _ in 0..number_of_streams {
+            let header = MetadataRoot::parse_stream_header(data, &mut offset)?;
+
+            let start = header.offset as usize;
+            let stream = match data.get(start..start + header.size as usize) {
+                Some(stream) => stream.to_vec(),
+                None => {
+                    let msg = format!("The stream {} at {:#X} ({:#X} bytes) is out of the metadata", header.name, header.offset, header.size);
+                    return Err(failure::err_msg(msg));
+                }
+            };
+
+            streams.push(Stream { header, data: stream });
+        }
+
+        Ok(MetadataRoot {
+            signature,
+            major_version,
+            minor_version,
+            reserved,
+            version,
+            flags,
+            streams,
+        })
+    }
+
+    fn parse_stream_header(data: &[u8], offset: &mut usize) -> Result<StreamHeader, failure::Error> {
+        let address = *offset;
+        let read = |_: scroll::Error| {
+            let msg = format!("Failed to read the stream header at {:#X}", address);
+            failure::err_msg(msg)
+        };
+
+        let stream_offset = data.gread_with::<u32>(offset, LE).map_err(read)?;
+        let size = data.gread_with::<u32>(offset, LE).map_err(read)?;
+
+        // ASCII, null-terminated and padded to a multiple of 4, 32 characters at most
+        let name = data.get(*offset..).unwrap_or_default().iter().take(32).take_while(|w| **w != 0).map(|w| *w as char).collect::<String>();
+        *offset += (name.len() + 4) & !3;
+
+        Ok(StreamHeader { offset: stream_offset, size, name })
+    }
+
+    pub fn blobs(&self) -> BlobHeap<'_> {
+        BlobHeap::new(self.stream_data("#Blob"))
+    }
+
+    pub fn flags(&self) -> u16 {
+        self.flags
+    }
+
+    pub fn guids(&self) -> GuidHeap<'_> {
+        GuidHeap::new(self.stream_data("#GUID"))
+    }
+
+    // #- is the uncompressed layout used by edit-and-continue images
+    pub fn is_uncompressed(&self) -> bool {
+        self.stream("#-").is_some()
+    }
+
+    pub fn major_version(&self) -> u16 {
+        self.major_version
+    }
+
+    pub fn minor_version(&self) -> u16 {
+        self.minor_version
+    }
+
+    pub fn reserved(&self) -> u32 {
+        self.reserved
+    }
+
+    pub fn signature(&self) -> u32 {
+        self.signature
+    }
+
+    // the first stream wins if the name is duplicated
+    pub fn stream(&self, name: &str) -> Option<&Stream> {
+        self.streams.iter().find(|w| w.header.name == name)
+    }
+
+    fn stream_data(&self, name: &str) -> &[u8] {
+        self.stream(name).map(|w| w.data()).unwrap_or_default()
+    }
+
+    pub fn streams(&self) -> Vec<&Stream> {
+        self.streams.iter().collect()
+    }
+
+    pub fn strings(&self) -> StringsHeap<'_> {
+        StringsHeap::new(self.stream_data("#Strings"))
+    }
+
+    pub fn tables_stream(&self) -> Option<&Stream> {
+        self.stream("#~").or_else(|| self.stream("#-"))
+    }
+
+    pub fn user_strings(&self) -> UserStringsHeap<'_> {
+        UserStringsHeap::new(self.stream_data("#US"))
+    }
+
+    // runtime version the image was built against, e.g. v4.0.30319
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+}
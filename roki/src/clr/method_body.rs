@@ -0,0 +1,300 @@
+use scroll::{Pread, LE};
+
+use super::{LocalVarSignature, MetadataRoot, MetadataTables, Token, TABLE_STAND_ALONE_SIG};
+use crate::Executable;
+
+// https://www.ecma-international.org/publications-and-standards/standards/ecma-335/
+// II.25.4 Common Intermediate Language physical layout
+pub const METHOD_HEADER_TINY_FORMAT: u8 = 0x02;
+pub const METHOD_HEADER_FAT_FORMAT: u8 = 0x03;
+pub const METHOD_HEADER_FORMAT_MASK: u8 = 0x03;
+pub const METHOD_HEADER_MORE_SECTS: u16 = 0x0008;
+pub const METHOD_HEADER_INIT_LOCALS: u16 = 0x0010;
+
+// II.25.4.5 method data section kinds
+pub const METHOD_DATA_SECTION_EH_TABLE: u8 = 0x01;
+pub const METHOD_DATA_SECTION_OPT_IL_TABLE: u8 = 0x02;
+pub const METHOD_DATA_SECTION_FAT_FORMAT: u8 = 0x40;
+pub const METHOD_DATA_SECTION_MORE_SECTS: u8 = 0x80;
+
+// II.25.4.6 exception handling clause flags
+pub const COR_ILEXCEPTION_CLAUSE_EXCEPTION: u32 = 0x0000;
+pub const COR_ILEXCEPTION_CLAUSE_FILTER: u32 = 0x0001;
+pub const COR_ILEXCEPTION_CLAUSE_FINALLY: u32 = 0x0002;
+pub const COR_ILEXCEPTION_CLAUSE_FAULT: u32 = 0x0004;
+
+// a tiny header implies a max stack of 8
+const TINY_MAX_STACK: u16 = 8;
+
+// runs of data sections are bounded so that a corrupt MoreSects chain cannot loop forever
+const MAX_DATA_SECTIONS: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExceptionClauseKind {
+    Catch,
+    Filter,
+    Finally,
+    Fault,
+    Unknown,
+}
+
+#[derive(Debug)]
+pub struct ExceptionClause {
+    flags: u32,
+    try_offset: u32,
+    try_length: u32,
+    handler_offset: u32,
+    handler_length: u32,
+    class_token_or_filter_offset: u32,
+}
+
+impl ExceptionClause {
+    pub fn class_token(&self) -> Option<Token> {
+        if self.kind() != ExceptionClauseKind::Catch {
+            return None;
+        }
+
+        Some(Token::from_raw(self.class_token_or_filter_offset))
+    }
+
+    // IL offset of the filter block
+    pub fn filter_offset(&self) -> Option<u32> {
+        if self.kind() != ExceptionClauseKind::Filter {
+            return None;
+        }
+
+        Some(self.class_token_or_filter_offset)
+    }
+
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    pub fn handler_length(&self) -> u32 {
+        self.handler_length
+    }
+
+    pub fn handler_offset(&self) -> u32 {
+        self.handler_offset
+    }
+
+    pub fn kind(&self) -> ExceptionClauseKind {
+        match self.flags {
+            COR_ILEXCEPTION_CLAUSE_EXCEPTION => ExceptionClauseKind::Catch,
+            COR_ILEXCEPTION_CLAUSE_FILTER => ExceptionClauseKind::Filter,
+            COR_ILEXCEPTION_CLAUSE_FINALLY => ExceptionClauseKind::Finally,
+            COR_ILEXCEPTION_CLAUSE_FAULT => ExceptionClauseKind::Fault,
+            _ => ExceptionClauseKind::Unknown,
+        }
+    }
+
+    pub fn try_length(&self) -> u32 {
+        self.try_length
+    }
+
+    pub fn try_offset(&self) -> u32 {
+        self.try_offset
+    }
+}
+
+#[derive(Debug)]
+pub struct MethodBody {
+    is_fat: bool,
+    flags: u16,
+    header_size: u8,
+    max_stack: u16,
+    local_var_sig_token: Token,
+    code: Vec<u8>,
+    exception_clauses: Vec<ExceptionClause>,
+}
+
+impl MethodBody {
+    // `rva` is the RVA column of a MethodDef row
+    pub fn parse(executable: &Executable, rva: u32) -> Result<Self, failure::Error> {
+        let start = match executable.rva_to_offset(rva) {
+            Some(start) => start,
+            None => {
+                let msg = format!("Failed to resolve the method body at RVA {:#010X}", rva);
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        match executable.buffer().get(start..) {
+            Some(data) => MethodBody::parse_data(data),
+            None => {
+                let msg = format!("Method body at {:#X} is out of bounds", start);
+                Err(failure::err_msg(msg))
+            }
+        }
+    }
+
+    // `data` starts with the method header and may extend beyond the body
+    pub fn parse_data(data: &[u8]) -> Result<Self, failure::Error> {
+        let read = |_: scroll::Error| failure::err_msg("Failed to read the method header");
+
+        let first = data.pread::<u8>(0).map_err(read)?;
+        let (is_fat, flags, header_size, max_stack, code_size, local_var_sig_token) = match first & METHOD_HEADER_FORMAT_MASK {
+            METHOD_HEADER_TINY_FORMAT => (false, 0, 1, TINY_MAX_STACK, (first >> 2) as u32, Token::default()),
+            METHOD_HEADER_FAT_FORMAT => {
+                let mut offset = 0;
+                let flags_and_size = data.gread_with::<u16>(&mut offset, LE).map_err(read)?;
+                let max_stack = data.gread_with::<u16>(&mut offset, LE).map_err(read)?;
+                let code_size = data.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+                let local_var_sig_token = data.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+
+                // size of the header in dwords, 3 for the current format
+                let header_size = ((flags_and_size >> 12) * 4) as u8;
+                if (header_size as usize) < offset {
+                    let msg = format!("Invalid fat method header size {:#X}", header_size);
+                    return Err(failure::err_msg(msg));
+                }
+
+                (true, flags_and_size & 0x0FFF, header_size, max_stack, code_size, Token::from_raw(local_var_sig_token))
+            }
+            _ => {
+                let msg = format!("Invalid method header format {:#04X}", first);
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        let code_start = header_size as usize;
+        let code_end = code_start + code_size as usize;
+        let code = match data.get(code_start..code_end) {
+            Some(code) => code.to_vec(),
+            None => {
+                let msg = format!("Method code ({:#X} bytes) is out of bounds", code_size);
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        let exception_clauses = if flags & METHOD_HEADER_MORE_SECTS != 0 { read_data_sections(data, code_end)? } else { Vec::new() };
+
+        Ok(MethodBody {
+            is_fat,
+            flags,
+            header_size,
+            max_stack,
+            local_var_sig_token,
+            code,
+            exception_clauses,
+        })
+    }
+
+    pub fn code(&self) -> &[u8] {
+        &self.code
+    }
+
+    pub fn code_size(&self) -> u32 {
+        self.code.len() as u32
+    }
+
+    pub fn exception_clauses(&self) -> Vec<&ExceptionClause> {
+        self.exception_clauses.iter().collect()
+    }
+
+    // 0 for tiny headers
+    pub fn flags(&self) -> u16 {
+        self.flags
+    }
+
+    pub fn header_size(&self) -> u8 {
+        self.header_size
+    }
+
+    pub fn init_locals(&self) -> bool {
+        self.flags & METHOD_HEADER_INIT_LOCALS != 0
+    }
+
+    pub fn is_fat(&self) -> bool {
+        self.is_fat
+    }
+
+    // StandAloneSig token of the locals, null if the method has none
+    pub fn local_var_sig_token(&self) -> Token {
+        self.local_var_sig_token
+    }
+
+    // decodes the StandAloneSig of the locals, None if the method has none
+    pub fn locals(&self, metadata_root: &MetadataRoot, tables: &MetadataTables) -> Result<Option<LocalVarSignature>, failure::Error> {
+        let token = self.local_var_sig_token;
+        if token.is_null() {
+            return Ok(None);
+        }
+
+        let stand_alone_sig = match tables.stand_alone_sig(token.row()) {
+            Some(stand_alone_sig) if token.table() == TABLE_STAND_ALONE_SIG => stand_alone_sig,
+            _ => {
+                let msg = format!("Invalid local variable signature token {}", token);
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        let blob = metadata_root.blobs().get(stand_alone_sig.signature())?;
+        Ok(Some(LocalVarSignature::parse(blob)?))
+    }
+
+    pub fn max_stack(&self) -> u16 {
+        self.max_stack
+    }
+}
+
+// II.25.4.5, the sections follow the code aligned to 4 bytes
+fn read_data_sections(data: &[u8], code_end: usize) -> Result<Vec<ExceptionClause>, failure::Error> {
+    let read = |_: scroll::Error| failure::err_msg("Failed to read the method data sections");
+
+    let mut exception_clauses: Vec<ExceptionClause> = Vec::new();
+    let mut offset = (code_end + 3) & !3;
+    for _ in 0..MAX_DATA_SECTIONS {
+        let start = offset;
+        let kind = data.gread::<u8>(&mut offset).map_err(read)?;
+
+        let is_fat = kind & METHOD_DATA_SECTION_FAT_FORMAT != 0;
+        // the kind byte is followed by a 3 byte size in the fat format
+        let data_size = if is_fat {
+            (data.pread_with::<u32>(start, LE).map_err(read)? >> 8) as usize
+        } else {
+            data.pread::<u8>(offset).map_err(read)? as usize
+        };
+
+        if data_size < 4 {
+            let msg = format!("Invalid method data section size {:#X}", data_size);
+            return Err(failure::err_msg(msg));
+        }
+
+        if kind & METHOD_DATA_SECTION_EH_TABLE != 0 {
+            let clause_size = if is_fat { 24 } else { 12 };
+            let mut clause_offset = start + 4;
+            for _ in 0..(data_size - 4) / clause_size {
+                let clause = if is_fat {
+                    ExceptionClause {
+                        flags: data.gread_with::<u32>(&mut clause_offset, LE).map_err(read)?,
+                        try_offset: data.gread_with::<u32>(&mut clause_offset, LE).map_err(read)?,
+                        try_length: data.gread_with::<u32>(&mut clause_offset, LE).map_err(read)?,
+                        handler_offset: data.gread_with::<u32>(&mut clause_offset, LE).map_err(read)?,
+                        handler_length: data.gread_with::<u32>(&mut clause_offset, LE).map_err(read)?,
+                        class_token_or_filter_offset: data.gread_with::<u32>(&mut clause_offset, LE).map_err(read)?,
+                    }
+                } else {
+                    ExceptionClause {
+                        flags: data.gread_with::<u16>(&mut clause_offset, LE).map_err(read)? as u32,
+                        try_offset: data.gread_with::<u16>(&mut clause_offset, LE).map_err(read)? as u32,
+                        try_length: data.gread::<u8>(&mut clause_offset).map_err(read)? as u32,
+                        handler_offset: data.gread_with::<u16>(&mut clause_offset, LE).map_err(read)? as u32,
+                        handler_length: data.gread::<u8>(&mut clause_offset).map_err(read)? as u32,
+                        class_token_or_filter_offset: data.gread_with::<u32>(&mut clause_offset, LE).map_err(read)?,
+                    }
+                };
+
+                exception_clauses.push(clause);
+            }
+        }
+
+        if kind & METHOD_DATA_SECTION_MORE_SECTS == 0 {
+            break;
+        }
+
+        offset = (start + data_size + 3) & !3;
+    }
+
+    Ok(exception_clauses)
+}
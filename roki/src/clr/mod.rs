@@ -0,0 +1,52 @@
+mod assembly;
+mod heaps;
+mod il;
+mod managed_resource;
+mod metadata_root;
+mod method_body;
+mod native_header;
+mod pinvoke;
+mod ready_to_run;
+mod resource_set;
+mod signature;
+mod strong_name;
+mod tables;
+mod token;
+
+pub use assembly::*;
+pub use heaps::*;
+pub use il::*;
+pub use managed_resource::*;
+pub use metadata_root::*;
+pub use method_body::*;
+pub use native_header::*;
+pub use pinvoke::*;
+pub use ready_to_run::*;
+pub use resource_set::*;
+pub use signature::*;
+pub use strong_name::*;
+pub use tables::*;
+pub use token::*;
+
+use scroll::Pread;
+
+// ECMA-335 II.23.2 compressed unsigned integer, 1, 2 or 4 bytes big-endian
+pub(in crate) fn gread_compressed_u32(data: &[u8], offset: &mut usize) -> Result<u32, scroll::Error> {
+    let first = data.gread::<u8>(offset)? as u32;
+
+    if first & 0x80 == 0 {
+        Ok(first)
+    } else if first & 0xC0 == 0x80 {
+        let second = data.gread::<u8>(offset)? as u32;
+        Ok((first & 0x3F) << 8 | second)
+    } else if first & 0xE0 == 0xC0 {
+        let mut value = first & 0x1F;
+        for _ in 0..3 {
+            value = value << 8 | data.gread::<u8>(offset)? as u32;
+        }
+
+        Ok(value)
+    } else {
+        Err(scroll::Error::Custom(format!("Invalid compressed integer {:#04X}", first)))
+    }
+}
@@ -0,0 +1,112 @@
+use scroll::{Pread, LE};
+
+use super::ReadyToRunHeader;
+use crate::directories::DataDirectory;
+use crate::headers::Cor20Header;
+use crate::Executable;
+
+// CORCOMPILE_HEADER of the .NET Framework 4 native images, see corcompile.h
+pub const CORCOMPILE_HEADER_DIRECTORIES: [&str; 13] = [
+    "EEInfoTable",
+    "HelperTable",
+    "ImportSections",
+    "ImportTable",
+    "StubsData",
+    "VersionInfo",
+    "Dependencies",
+    "DebugMap",
+    "ModuleImage",
+    "CodeManagerTable",
+    "ProfileDataList",
+    "ManifestMetaData",
+    "VirtualSectionsTable",
+];
+
+// CORCOMPILE_HEADER of images precompiled by ngen, only the leading directories are read as the rest depends on the runtime version
+#[derive(Debug)]
+pub struct NGenHeader {
+    rva: u32,
+    size: u32,
+    directories: Vec<DataDirectory>,
+}
+
+impl NGenHeader {
+    pub fn parse(executable: &Executable, directory: &DataDirectory) -> Result<Self, failure::Error> {
+        let read = |_: scroll::Error| failure::err_msg("Failed to read the NGen header");
+
+        let mut offset = match executable.rva_to_offset(directory.virtual_address()) {
+            Some(offset) => offset,
+            None => {
+                let msg = format!("Failed to resolve the NGen header at RVA {:#010X}", directory.virtual_address());
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        let mut directories: Vec<DataDirectory> = Vec::new();
+        for _ in CORCOMPILE_HEADER_DIRECTORIES.iter() {
+            directories.push(executable.buffer().gread_with::<DataDirectory>(&mut offset, LE).map_err(read)?);
+        }
+
+        Ok(NGenHeader {
+            rva: directory.virtual_address(),
+            size: directory.size(),
+            directories,
+        })
+    }
+
+    // paired with the names of CORCOMPILE_HEADER_DIRECTORIES
+    pub fn directories(&self) -> Vec<(&'static str, &DataDirectory)> {
+        CORCOMPILE_HEADER_DIRECTORIES.iter().copied().zip(self.directories.iter()).collect()
+    }
+
+    pub fn rva(&self) -> u32 {
+        self.rva
+    }
+
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    // CORCOMPILE_VERSION_INFO, the runtime and the IL image the code was compiled against
+    pub fn version_info(&self) -> &DataDirectory {
+        &self.directories[5]
+    }
+}
+
+// the precompiled code referenced by the managed native header of the CLR header
+#[derive(Debug)]
+pub enum NativeHeader {
+    ReadyToRun(ReadyToRunHeader),
+    NGen(NGenHeader),
+    // neither a READYTORUN_HEADER nor an ngen image, e.g. a header of another runtime
+    Unknown(DataDirectory),
+}
+
+impl NativeHeader {
+    // None for IL-only images
+    pub fn parse(executable: &Executable, cor20_header: &Cor20Header) -> Result<Option<Self>, failure::Error> {
+        let directory = cor20_header.managed_native_header();
+        if directory.virtual_address() == 0 || directory.size() == 0 {
+            return Ok(None);
+        }
+
+        if ReadyToRunHeader::is_ready_to_run(executable, directory.virtual_address()) {
+            return Ok(Some(NativeHeader::ReadyToRun(ReadyToRunHeader::parse(executable, directory.virtual_address())?)));
+        }
+
+        // ngen marks its images as IL libraries, the CORCOMPILE_HEADER has no signature
+        if cor20_header.is_il_library() {
+            return Ok(Some(NativeHeader::NGen(NGenHeader::parse(executable, directory)?)));
+        }
+
+        Ok(Some(NativeHeader::Unknown(*directory)))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            NativeHeader::ReadyToRun(_) => "ReadyToRun",
+            NativeHeader::NGen(_) => "NGen",
+            NativeHeader::Unknown(_) => "unknown",
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use super::{MetadataTables, Token, TABLE_FIELD, TABLE_METHOD_DEF};
+
+// https://www.ecma-international.org/publications-and-standards/standards/ecma-335/
+// II.23.1.8 PInvokeAttributes
+pub const PINVOKE_NO_MANGLE: u16 = 0x0001;
+pub const PINVOKE_CHAR_SET_MASK: u16 = 0x0006;
+pub const PINVOKE_CHAR_SET_NOT_SPEC: u16 = 0x0000;
+pub const PINVOKE_CHAR_SET_ANSI: u16 = 0x0002;
+pub const PINVOKE_CHAR_SET_UNICODE: u16 = 0x0004;
+pub const PINVOKE_CHAR_SET_AUTO: u16 = 0x0006;
+pub const PINVOKE_SUPPORTS_LAST_ERROR: u16 = 0x0040;
+pub const PINVOKE_CALL_CONV_MASK: u16 = 0x0700;
+pub const PINVOKE_CALL_CONV_PLATFORMAPI: u16 = 0x0100;
+pub const PINVOKE_CALL_CONV_CDECL: u16 = 0x0200;
+pub const PINVOKE_CALL_CONV_STDCALL: u16 = 0x0300;
+pub const PINVOKE_CALL_CONV_THISCALL: u16 = 0x0400;
+pub const PINVOKE_CALL_CONV_FASTCALL: u16 = 0x0500;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PInvokeCallingConvention {
+    // stdcall on Windows x86, the platform default elsewhere
+    PlatformApi,
+    Cdecl,
+    Stdcall,
+    Thiscall,
+    Fastcall,
+    Unknown(u16),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PInvokeCharSet {
+    NotSpecified,
+    Ansi,
+    Unicode,
+    Auto,
+}
+
+// a method or a field whose implementation is in a native DLL
+#[derive(Debug)]
+pub struct PInvokeImport {
+    module: String,
+    entry_point: String,
+    member: Token,
+    member_name: String,
+    flags: u16,
+}
+
+impl PInvokeImport {
+    // joins each ImplMap row with its ModuleRef and its MethodDef or Field
+    pub fn parse(tables: &MetadataTables) -> Result<Vec<Self>, failure::Error> {
+        let mut imports: Vec<PInvokeImport> = Vec::new();
+        for impl_map in tables.impl_maps() {
+            let member = impl_map.member_forwarded();
+            let member_name = match member.table() {
+                TABLE_METHOD_DEF => tables.method_def(member.row()).map(|w| w.name()),
+                TABLE_FIELD => tables.field(member.row()).map(|w| w.name()),
+                _ => None,
+            };
+
+            let member_name = match member_name {
+                Some(member_name) => member_name,
+                None => {
+                    let msg = format!("P/Invoke member {} is out of bounds", member);
+                    return Err(failure::err_msg(msg));
+                }
+            };
+
+            let module = match tables.module_ref(impl_map.import_scope()) {
+                Some(module_ref) => module_ref.name(),
+                None => {
+                    let msg = format!("P/Invoke module #{} of `{}` is out of bounds", impl_map.import_scope(), member_name);
+                    return Err(failure::err_msg(msg));
+                }
+            };
+
+            // the managed name is used when the import name is omitted
+            let entry_point = if impl_map.import_name().is_empty() { member_name } else { impl_map.import_name() };
+
+            imports.push(PInvokeImport {
+                module: module.to_owned(),
+                entry_point: entry_point.to_owned(),
+                member,
+                member_name: member_name.to_owned(),
+                flags: impl_map.mapping_flags(),
+            });
+        }
+
+        Ok(imports)
+    }
+
+    pub fn calling_convention(&self) -> PInvokeCallingConvention {
+        match self.flags & PINVOKE_CALL_CONV_MASK {
+            PINVOKE_CALL_CONV_PLATFORMAPI => PInvokeCallingConvention::PlatformApi,
+            PINVOKE_CALL_CONV_CDECL => PInvokeCallingConvention::Cdecl,
+            PINVOKE_CALL_CONV_STDCALL => PInvokeCallingConvention::Stdcall,
+            PINVOKE_CALL_CONV_THISCALL => PInvokeCallingConvention::Thiscall,
+            PINVOKE_CALL_CONV_FASTCALL => PInvokeCallingConvention::Fastcall,
+            value => PInvokeCallingConvention::Unknown(value),
+        }
+    }
+
+    pub fn char_set(&self) -> PInvokeCharSet {
+        match self.flags & PINVOKE_CHAR_SET_MASK {
+            PINVOKE_CHAR_SET_ANSI => PInvokeCharSet::Ansi,
+            PINVOKE_CHAR_SET_UNICODE => PInvokeCharSet::Unicode,
+            PINVOKE_CHAR_SET_AUTO => PInvokeCharSet::Auto,
+            _ => PInvokeCharSet::NotSpecified,
+        }
+    }
+
+    // name of the native function
+    pub fn entry_point(&self) -> &str {
+        &self.entry_point
+    }
+
+    pub fn flags(&self) -> u16 {
+        self.flags
+    }
+
+    // the MethodDef or the Field token
+    pub fn member(&self) -> Token {
+        self.member
+    }
+
+    pub fn member_name(&self) -> &str {
+        &self.member_name
+    }
+
+    // name of the DLL as written in the ModuleRef, the extension may be omitted
+    pub fn module(&self) -> &str {
+        &self.module
+    }
+
+    // the runtime does not probe for the A or W suffixed names
+    pub fn no_mangle(&self) -> bool {
+        self.flags & PINVOKE_NO_MANGLE != 0
+    }
+
+    pub fn supports_last_error(&self) -> bool {
+        self.flags & PINVOKE_SUPPORTS_LAST_ERROR != 0
+    }
+}
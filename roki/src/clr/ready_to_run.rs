@@ -0,0 +1,716 @@
+use scroll::{Pread, LE};
+
+use super::{Token, TABLE_EXPORTED_TYPE, TABLE_METHOD_DEF, TABLE_TYPE_DEF};
+use crate::constant::{IMAGE_FILE_MACHINE_AMD64, IMAGE_FILE_MACHINE_ARM64, IMAGE_FILE_MACHINE_ARMNT, IMAGE_FILE_MACHINE_I386};
+use crate::directories::DataDirectory;
+use crate::Executable;
+
+// https://github.com/dotnet/runtime/blob/main/docs/design/coreclr/botr/readytorun-format.md
+pub const READYTORUN_SIGNATURE: u32 = 0x0052_5452; // 'RTR'
+
+// READYTORUN_FLAG
+pub const READYTORUN_FLAG_PLATFORM_NEUTRAL_SOURCE: u32 = 0x0000_0001;
+pub const READYTORUN_FLAG_SKIP_TYPE_VALIDATION: u32 = 0x0000_0002;
+pub const READYTORUN_FLAG_PARTIAL: u32 = 0x0000_0004;
+pub const READYTORUN_FLAG_NONSHARED_PINVOKE_STUBS: u32 = 0x0000_0008;
+pub const READYTORUN_FLAG_EMBEDDED_MSIL: u32 = 0x0000_0010;
+pub const READYTORUN_FLAG_COMPONENT: u32 = 0x0000_0020;
+pub const READYTORUN_FLAG_MULTIMODULE_VERSION_BUBBLE: u32 = 0x0000_0040;
+pub const READYTORUN_FLAG_UNRELATED_R2R_CODE: u32 = 0x0000_0080;
+
+pub const READYTORUN_FLAGS: [(u32, &str); 8] = [
+    (READYTORUN_FLAG_PLATFORM_NEUTRAL_SOURCE, "READYTORUN_FLAG_PLATFORM_NEUTRAL_SOURCE"),
+    (READYTORUN_FLAG_SKIP_TYPE_VALIDATION, "READYTORUN_FLAG_SKIP_TYPE_VALIDATION"),
+    (READYTORUN_FLAG_PARTIAL, "READYTORUN_FLAG_PARTIAL"),
+    (READYTORUN_FLAG_NONSHARED_PINVOKE_STUBS, "READYTORUN_FLAG_NONSHARED_PINVOKE_STUBS"),
+    (READYTORUN_FLAG_EMBEDDED_MSIL, "READYTORUN_FLAG_EMBEDDED_MSIL"),
+    (READYTORUN_FLAG_COMPONENT, "READYTORUN_FLAG_COMPONENT"),
+    (READYTORUN_FLAG_MULTIMODULE_VERSION_BUBBLE, "READYTORUN_FLAG_MULTIMODULE_VERSION_BUBBLE"),
+    (READYTORUN_FLAG_UNRELATED_R2R_CODE, "READYTORUN_FLAG_UNRELATED_R2R_CODE"),
+];
+
+// ReadyToRunSectionType
+pub const READYTORUN_SECTION_COMPILER_IDENTIFIER: u32 = 100;
+pub const READYTORUN_SECTION_IMPORT_SECTIONS: u32 = 101;
+pub const READYTORUN_SECTION_RUNTIME_FUNCTIONS: u32 = 102;
+pub const READYTORUN_SECTION_METHODDEF_ENTRYPOINTS: u32 = 103;
+pub const READYTORUN_SECTION_EXCEPTION_INFO: u32 = 104;
+pub const READYTORUN_SECTION_DEBUG_INFO: u32 = 105;
+pub const READYTORUN_SECTION_DELAYLOAD_METHODCALL_THUNKS: u32 = 106;
+pub const READYTORUN_SECTION_AVAILABLE_TYPES: u32 = 108;
+pub const READYTORUN_SECTION_INSTANCE_METHOD_ENTRYPOINTS: u32 = 109;
+pub const READYTORUN_SECTION_INLINING_INFO: u32 = 110;
+pub const READYTORUN_SECTION_PROFILEDATA_INFO: u32 = 111;
+pub const READYTORUN_SECTION_MANIFEST_METADATA: u32 = 112;
+pub const READYTORUN_SECTION_ATTRIBUTEPRESENCE: u32 = 113;
+pub const READYTORUN_SECTION_INLINING_INFO2: u32 = 114;
+pub const READYTORUN_SECTION_COMPONENT_ASSEMBLIES: u32 = 115;
+pub const READYTORUN_SECTION_OWNER_COMPOSITE_EXECUTABLE: u32 = 116;
+pub const READYTORUN_SECTION_PGO_INSTRUMENTATION_DATA: u32 = 117;
+pub const READYTORUN_SECTION_MANIFEST_ASSEMBLY_MVIDS: u32 = 118;
+pub const READYTORUN_SECTION_CROSS_MODULE_INLINE_INFO: u32 = 119;
+pub const READYTORUN_SECTION_HOT_COLD_MAP: u32 = 120;
+pub const READYTORUN_SECTION_METHOD_IS_GENERIC_MAP: u32 = 121;
+pub const READYTORUN_SECTION_ENCLOSING_TYPE_MAP: u32 = 122;
+pub const READYTORUN_SECTION_TYPE_GENERIC_INFO_MAP: u32 = 123;
+
+// READYTORUN_IMPORT_SECTION_FLAGS
+pub const READYTORUN_IMPORT_SECTION_FLAGS_EAGER: u16 = 0x0001;
+pub const READYTORUN_IMPORT_SECTION_FLAGS_PCODE: u16 = 0x0004;
+
+// READYTORUN_IMPORT_SECTION_TYPE
+pub const READYTORUN_IMPORT_SECTION_TYPE_UNKNOWN: u8 = 0;
+pub const READYTORUN_IMPORT_SECTION_TYPE_STUB_DISPATCH: u8 = 2;
+pub const READYTORUN_IMPORT_SECTION_TYPE_STRING_HANDLE: u8 = 3;
+pub const READYTORUN_IMPORT_SECTION_TYPE_ILBODYFIXUPS: u8 = 7;
+
+// the machine of images compiled for other operating systems is xored with one of these
+pub const READYTORUN_MACHINE_OS_OVERRIDE_APPLE: u16 = 0x4644;
+pub const READYTORUN_MACHINE_OS_OVERRIDE_FREEBSD: u16 = 0xADC4;
+pub const READYTORUN_MACHINE_OS_OVERRIDE_LINUX: u16 = 0x7B79;
+pub const READYTORUN_MACHINE_OS_OVERRIDE_NETBSD: u16 = 0x1993;
+pub const READYTORUN_MACHINE_OS_OVERRIDE_SUNOS: u16 = 0x1992;
+
+// the symbol exported by composite images, which have no CLR header of their own
+pub const READYTORUN_COMPOSITE_HEADER_EXPORT: &str = "RTR_HEADER";
+
+// Signature, MajorVersion, MinorVersion, Flags and NumberOfSections
+const HEADER_SIZE: usize = 16;
+
+// Type and the DataDirectory
+const SECTION_SIZE: usize = 12;
+
+// sections are bounded so that a corrupt header cannot allocate without limit
+const MAX_SECTIONS: u32 = 256;
+
+// entries of a NativeArray are grouped in blocks of 16
+const NATIVE_ARRAY_BLOCK_SIZE: u32 = 16;
+
+pub fn ready_to_run_section_name(section_type: u32) -> Option<&'static str> {
+    let name = match section_type {
+        READYTORUN_SECTION_COMPILER_IDENTIFIER => "CompilerIdentifier",
+        READYTORUN_SECTION_IMPORT_SECTIONS => "ImportSections",
+        READYTORUN_SECTION_RUNTIME_FUNCTIONS => "RuntimeFunctions",
+        READYTORUN_SECTION_METHODDEF_ENTRYPOINTS => "MethodDefEntryPoints",
+        READYTORUN_SECTION_EXCEPTION_INFO => "ExceptionInfo",
+        READYTORUN_SECTION_DEBUG_INFO => "DebugInfo",
+        READYTORUN_SECTION_DELAYLOAD_METHODCALL_THUNKS => "DelayLoadMethodCallThunks",
+        READYTORUN_SECTION_AVAILABLE_TYPES => "AvailableTypes",
+        READYTORUN_SECTION_INSTANCE_METHOD_ENTRYPOINTS => "InstanceMethodEntryPoints",
+        READYTORUN_SECTION_INLINING_INFO => "InliningInfo",
+        READYTORUN_SECTION_PROFILEDATA_INFO => "ProfileDataInfo",
+        READYTORUN_SECTION_MANIFEST_METADATA => "ManifestMetadata",
+        READYTORUN_SECTION_ATTRIBUTEPRESENCE => "AttributePresence",
+        READYTORUN_SECTION_INLINING_INFO2 => "InliningInfo2",
+        READYTORUN_SECTION_COMPONENT_ASSEMBLIES => "ComponentAssemblies",
+        READYTORUN_SECTION_OWNER_COMPOSITE_EXECUTABLE => "OwnerCompositeExecutable",
+        READYTORUN_SECTION_PGO_INSTRUMENTATION_DATA => "PgoInstrumentationData",
+        READYTORUN_SECTION_MANIFEST_ASSEMBLY_MVIDS => "ManifestAssemblyMvids",
+        READYTORUN_SECTION_CROSS_MODULE_INLINE_INFO => "CrossModuleInlineInfo",
+        READYTORUN_SECTION_HOT_COLD_MAP => "HotColdMap",
+        READYTORUN_SECTION_METHOD_IS_GENERIC_MAP => "MethodIsGenericMap",
+        READYTORUN_SECTION_ENCLOSING_TYPE_MAP => "EnclosingTypeMap",
+        READYTORUN_SECTION_TYPE_GENERIC_INFO_MAP => "TypeGenericInfoMap",
+        _ => return None,
+    };
+
+    Some(name)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TargetOs {
+    Windows,
+    Linux,
+    Apple,
+    FreeBsd,
+    NetBsd,
+    SunOs,
+}
+
+impl TargetOs {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TargetOs::Windows => "Windows",
+            TargetOs::Linux => "Linux",
+            TargetOs::Apple => "Apple",
+            TargetOs::FreeBsd => "FreeBSD",
+            TargetOs::NetBsd => "NetBSD",
+            TargetOs::SunOs => "SunOS",
+        }
+    }
+}
+
+// splits the machine of the file header of a ReadyToRun image into the actual machine and the target OS
+pub fn ready_to_run_machine(machine: u16) -> (u16, TargetOs) {
+    let overrides = [
+        (0, TargetOs::Windows),
+        (READYTORUN_MACHINE_OS_OVERRIDE_LINUX, TargetOs::Linux),
+        (READYTORUN_MACHINE_OS_OVERRIDE_APPLE, TargetOs::Apple),
+        (READYTORUN_MACHINE_OS_OVERRIDE_FREEBSD, TargetOs::FreeBsd),
+        (READYTORUN_MACHINE_OS_OVERRIDE_NETBSD, TargetOs::NetBsd),
+        (READYTORUN_MACHINE_OS_OVERRIDE_SUNOS, TargetOs::SunOs),
+    ];
+
+    for (mask, os) in overrides.iter() {
+        match machine ^ mask {
+            actual @ (IMAGE_FILE_MACHINE_I386 | IMAGE_FILE_MACHINE_AMD64 | IMAGE_FILE_MACHINE_ARMNT | IMAGE_FILE_MACHINE_ARM64) => return (actual, *os),
+            _ => continue,
+        }
+    }
+
+    (machine, TargetOs::Windows)
+}
+
+#[derive(Debug)]
+pub struct ReadyToRunSection {
+    section_type: u32,
+    section: DataDirectory,
+}
+
+impl ReadyToRunSection {
+    pub fn name(&self) -> Option<&'static str> {
+        ready_to_run_section_name(self.section_type)
+    }
+
+    pub fn section(&self) -> &DataDirectory {
+        &self.section
+    }
+
+    pub fn section_type(&self) -> u32 {
+        self.section_type
+    }
+}
+
+// READYTORUN_IMPORT_SECTION, a run of cells fixed up by the runtime
+#[derive(Debug)]
+pub struct ImportSection {
+    section: DataDirectory,
+    flags: u16,
+    kind: u8,
+    entry_size: u8,
+    signatures: u32,
+    auxiliary_data: u32,
+}
+
+impl ImportSection {
+    pub fn auxiliary_data(&self) -> u32 {
+        self.auxiliary_data
+    }
+
+    pub fn entry_count(&self) -> u32 {
+        if self.entry_size == 0 {
+            return 0;
+        }
+
+        self.section.size() / self.entry_size as u32
+    }
+
+    pub fn entry_size(&self) -> u8 {
+        self.entry_size
+    }
+
+    pub fn flags(&self) -> u16 {
+        self.flags
+    }
+
+    pub fn is_eager(&self) -> bool {
+        self.flags & READYTORUN_IMPORT_SECTION_FLAGS_EAGER != 0
+    }
+
+    // READYTORUN_IMPORT_SECTION_TYPE
+    pub fn kind(&self) -> u8 {
+        self.kind
+    }
+
+    pub fn section(&self) -> &DataDirectory {
+        &self.section
+    }
+
+    // RVA of the array of signature RVAs, one per cell
+    pub fn signatures(&self) -> u32 {
+        self.signatures
+    }
+}
+
+// RUNTIME_FUNCTION, only x64 records the end address
+#[derive(Debug)]
+pub struct RuntimeFunction {
+    begin_address: u32,
+    end_address: Option<u32>,
+    unwind_data: u32,
+}
+
+impl RuntimeFunction {
+    pub fn begin_address(&self) -> u32 {
+        self.begin_address
+    }
+
+    pub fn end_address(&self) -> Option<u32> {
+        self.end_address
+    }
+
+    pub fn unwind_data(&self) -> u32 {
+        self.unwind_data
+    }
+}
+
+// a MethodDef with precompiled code
+#[derive(Debug)]
+pub struct MethodEntryPoint {
+    method_def: Token,
+    runtime_function: u32,
+    has_fixups: bool,
+}
+
+impl MethodEntryPoint {
+    // the fixups have to be resolved before the code runs
+    pub fn has_fixups(&self) -> bool {
+        self.has_fixups
+    }
+
+    pub fn method_def(&self) -> Token {
+        self.method_def
+    }
+
+    // index into the RuntimeFunctions section
+    pub fn runtime_function(&self) -> u32 {
+        self.runtime_function
+    }
+}
+
+// READYTORUN_COMPONENT_ASSEMBLIES_ENTRY
+#[derive(Debug)]
+pub struct ComponentAssembly {
+    cor_header: DataDirectory,
+    ready_to_run_core_header: DataDirectory,
+}
+
+impl ComponentAssembly {
+    pub fn cor_header(&self) -> &DataDirectory {
+        &self.cor_header
+    }
+
+    // READYTORUN_CORE_HEADER, Flags, NumberOfSections and the sections without the signature and the version
+    pub fn ready_to_run_core_header(&self) -> &DataDirectory {
+        &self.ready_to_run_core_header
+    }
+}
+
+// READYTORUN_HEADER of images precompiled by crossgen
+#[derive(Debug)]
+pub struct ReadyToRunHeader {
+    rva: u32,
+    major_version: u16,
+    minor_version: u16,
+    flags: u32,
+    sections: Vec<ReadyToRunSection>,
+    compiler_identifier: Option<String>,
+    import_sections: Vec<ImportSection>,
+    runtime_functions: Vec<RuntimeFunction>,
+    method_entry_points: Vec<MethodEntryPoint>,
+    available_types: Vec<Token>,
+    component_assemblies: Vec<ComponentAssembly>,
+    owner_composite_executable: Option<String>,
+}
+
+impl ReadyToRunHeader {
+    pub fn is_ready_to_run(executable: &Executable, rva: u32) -> bool {
+        executable.rva_to_offset(rva).is_some_and(|w| executable.buffer().pread_with::<u32>(w, LE).ok() == Some(READYTORUN_SIGNATURE))
+    }
+
+    // `rva` is the managed native header of the CLR header, or the RTR_HEADER export of a composite image
+    pub fn parse(executable: &Executable, rva: u32) -> Result<Self, failure::Error> {
+        let read = |_: scroll::Error| failure::err_msg("Failed to read the ReadyToRun header");
+
+        let mut offset = match executable.rva_to_offset(rva) {
+            Some(offset) => offset,
+            None => {
+                let msg = format!("Failed to resolve the ReadyToRun header at RVA {:#010X}", rva);
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        let buffer = executable.buffer();
+        let signature = buffer.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+        if signature != READYTORUN_SIGNATURE {
+            let msg = format!("Invalid ReadyToRun signature {:#010X}", signature);
+            return Err(failure::err_msg(msg));
+        }
+
+        let major_version = buffer.gread_with::<u16>(&mut offset, LE).map_err(read)?;
+        let minor_version = buffer.gread_with::<u16>(&mut offset, LE).map_err(read)?;
+        let flags = buffer.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+        let number_of_sections = buffer.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+        if number_of_sections > MAX_SECTIONS {
+            let msg = format!("Too many ReadyToRun sections: {}", number_of_sections);
+            return Err(failure::err_msg(msg));
+        }
+
+        let mut sections: Vec<ReadyToRunSection> = Vec::new();
+        for _ in 0..number_of_sections {
+            let section_type = buffer.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+            let section = buffer.gread_with::<DataDirectory>(&mut offset, LE).map_err(read)?;
+            sections.push(ReadyToRunSection { section_type, section });
+        }
+
+        let machine = executable.file_header().map_or(0, |w| w.machine());
+        let (machine, _) = ready_to_run_machine(machine);
+
+        let mut header = ReadyToRunHeader {
+            rva,
+            major_version,
+            minor_version,
+            flags,
+            sections,
+            compiler_identifier: None,
+            import_sections: Vec::new(),
+            runtime_functions: Vec::new(),
+            method_entry_points: Vec::new(),
+            available_types: Vec::new(),
+            component_assemblies: Vec::new(),
+            owner_composite_executable: None,
+        };
+
+        for section in header.sections.iter() {
+            let data = match section_data(executable, &section.section) {
+                Some(data) => data,
+                None => {
+                    let msg = format!("Failed to read the ReadyToRun section {} at RVA {:#010X}", section.section_type, section.section.virtual_address());
+                    return Err(failure::err_msg(msg));
+                }
+            };
+
+            let invalid = |_: scroll::Error| {
+                let name = section.name().unwrap_or("unknown");
+                let msg = format!("Invalid ReadyToRun section {} ({})", name, section.section_type);
+                failure::err_msg(msg)
+            };
+
+            match section.section_type {
+                READYTORUN_SECTION_COMPILER_IDENTIFIER => header.compiler_identifier = Some(read_string(data)),
+                READYTORUN_SECTION_IMPORT_SECTIONS => header.import_sections = parse_import_sections(data).map_err(invalid)?,
+                READYTORUN_SECTION_RUNTIME_FUNCTIONS => header.runtime_functions = parse_runtime_functions(data, machine).map_err(invalid)?,
+                READYTORUN_SECTION_METHODDEF_ENTRYPOINTS => header.method_entry_points = parse_method_entry_points(data).map_err(invalid)?,
+                READYTORUN_SECTION_AVAILABLE_TYPES => header.available_types = parse_available_types(data).map_err(invalid)?,
+                READYTORUN_SECTION_COMPONENT_ASSEMBLIES => header.component_assemblies = parse_component_assemblies(data).map_err(invalid)?,
+                READYTORUN_SECTION_OWNER_COMPOSITE_EXECUTABLE => header.owner_composite_executable = Some(read_string(data)),
+                _ => (),
+            }
+        }
+
+        Ok(header)
+    }
+
+    // composite images carry the code of several component assemblies and are located by their export
+    pub fn parse_composite(executable: &Executable) -> Result<Option<Self>, failure::Error> {
+        let functions = match executable.export_data().and_then(|w| w.functions()) {
+            Some(functions) => functions,
+            None => return Ok(None),
+        };
+
+        match functions.iter().find(|w| w.name() == READYTORUN_COMPOSITE_HEADER_EXPORT) {
+            Some(function) => Ok(Some(ReadyToRunHeader::parse(executable, function.function())?)),
+            None => Ok(None),
+        }
+    }
+
+    // TypeDef tokens, or ExportedType tokens for forwarded types
+    pub fn available_types(&self) -> Vec<Token> {
+        self.available_types.clone()
+    }
+
+    pub fn compiler_identifier(&self) -> Option<&str> {
+        self.compiler_identifier.as_deref()
+    }
+
+    pub fn component_assemblies(&self) -> Vec<&ComponentAssembly> {
+        self.component_assemblies.iter().collect()
+    }
+
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    pub fn import_sections(&self) -> Vec<&ImportSection> {
+        self.import_sections.iter().collect()
+    }
+
+    // a component assembly whose code lives in the composite image named by owner_composite_executable
+    pub fn is_component(&self) -> bool {
+        self.flags & READYTORUN_FLAG_COMPONENT != 0
+    }
+
+    // the image carries the code of the assemblies listed in component_assemblies
+    pub fn is_composite(&self) -> bool {
+        self.section(READYTORUN_SECTION_COMPONENT_ASSEMBLIES).is_some()
+    }
+
+    // only some of the methods were compiled, the rest are jitted
+    pub fn is_partial(&self) -> bool {
+        self.flags & READYTORUN_FLAG_PARTIAL != 0
+    }
+
+    pub fn major_version(&self) -> u16 {
+        self.major_version
+    }
+
+    // RVA of the code of a method, None if it is not precompiled
+    pub fn method_entry_point(&self, method_def: u32) -> Option<u32> {
+        let entry_point = self.method_entry_points.iter().find(|w| w.method_def.row() == method_def)?;
+        self.runtime_functions.get(entry_point.runtime_function as usize).map(|w| w.begin_address)
+    }
+
+    // ordered by MethodDef
+    pub fn method_entry_points(&self) -> Vec<&MethodEntryPoint> {
+        self.method_entry_points.iter().collect()
+    }
+
+    pub fn minor_version(&self) -> u16 {
+        self.minor_version
+    }
+
+    pub fn owner_composite_executable(&self) -> Option<&str> {
+        self.owner_composite_executable.as_deref()
+    }
+
+    pub fn rva(&self) -> u32 {
+        self.rva
+    }
+
+    pub fn runtime_functions(&self) -> Vec<&RuntimeFunction> {
+        self.runtime_functions.iter().collect()
+    }
+
+    pub fn section(&self, section_type: u32) -> Option<&ReadyToRunSection> {
+        self.sections.iter().find(|w| w.section_type == section_type)
+    }
+
+    pub fn sections(&self) -> Vec<&ReadyToRunSection> {
+        self.sections.iter().collect()
+    }
+
+    pub fn size(&self) -> u32 {
+        (HEADER_SIZE + self.sections.len() * SECTION_SIZE) as u32
+    }
+}
+
+fn section_data<'a>(executable: &'a Executable, section: &DataDirectory) -> Option<&'a [u8]> {
+    let start = executable.rva_to_offset(section.virtual_address())?;
+    executable.buffer().get(start..start + section.size() as usize)
+}
+
+// zero terminated UTF-8, the terminator may be missing
+fn read_string(data: &[u8]) -> String {
+    let end = data.iter().position(|w| *w == 0).unwrap_or(data.len());
+    String::from_utf8_lossy(&data[..end]).into_owned()
+}
+
+fn parse_import_sections(data: &[u8]) -> Result<Vec<ImportSection>, scroll::Error> {
+    let mut import_sections: Vec<ImportSection> = Vec::new();
+    let mut offset = 0;
+    while offset + 20 <= data.len() {
+        import_sections.push(ImportSection {
+            section: data.gread_with::<DataDirectory>(&mut offset, LE)?,
+            flags: data.gread_with::<u16>(&mut offset, LE)?,
+            kind: data.gread_with::<u8>(&mut offset, LE)?,
+            entry_size: data.gread_with::<u8>(&mut offset, LE)?,
+            signatures: data.gread_with::<u32>(&mut offset, LE)?,
+            auxiliary_data: data.gread_with::<u32>(&mut offset, LE)?,
+        });
+    }
+
+    Ok(import_sections)
+}
+
+fn parse_runtime_functions(data: &[u8], machine: u16) -> Result<Vec<RuntimeFunction>, scroll::Error> {
+    let entry_size = if machine == IMAGE_FILE_MACHINE_AMD64 { 12 } else { 8 };
+
+    let mut runtime_functions: Vec<RuntimeFunction> = Vec::new();
+    let mut offset = 0;
+    while offset + entry_size <= data.len() {
+        let begin_address = data.gread_with::<u32>(&mut offset, LE)?;
+        let end_address = if entry_size == 12 { Some(data.gread_with::<u32>(&mut offset, LE)?) } else { None };
+        let unwind_data = data.gread_with::<u32>(&mut offset, LE)?;
+
+        runtime_functions.push(RuntimeFunction { begin_address, end_address, unwind_data });
+    }
+
+    Ok(runtime_functions)
+}
+
+fn parse_method_entry_points(data: &[u8]) -> Result<Vec<MethodEntryPoint>, scroll::Error> {
+    let mut offset = 0;
+    let header = decode_unsigned(data, &mut offset)?;
+    let base = offset;
+    let count = header >> 2;
+    let entry_index_size = header & 3;
+
+    let mut method_entry_points: Vec<MethodEntryPoint> = Vec::new();
+    for index in 0..count {
+        let mut offset = match native_array_offset(data, base, entry_index_size, index)? {
+            Some(offset) => offset,
+            None => continue,
+        };
+
+        // the runtime function index, shifted left by one or by two when fixups follow
+        let id = decode_unsigned(data, &mut offset)?;
+        let has_fixups = id & 1 != 0;
+        let runtime_function = if has_fixups { id >> 2 } else { id >> 1 };
+
+        method_entry_points.push(MethodEntryPoint {
+            method_def: Token::new(TABLE_METHOD_DEF, index + 1),
+            runtime_function,
+            has_fixups,
+        });
+    }
+
+    Ok(method_entry_points)
+}
+
+fn parse_available_types(data: &[u8]) -> Result<Vec<Token>, scroll::Error> {
+    let mut offset = 0;
+    let header = data.gread::<u8>(&mut offset)?;
+    let base = offset;
+    let number_of_buckets = 1usize << (header >> 2);
+    let entry_index_size = header & 3;
+
+    let bucket_offset = |bucket: usize| -> Result<usize, scroll::Error> {
+        let offset = match entry_index_size {
+            0 => data.pread::<u8>(base + bucket)? as usize,
+            1 => data.pread_with::<u16>(base + bucket * 2, LE)? as usize,
+            _ => data.pread_with::<u32>(base + bucket * 4, LE)? as usize,
+        };
+
+        Ok(base + offset)
+    };
+
+    let mut available_types: Vec<Token> = Vec::new();
+    for bucket in 0..number_of_buckets {
+        let mut offset = bucket_offset(bucket)?;
+        let end = bucket_offset(bucket + 1)?;
+
+        while offset < end {
+            // the low byte of the hash code of the name, then the offset of the entry relative to itself
+            let _ = data.gread::<u8>(&mut offset)?;
+            let position = offset;
+            let delta = decode_signed(data, &mut offset)?;
+
+            let mut entry = match (position as i64).checked_add(delta as i64) {
+                Some(entry) if entry >= 0 => entry as usize,
+                _ => return Err(scroll::Error::BadOffset(position)),
+            };
+
+            // RID << 1 | isExportedType
+            let value = decode_unsigned(data, &mut entry)?;
+            let table = if value & 1 != 0 { TABLE_EXPORTED_TYPE } else { TABLE_TYPE_DEF };
+            available_types.push(Token::new(table, value >> 1));
+        }
+    }
+
+    Ok(available_types)
+}
+
+fn parse_component_assemblies(data: &[u8]) -> Result<Vec<ComponentAssembly>, scroll::Error> {
+    let mut component_assemblies: Vec<ComponentAssembly> = Vec::new();
+    let mut offset = 0;
+    while offset + 16 <= data.len() {
+        component_assemblies.push(ComponentAssembly {
+            cor_header: data.gread_with::<DataDirectory>(&mut offset, LE)?,
+            ready_to_run_core_header: data.gread_with::<DataDirectory>(&mut offset, LE)?,
+        });
+    }
+
+    Ok(component_assemblies)
+}
+
+// NativeFormat unsigned integer, the number of trailing one bits of the first byte gives the length
+fn decode_unsigned(data: &[u8], offset: &mut usize) -> Result<u32, scroll::Error> {
+    let first = data.gread::<u8>(offset)? as u32;
+
+    if first & 0x01 == 0 {
+        Ok(first >> 1)
+    } else if first & 0x02 == 0 {
+        Ok(first >> 2 | (data.gread::<u8>(offset)? as u32) << 6)
+    } else if first & 0x04 == 0 {
+        let value = first >> 3 | (data.gread::<u8>(offset)? as u32) << 5;
+        Ok(value | (data.gread::<u8>(offset)? as u32) << 13)
+    } else if first & 0x08 == 0 {
+        let mut value = first >> 4;
+        for shift in [4, 12, 20].iter() {
+            value |= (data.gread::<u8>(offset)? as u32) << shift;
+        }
+
+        Ok(value)
+    } else if first & 0x10 == 0 {
+        data.gread_with::<u32>(offset, LE)
+    } else {
+        Err(scroll::Error::BadInput {
+            size: *offset,
+            msg: "invalid NativeFormat integer",
+        })
+    }
+}
+
+// NativeFormat signed integer, encoded like the unsigned one with the sign in the most significant byte
+fn decode_signed(data: &[u8], offset: &mut usize) -> Result<i32, scroll::Error> {
+    let first = data.gread::<u8>(offset)? as i32;
+
+    if first & 0x01 == 0 {
+        Ok(first as u8 as i8 as i32 >> 1)
+    } else if first & 0x02 == 0 {
+        Ok(first >> 2 | (data.gread::<i8>(offset)? as i32) << 6)
+    } else if first & 0x04 == 0 {
+        let value = first >> 3 | (data.gread::<u8>(offset)? as i32) << 5;
+        Ok(value | (data.gread::<i8>(offset)? as i32) << 13)
+    } else if first & 0x08 == 0 {
+        let mut value = first >> 4;
+        value |= (data.gread::<u8>(offset)? as i32) << 4;
+        value |= (data.gread::<u8>(offset)? as i32) << 12;
+        Ok(value | (data.gread::<i8>(offset)? as i32) << 20)
+    } else if first & 0x10 == 0 {
+        data.gread_with::<i32>(offset, LE)
+    } else {
+        Err(scroll::Error::BadInput {
+            size: *offset,
+            msg: "invalid NativeFormat integer",
+        })
+    }
+}
+
+// NativeArray lookup, each block of 16 elements is a small binary tree, None for missing elements
+fn native_array_offset(data: &[u8], base: usize, entry_index_size: u32, index: u32) -> Result<Option<usize>, scroll::Error> {
+    let block = (index / NATIVE_ARRAY_BLOCK_SIZE) as usize;
+    let block_offset = match entry_index_size {
+        0 => data.pread::<u8>(base + block)? as usize,
+        1 => data.pread_with::<u16>(base + block * 2, LE)? as usize,
+        _ => data.pread_with::<u32>(base + block * 4, LE)? as usize,
+    };
+
+    let mut offset = base + block_offset;
+    let mut bit = NATIVE_ARRAY_BLOCK_SIZE >> 1;
+    while bit > 0 {
+        let mut next = offset;
+        let value = decode_unsigned(data, &mut next)?;
+
+        if index & bit != 0 {
+            if value & 2 != 0 {
+                offset += (value >> 2) as usize;
+                bit >>= 1;
+                continue;
+            }
+        } else if value & 1 != 0 {
+            offset = next;
+            bit >>= 1;
+            continue;
+        }
+
+        // a leaf for a single element
+        if value & 3 == 0 && value >> 2 == index & (NATIVE_ARRAY_BLOCK_SIZE - 1) {
+            return Ok(Some(next));
+        }
+
+        return Ok(None);
+    }
+
+    Ok(Some(offset))
+}
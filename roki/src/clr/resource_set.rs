@@ -0,0 +1,343 @@
+use scroll::{Pread, LE};
+
+// https://github.com/dotnet/runtime/blob/main/src/libraries/System.Private.CoreLib/src/System/Resources/ResourceReader.cs
+pub const RESOURCE_MANAGER_MAGIC: u32 = 0xBEEF_CACE;
+
+// ResourceTypeCode of the version 2 format, user types start at USER_TYPES
+pub const RESOURCE_TYPE_NULL: u32 = 0x00;
+pub const RESOURCE_TYPE_STRING: u32 = 0x01;
+pub const RESOURCE_TYPE_BOOLEAN: u32 = 0x02;
+pub const RESOURCE_TYPE_CHAR: u32 = 0x03;
+pub const RESOURCE_TYPE_BYTE: u32 = 0x04;
+pub const RESOURCE_TYPE_SBYTE: u32 = 0x05;
+pub const RESOURCE_TYPE_INT16: u32 = 0x06;
+pub const RESOURCE_TYPE_UINT16: u32 = 0x07;
+pub const RESOURCE_TYPE_INT32: u32 = 0x08;
+pub const RESOURCE_TYPE_UINT32: u32 = 0x09;
+pub const RESOURCE_TYPE_INT64: u32 = 0x0A;
+pub const RESOURCE_TYPE_UINT64: u32 = 0x0B;
+pub const RESOURCE_TYPE_SINGLE: u32 = 0x0C;
+pub const RESOURCE_TYPE_DOUBLE: u32 = 0x0D;
+pub const RESOURCE_TYPE_DECIMAL: u32 = 0x0E;
+pub const RESOURCE_TYPE_DATE_TIME: u32 = 0x0F;
+pub const RESOURCE_TYPE_TIME_SPAN: u32 = 0x10;
+pub const RESOURCE_TYPE_BYTE_ARRAY: u32 = 0x20;
+pub const RESOURCE_TYPE_STREAM: u32 = 0x21;
+pub const RESOURCE_TYPE_USER_TYPES: u32 = 0x40;
+
+// a hostile count must not make us reserve gigabytes up front
+const MAX_RESOURCES: usize = 0x10_0000;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ResourceValue {
+    Null,
+    String(String),
+    Boolean(bool),
+    Char(u16),
+    Byte(u8),
+    SByte(i8),
+    Int16(i16),
+    UInt16(u16),
+    Int32(i32),
+    UInt32(u32),
+    Int64(i64),
+    UInt64(u64),
+    Single(f32),
+    Double(f64),
+    // the raw System.Decimal bits
+    Decimal([u8; 16]),
+    // the raw value of DateTime.ToBinary
+    DateTime(i64),
+    // ticks of 100 nanoseconds
+    TimeSpan(i64),
+    ByteArray(Vec<u8>),
+    Stream(Vec<u8>),
+    // serialized by the BinaryFormatter, left undecoded
+    Serialized(Vec<u8>),
+}
+
+#[derive(Debug)]
+pub struct ResourceEntry {
+    name: String,
+    type_name: String,
+    value: ResourceValue,
+}
+
+impl ResourceEntry {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // e.g. System.String, or the assembly qualified name of a user type
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    pub fn value(&self) -> &ResourceValue {
+        &self.value
+    }
+}
+
+#[derive(Debug)]
+pub struct ResourceSet {
+    header_version: u32,
+    reader_type: String,
+    resource_set_type: String,
+    version: u32,
+    types: Vec<String>,
+    entries: Vec<ResourceEntry>,
+}
+
+impl ResourceSet {
+    pub fn is_resource_set(data: &[u8]) -> bool {
+        data.pread_with::<u32>(0, LE).ok() == Some(RESOURCE_MANAGER_MAGIC)
+    }
+
+    pub fn parse(data: &[u8]) -> Result<Self, failure::Error> {
+        let read = |_: scroll::Error| failure::err_msg("Failed to read the .resources header");
+
+        let mut offset = 0;
+        let magic = data.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+        if magic != RESOURCE_MANAGER_MAGIC {
+            let msg = format!("Invalid .resources magic {:#010X}", magic);
+            return Err(failure::err_msg(msg));
+        }
+
+        // the reader and the resource set types, skipped as a whole by newer readers
+        let header_version = data.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+        let header_size = data.gread_with::<u32>(&mut offset, LE).map_err(read)? as usize;
+        let header_end = offset + header_size;
+
+        let (reader_type, resource_set_type) = if header_version == 1 {
+            (read_string(data, &mut offset).map_err(read)?, read_string(data, &mut offset).map_err(read)?)
+        } else {
+            (String::new(), String::new())
+        };
+        offset = header_end;
+
+        let version = data.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+        if version != 1 && version != 2 {
+            let msg = format!("Unsupported .resources version {}", version);
+            return Err(failure::err_msg(msg));
+        }
+
+        let number_of_resources = read_count(data, &mut offset)?;
+        let number_of_types = read_count(data, &mut offset)?;
+
+        let mut types: Vec<String> = Vec::new();
+        for _ in 0..number_of_types {
+            types.push(read_string(data, &mut offset).map_err(read)?);
+        }
+
+        // padded with "PAD" to 8 bytes from the beginning of the data
+        offset = (offset + 7) & !7;
+
+        // the hashes of the names are only needed for lookups
+        offset += number_of_resources * 4;
+
+        let mut name_positions: Vec<usize> = Vec::new();
+        for _ in 0..number_of_resources {
+            name_positions.push(data.gread_with::<u32>(&mut offset, LE).map_err(read)? as usize);
+        }
+
+        let data_section = data.gread_with::<u32>(&mut offset, LE).map_err(read)? as usize;
+        let name_section = offset;
+
+        let mut names: Vec<(String, usize)> = Vec::new();
+        for position in name_positions {
+            let mut offset = name_section + position;
+            let name = read_utf16_string(data, &mut offset).map_err(|_| {
+                let msg = format!("Failed to read the resource name at {:#X}", name_section + position);
+                failure::err_msg(msg)
+            })?;
+
+            let data_offset = data.gread_with::<u32>(&mut offset, LE).map_err(read)? as usize;
+            names.push((name, data_section + data_offset));
+        }
+
+        // values of user types are only delimited by the start of the next value
+        let mut ends: Vec<usize> = names.iter().map(|w| w.1).collect();
+        ends.push(data.len());
+        ends.sort_unstable();
+        ends.dedup();
+
+        let mut entries: Vec<ResourceEntry> = Vec::new();
+        for (name, start) in names {
+            let end = ends.get(ends.partition_point(|w| *w <= start)).copied().unwrap_or(data.len());
+            let (type_name, value) = read_value(data, start, end, version, &types).map_err(|_| {
+                let msg = format!("Failed to read the value of the resource `{}` at {:#X}", name, start);
+                failure::err_msg(msg)
+            })?;
+
+            entries.push(ResourceEntry { name, type_name, value });
+        }
+
+        Ok(ResourceSet {
+            header_version,
+            reader_type,
+            resource_set_type,
+            version,
+            types,
+            entries,
+        })
+    }
+
+    pub fn entries(&self) -> Vec<&ResourceEntry> {
+        self.entries.iter().collect()
+    }
+
+    pub fn header_version(&self) -> u32 {
+        self.header_version
+    }
+
+    // e.g. System.Resources.ResourceReader, empty for header versions other than 1
+    pub fn reader_type(&self) -> &str {
+        &self.reader_type
+    }
+
+    pub fn resource_set_type(&self) -> &str {
+        &self.resource_set_type
+    }
+
+    // type names of the values that are not primitives in version 2
+    pub fn types(&self) -> Vec<&str> {
+        self.types.iter().map(|w| w.as_str()).collect()
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+fn read_count(data: &[u8], offset: &mut usize) -> Result<usize, failure::Error> {
+    let count = data.gread_with::<i32>(offset, LE).map_err(|_| failure::err_msg("Failed to read the .resources header"))?;
+    if count < 0 || count as usize > MAX_RESOURCES {
+        let msg = format!("Invalid number of resources {}", count);
+        return Err(failure::err_msg(msg));
+    }
+
+    Ok(count as usize)
+}
+
+// BinaryReader.Read7BitEncodedInt
+fn read_7bit_encoded(data: &[u8], offset: &mut usize) -> Result<u32, scroll::Error> {
+    let mut value = 0u32;
+    for shift in (0..35).step_by(7) {
+        let byte = data.gread::<u8>(offset)?;
+        value |= ((byte & 0x7F) as u32) << shift;
+
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(scroll::Error::Custom("Invalid 7-bit encoded integer".to_owned()))
+}
+
+fn read_bytes<'a>(data: &'a [u8], offset: &mut usize, length: usize) -> Result<&'a [u8], scroll::Error> {
+    match data.get(*offset..*offset + length) {
+        Some(bytes) => {
+            *offset += length;
+            Ok(bytes)
+        }
+        None => Err(scroll::Error::TooBig {
+            size: length,
+            len: data.len().saturating_sub(*offset),
+        }),
+    }
+}
+
+// BinaryReader.ReadString, UTF-8 prefixed with its length in bytes
+fn read_string(data: &[u8], offset: &mut usize) -> Result<String, scroll::Error> {
+    let length = read_7bit_encoded(data, offset)? as usize;
+    Ok(String::from_utf8_lossy(read_bytes(data, offset, length)?).into_owned())
+}
+
+// the names of the resources are UTF-16 prefixed with their length in bytes
+fn read_utf16_string(data: &[u8], offset: &mut usize) -> Result<String, scroll::Error> {
+    let length = read_7bit_encoded(data, offset)? as usize;
+    let units: Vec<u16> = read_bytes(data, offset, length)?.chunks_exact(2).map(|w| u16::from_le_bytes([w[0], w[1]])).collect();
+    Ok(String::from_utf16_lossy(&units))
+}
+
+fn read_value(data: &[u8], start: usize, end: usize, version: u32, types: &[String]) -> Result<(String, ResourceValue), scroll::Error> {
+    let mut offset = start;
+
+    // version 1 refers to the type table only, version 2 has type codes for the primitives
+    let type_code = if version == 1 {
+        match read_7bit_encoded(data, &mut offset)? as i32 {
+            -1 => RESOURCE_TYPE_NULL,
+            index => match types.get(index as usize) {
+                Some(type_name) if type_name.starts_with("System.String") => RESOURCE_TYPE_STRING,
+                Some(_) => RESOURCE_TYPE_USER_TYPES + index as u32,
+                None => return Err(scroll::Error::Custom(format!("Invalid type index {}", index))),
+            },
+        }
+    } else {
+        read_7bit_encoded(data, &mut offset)?
+    };
+
+    let value = match type_code {
+        RESOURCE_TYPE_NULL => ResourceValue::Null,
+        RESOURCE_TYPE_STRING => ResourceValue::String(read_string(data, &mut offset)?),
+        RESOURCE_TYPE_BOOLEAN => ResourceValue::Boolean(data.gread::<u8>(&mut offset)? != 0),
+        RESOURCE_TYPE_CHAR => ResourceValue::Char(data.gread_with::<u16>(&mut offset, LE)?),
+        RESOURCE_TYPE_BYTE => ResourceValue::Byte(data.gread::<u8>(&mut offset)?),
+        RESOURCE_TYPE_SBYTE => ResourceValue::SByte(data.gread::<i8>(&mut offset)?),
+        RESOURCE_TYPE_INT16 => ResourceValue::Int16(data.gread_with::<i16>(&mut offset, LE)?),
+        RESOURCE_TYPE_UINT16 => ResourceValue::UInt16(data.gread_with::<u16>(&mut offset, LE)?),
+        RESOURCE_TYPE_INT32 => ResourceValue::Int32(data.gread_with::<i32>(&mut offset, LE)?),
+        RESOURCE_TYPE_UINT32 => ResourceValue::UInt32(data.gread_with::<u32>(&mut offset, LE)?),
+        RESOURCE_TYPE_INT64 => ResourceValue::Int64(data.gread_with::<i64>(&mut offset, LE)?),
+        RESOURCE_TYPE_UINT64 => ResourceValue::UInt64(data.gread_with::<u64>(&mut offset, LE)?),
+        RESOURCE_TYPE_SINGLE => ResourceValue::Single(data.gread_with::<f32>(&mut offset, LE)?),
+        RESOURCE_TYPE_DOUBLE => ResourceValue::Double(data.gread_with::<f64>(&mut offset, LE)?),
+        RESOURCE_TYPE_DECIMAL => {
+            let mut bits = [0u8; 16];
+            bits.copy_from_slice(read_bytes(data, &mut offset, 16)?);
+            ResourceValue::Decimal(bits)
+        }
+        RESOURCE_TYPE_DATE_TIME => ResourceValue::DateTime(data.gread_with::<i64>(&mut offset, LE)?),
+        RESOURCE_TYPE_TIME_SPAN => ResourceValue::TimeSpan(data.gread_with::<i64>(&mut offset, LE)?),
+        RESOURCE_TYPE_BYTE_ARRAY | RESOURCE_TYPE_STREAM => {
+            let length = data.gread_with::<u32>(&mut offset, LE)? as usize;
+            let bytes = read_bytes(data, &mut offset, length)?.to_vec();
+
+            if type_code == RESOURCE_TYPE_BYTE_ARRAY {
+                ResourceValue::ByteArray(bytes)
+            } else {
+                ResourceValue::Stream(bytes)
+            }
+        }
+        code if code >= RESOURCE_TYPE_USER_TYPES => {
+            let bytes = data.get(offset..end.max(offset)).unwrap_or_default();
+            ResourceValue::Serialized(bytes.to_vec())
+        }
+        code => return Err(scroll::Error::Custom(format!("Unknown resource type code {:#X}", code))),
+    };
+
+    let type_name = match type_code {
+        RESOURCE_TYPE_NULL => "null",
+        RESOURCE_TYPE_STRING => "System.String",
+        RESOURCE_TYPE_BOOLEAN => "System.Boolean",
+        RESOURCE_TYPE_CHAR => "System.Char",
+        RESOURCE_TYPE_BYTE => "System.Byte",
+        RESOURCE_TYPE_SBYTE => "System.SByte",
+        RESOURCE_TYPE_INT16 => "System.Int16",
+        RESOURCE_TYPE_UINT16 => "System.UInt16",
+        RESOURCE_TYPE_INT32 => "System.Int32",
+        RESOURCE_TYPE_UINT32 => "System.UInt32",
+        RESOURCE_TYPE_INT64 => "System.Int64",
+        RESOURCE_TYPE_UINT64 => "System.UInt64",
+        RESOURCE_TYPE_SINGLE => "System.Single",
+        RESOURCE_TYPE_DOUBLE => "System.Double",
+        RESOURCE_TYPE_DECIMAL => "System.Decimal",
+        RESOURCE_TYPE_DATE_TIME => "System.DateTime",
+        RESOURCE_TYPE_TIME_SPAN => "System.TimeSpan",
+        RESOURCE_TYPE_BYTE_ARRAY => "System.Byte[]",
+        RESOURCE_TYPE_STREAM => "System.IO.Stream",
+        code => types.get((code - RESOURCE_TYPE_USER_TYPES) as usize).map_or("", |w| w.as_str()),
+    };
+
+    Ok((type_name.to_owned(), value))
+}
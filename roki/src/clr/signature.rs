@@ -0,0 +1,760 @@
+use std::cell::{Cell, RefCell};
+
+use scroll::Pread;
+
+use super::{decode_coded_index, gread_compressed_u32, CodedIndex, MetadataRoot, MetadataTables, Token};
+use super::{TABLE_FIELD, TABLE_MEMBER_REF, TABLE_METHOD_DEF, TABLE_METHOD_SPEC, TABLE_STAND_ALONE_SIG, TABLE_TYPE_DEF, TABLE_TYPE_REF, TABLE_TYPE_SPEC};
+
+// https://www.ecma-international.org/publications-and-standards/standards/ecma-335/
+// II.23.1.16 Element types used in signatures
+pub const ELEMENT_TYPE_VOID: u8 = 0x01;
+pub const ELEMENT_TYPE_BOOLEAN: u8 = 0x02;
+pub const ELEMENT_TYPE_CHAR: u8 = 0x03;
+pub const ELEMENT_TYPE_I1: u8 = 0x04;
+pub const ELEMENT_TYPE_U1: u8 = 0x05;
+pub const ELEMENT_TYPE_I2: u8 = 0x06;
+pub const ELEMENT_TYPE_U2: u8 = 0x07;
+pub const ELEMENT_TYPE_I4: u8 = 0x08;
+pub const ELEMENT_TYPE_U4: u8 = 0x09;
+pub const ELEMENT_TYPE_I8: u8 = 0x0A;
+pub const ELEMENT_TYPE_U8: u8 = 0x0B;
+pub const ELEMENT_TYPE_R4: u8 = 0x0C;
+pub const ELEMENT_TYPE_R8: u8 = 0x0D;
+pub const ELEMENT_TYPE_STRING: u8 = 0x0E;
+pub const ELEMENT_TYPE_PTR: u8 = 0x0F;
+pub const ELEMENT_TYPE_BYREF: u8 = 0x10;
+pub const ELEMENT_TYPE_VALUETYPE: u8 = 0x11;
+pub const ELEMENT_TYPE_CLASS: u8 = 0x12;
+pub const ELEMENT_TYPE_VAR: u8 = 0x13;
+pub const ELEMENT_TYPE_ARRAY: u8 = 0x14;
+pub const ELEMENT_TYPE_GENERICINST: u8 = 0x15;
+pub const ELEMENT_TYPE_TYPEDBYREF: u8 = 0x16;
+pub const ELEMENT_TYPE_I: u8 = 0x18;
+pub const ELEMENT_TYPE_U: u8 = 0x19;
+pub const ELEMENT_TYPE_FNPTR: u8 = 0x1B;
+pub const ELEMENT_TYPE_OBJECT: u8 = 0x1C;
+pub const ELEMENT_TYPE_SZARRAY: u8 = 0x1D;
+pub const ELEMENT_TYPE_MVAR: u8 = 0x1E;
+pub const ELEMENT_TYPE_CMOD_REQD: u8 = 0x1F;
+pub const ELEMENT_TYPE_CMOD_OPT: u8 = 0x20;
+pub const ELEMENT_TYPE_SENTINEL: u8 = 0x41;
+pub const ELEMENT_TYPE_PINNED: u8 = 0x45;
+
+// II.23.2.3 calling convention byte
+pub const SIGNATURE_HAS_THIS: u8 = 0x20;
+pub const SIGNATURE_EXPLICIT_THIS: u8 = 0x40;
+pub const SIGNATURE_GENERIC: u8 = 0x10;
+pub const SIGNATURE_KIND_MASK: u8 = 0x0F;
+pub const SIGNATURE_KIND_VARARG: u8 = 0x05;
+pub const SIGNATURE_KIND_FIELD: u8 = 0x06;
+pub const SIGNATURE_KIND_LOCAL_SIG: u8 = 0x07;
+pub const SIGNATURE_KIND_PROPERTY: u8 = 0x08;
+pub const SIGNATURE_KIND_GENERIC_INST: u8 = 0x0A;
+
+// nesting of types in a signature, deeper blobs are rejected to bound the recursion
+const MAX_DEPTH: usize = 64;
+
+// tokens resolved while formatting a single type, TypeSpecs sharing their arguments would otherwise expand exponentially
+const MAX_RESOLVED_TOKENS: usize = 0x1000;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArrayShape {
+    rank: u32,
+    sizes: Vec<u32>,
+    lower_bounds: Vec<i32>,
+}
+
+impl ArrayShape {
+    pub fn lower_bounds(&self) -> &[i32] {
+        &self.lower_bounds
+    }
+
+    pub fn rank(&self) -> u32 {
+        self.rank
+    }
+
+    pub fn sizes(&self) -> &[u32] {
+        &self.sizes
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeSignature {
+    Void,
+    Boolean,
+    Char,
+    I1,
+    U1,
+    I2,
+    U2,
+    I4,
+    U4,
+    I8,
+    U8,
+    R4,
+    R8,
+    String,
+    Object,
+    IntPtr,
+    UIntPtr,
+    TypedByRef,
+    Class(Token),
+    ValueType(Token),
+    Ptr(Box<TypeSignature>),
+    ByRef(Box<TypeSignature>),
+    Pinned(Box<TypeSignature>),
+    SzArray(Box<TypeSignature>),
+    Array(Box<TypeSignature>, ArrayShape),
+    GenericInst(Box<TypeSignature>, Vec<TypeSignature>),
+    Var(u32),
+    MVar(u32),
+    FnPtr(Box<MethodSignature>),
+    Modified { required: bool, modifier: Token, inner: Box<TypeSignature> },
+}
+
+impl TypeSignature {
+    // a TypeSpec blob
+    pub fn parse(blob: &[u8]) -> Result<Self, failure::Error> {
+        let mut reader = Reader::new(blob);
+        reader.r#type(0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MethodSignature {
+    calling_convention: u8,
+    generic_param_count: u32,
+    return_type: TypeSignature,
+    params: Vec<TypeSignature>,
+    sentinel: Option<usize>,
+}
+
+impl MethodSignature {
+    // MethodDefSig, MethodRefSig and StandAloneMethodSig
+    pub fn parse(blob: &[u8]) -> Result<Self, failure::Error> {
+        let mut reader = Reader::new(blob);
+        reader.method(0)
+    }
+
+    pub fn calling_convention(&self) -> u8 {
+        self.calling_convention
+    }
+
+    pub fn generic_param_count(&self) -> u32 {
+        self.generic_param_count
+    }
+
+    pub fn has_this(&self) -> bool {
+        self.calling_convention & SIGNATURE_HAS_THIS != 0
+    }
+
+    pub fn is_vararg(&self) -> bool {
+        self.calling_convention & SIGNATURE_KIND_MASK == SIGNATURE_KIND_VARARG
+    }
+
+    pub fn params(&self) -> Vec<&TypeSignature> {
+        self.params.iter().collect()
+    }
+
+    pub fn return_type(&self) -> &TypeSignature {
+        &self.return_type
+    }
+
+    // index of the first variable argument of a vararg call site
+    pub fn sentinel(&self) -> Option<usize> {
+        self.sentinel
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldSignature {
+    r#type: TypeSignature,
+}
+
+impl FieldSignature {
+    pub fn parse(blob: &[u8]) -> Result<Self, failure::Error> {
+        let mut reader = Reader::new(blob);
+        reader.expect_kind(SIGNATURE_KIND_FIELD, "FieldSig")?;
+
+        Ok(FieldSignature { r#type: reader.r#type(0)? })
+    }
+
+    pub fn r#type(&self) -> &TypeSignature {
+        &self.r#type
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PropertySignature {
+    has_this: bool,
+    r#type: TypeSignature,
+    params: Vec<TypeSignature>,
+}
+
+impl PropertySignature {
+    pub fn parse(blob: &[u8]) -> Result<Self, failure::Error> {
+        let mut reader = Reader::new(blob);
+        let calling_convention = reader.expect_kind(SIGNATURE_KIND_PROPERTY, "PropertySig")?;
+
+        let count = reader.compressed()?;
+        let r#type = reader.r#type(0)?;
+        let mut params: Vec<TypeSignature> = Vec::new();
+        for _ in 0..count {
+            params.push(reader.r#type(0)?);
+        }
+
+        Ok(PropertySignature {
+            has_this: calling_convention & SIGNATURE_HAS_THIS != 0,
+            r#type,
+            params,
+        })
+    }
+
+    pub fn has_this(&self) -> bool {
+        self.has_this
+    }
+
+    // parameters of an indexer
+    pub fn params(&self) -> Vec<&TypeSignature> {
+        self.params.iter().collect()
+    }
+
+    pub fn r#type(&self) -> &TypeSignature {
+        &self.r#type
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalVarSignature {
+    locals: Vec<TypeSignature>,
+}
+
+impl LocalVarSignature {
+    pub fn parse(blob: &[u8]) -> Result<Self, failure::Error> {
+        let mut reader = Reader::new(blob);
+        reader.expect_kind(SIGNATURE_KIND_LOCAL_SIG, "LocalVarSig")?;
+
+        let count = reader.compressed()?;
+        let mut locals: Vec<TypeSignature> = Vec::new();
+        for _ in 0..count {
+            locals.push(reader.r#type(0)?);
+        }
+
+        Ok(LocalVarSignature { locals })
+    }
+
+    pub fn locals(&self) -> Vec<&TypeSignature> {
+        self.locals.iter().collect()
+    }
+}
+
+// the instantiation of a MethodSpec
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MethodSpecSignature {
+    arguments: Vec<TypeSignature>,
+}
+
+impl MethodSpecSignature {
+    pub fn parse(blob: &[u8]) -> Result<Self, failure::Error> {
+        let mut reader = Reader::new(blob);
+        reader.expect_kind(SIGNATURE_KIND_GENERIC_INST, "MethodSpec")?;
+
+        let count = reader.compressed()?;
+        let mut arguments: Vec<TypeSignature> = Vec::new();
+        for _ in 0..count {
+            arguments.push(reader.r#type(0)?);
+        }
+
+        Ok(MethodSpecSignature { arguments })
+    }
+
+    pub fn arguments(&self) -> Vec<&TypeSignature> {
+        self.arguments.iter().collect()
+    }
+}
+
+struct Reader<'a> {
+    blob: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(blob: &'a [u8]) -> Self {
+        Reader { blob, offset: 0 }
+    }
+
+    fn error(&self) -> failure::Error {
+        let msg = format!("Failed to read the signature at {:#X} of the blob", self.offset);
+        failure::err_msg(msg)
+    }
+
+    fn u8(&mut self) -> Result<u8, failure::Error> {
+        self.blob.gread::<u8>(&mut self.offset).map_err(|_| self.error())
+    }
+
+    fn peek(&self) -> Result<u8, failure::Error> {
+        self.blob.pread::<u8>(self.offset).map_err(|_| self.error())
+    }
+
+    fn compressed(&mut self) -> Result<u32, failure::Error> {
+        gread_compressed_u32(self.blob, &mut self.offset).map_err(|_| self.error())
+    }
+
+    // II.23.2, the sign bit is rotated into the least significant bit
+    fn compressed_signed(&mut self) -> Result<i32, failure::Error> {
+        let start = self.offset;
+        let value = self.compressed()?;
+
+        let sign_extension: u32 = match self.offset - start {
+            1 => 0xFFFF_FFC0,
+            2 => 0xFFFF_E000,
+            _ => 0xF000_0000,
+        };
+
+        let magnitude = value >> 1;
+        Ok(if value & 1 != 0 { (magnitude | sign_extension) as i32 } else { magnitude as i32 })
+    }
+
+    // TypeDefOrRefOrSpecEncoded
+    fn type_token(&mut self) -> Result<Token, failure::Error> {
+        let value = self.compressed()?;
+        decode_coded_index(CodedIndex::TypeDefOrRef, value)
+    }
+
+    fn expect_kind(&mut self, kind: u8, name: &str) -> Result<u8, failure::Error> {
+        let calling_convention = self.u8()?;
+        if calling_convention & SIGNATURE_KIND_MASK != kind {
+            let msg = format!("Invalid {} calling convention {:#04X}", name, calling_convention);
+            return Err(failure::err_msg(msg));
+        }
+
+        Ok(calling_convention)
+    }
+
+    fn method(&mut self, depth: usize) -> Result<MethodSignature, failure::Error> {
+        let calling_convention = self.u8()?;
+        let generic_param_count = if calling_convention & SIGNATURE_GENERIC != 0 { self.compressed()? } else { 0 };
+
+        let count = self.compressed()?;
+        let return_type = self.r#type(depth + 1)?;
+
+        let mut params: Vec<TypeSignature> = Vec::new();
+        let mut sentinel: Option<usize> = None;
+        while params.len() < count as usize {
+            if self.peek()? == ELEMENT_TYPE_SENTINEL {
+                self.offset += 1;
+                sentinel = Some(params.len());
+                continue;
+            }
+
+            params.push(self.r#type(depth + 1)?);
+        }
+
+        Ok(MethodSignature {
+            calling_convention,
+            generic_param_count,
+            return_type,
+            params,
+            sentinel,
+        })
+    }
+
+    fn r#type(&mut self, depth: usize) -> Result<TypeSignature, failure::Error> {
+        if depth > MAX_DEPTH {
+            return Err(failure::err_msg("Signature is nested too deeply"));
+        }
+
+        let element_type = self.u8()?;
+        let r#type = match element_type {
+            ELEMENT_TYPE_VOID => TypeSignature::Void,
+            ELEMENT_TYPE_BOOLEAN => TypeSignature::Boolean,
+            ELEMENT_TYPE_CHAR => TypeSignature::Char,
+            ELEMENT_TYPE_I1 => TypeSignature::I1,
+            ELEMENT_TYPE_U1 => TypeSignature::U1,
+            ELEMENT_TYPE_I2 => TypeSignature::I2,
+            ELEMENT_TYPE_U2 => TypeSignature::U2,
+            ELEMENT_TYPE_I4 => TypeSignature::I4,
+            ELEMENT_TYPE_U4 => TypeSignature::U4,
+            ELEMENT_TYPE_I8 => TypeSignature::I8,
+            ELEMENT_TYPE_U8 => TypeSignature::U8,
+            ELEMENT_TYPE_R4 => TypeSignature::R4,
+            ELEMENT_TYPE_R8 => TypeSignature::R8,
+            ELEMENT_TYPE_STRING => TypeSignature::String,
+            ELEMENT_TYPE_OBJECT => TypeSignature::Object,
+            ELEMENT_TYPE_I => TypeSignature::IntPtr,
+            ELEMENT_TYPE_U => TypeSignature::UIntPtr,
+            ELEMENT_TYPE_TYPEDBYREF => TypeSignature::TypedByRef,
+            ELEMENT_TYPE_CLASS => TypeSignature::Class(self.type_token()?),
+            ELEMENT_TYPE_VALUETYPE => TypeSignature::ValueType(self.type_token()?),
+            ELEMENT_TYPE_PTR => TypeSignature::Ptr(Box::new(self.r#type(depth + 1)?)),
+            ELEMENT_TYPE_BYREF => TypeSignature::ByRef(Box::new(self.r#type(depth + 1)?)),
+            ELEMENT_TYPE_PINNED => TypeSignature::Pinned(Box::new(self.r#type(depth + 1)?)),
+            ELEMENT_TYPE_SZARRAY => TypeSignature::SzArray(Box::new(self.r#type(depth + 1)?)),
+            ELEMENT_TYPE_ARRAY => {
+                let element = self.r#type(depth + 1)?;
+                let rank = self.compressed()?;
+
+                let mut sizes: Vec<u32> = Vec::new();
+                for _ in 0..self.compressed()?.min(rank) {
+                    sizes.push(self.compressed()?);
+                }
+
+                let mut lower_bounds: Vec<i32> = Vec::new();
+                for _ in 0..self.compressed()?.min(rank) {
+                    lower_bounds.push(self.compressed_signed()?);
+                }
+
+                TypeSignature::Array(Box::new(element), ArrayShape { rank, sizes, lower_bounds })
+            }
+            ELEMENT_TYPE_GENERICINST => {
+                let generic = self.r#type(depth + 1)?;
+                let count = self.compressed()?;
+
+                let mut arguments: Vec<TypeSignature> = Vec::new();
+                for _ in 0..count {
+                    arguments.push(self.r#type(depth + 1)?);
+                }
+
+                TypeSignature::GenericInst(Box::new(generic), arguments)
+            }
+            ELEMENT_TYPE_VAR => TypeSignature::Var(self.compressed()?),
+            ELEMENT_TYPE_MVAR => TypeSignature::MVar(self.compressed()?),
+            ELEMENT_TYPE_FNPTR => TypeSignature::FnPtr(Box::new(self.method(depth + 1)?)),
+            ELEMENT_TYPE_CMOD_REQD | ELEMENT_TYPE_CMOD_OPT => {
+                let modifier = self.type_token()?;
+                TypeSignature::Modified {
+                    required: element_type == ELEMENT_TYPE_CMOD_REQD,
+                    modifier,
+                    inner: Box::new(self.r#type(depth + 1)?),
+                }
+            }
+            _ => {
+                let msg = format!("Unknown element type {:#04X} at {:#X} of the signature", element_type, self.offset - 1);
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        Ok(r#type)
+    }
+}
+
+// the generic parameters in scope, a TypeDef row for VAR and a MethodDef row for MVAR
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GenericContext {
+    type_def: Option<u32>,
+    method_def: Option<u32>,
+}
+
+impl GenericContext {
+    pub fn new(type_def: Option<u32>, method_def: Option<u32>) -> Self {
+        GenericContext { type_def, method_def }
+    }
+}
+
+// renders signatures as C# declarations, resolving type tokens through the tables
+pub struct SignatureFormatter<'a> {
+    metadata_root: &'a MetadataRoot,
+    tables: &'a MetadataTables,
+    // TypeSpec rows being expanded, a TypeSpec referring to itself is printed as its token
+    expanding: RefCell<Vec<u32>>,
+    resolved: Cell<usize>,
+}
+
+impl<'a> SignatureFormatter<'a> {
+    pub fn new(metadata_root: &'a MetadataRoot, tables: &'a MetadataTables) -> Self {
+        SignatureFormatter {
+            metadata_root,
+            tables,
+            expanding: RefCell::new(Vec::new()),
+            resolved: Cell::new(0),
+        }
+    }
+
+    // full name of a TypeDef, TypeRef or TypeSpec token, nested types are joined by a dot
+    pub fn type_name(&self, token: Token, context: GenericContext) -> String {
+        self.resolved.set(0);
+        self.type_name_at(token, context, 0)
+    }
+
+    fn type_name_at(&self, token: Token, context: GenericContext, depth: usize) -> String {
+        if depth > MAX_DEPTH || self.resolved.get() >= MAX_RESOLVED_TOKENS {
+            return format!("/* {} */", token);
+        }
+
+        self.resolved.set(self.resolved.get() + 1);
+
+        let name = match token.table() {
+            TABLE_TYPE_DEF => self.tables.type_def(token.row()).map(|w| match self.tables.enclosing_class_of(token.row()) {
+                Some(enclosing) if enclosing != token.row() => format!("{}.{}", self.type_name_at(Token::new(TABLE_TYPE_DEF, enclosing), context, depth + 1), w.type_name()),
+                _ => join_namespace(w.type_namespace(), w.type_name()),
+            }),
+            TABLE_TYPE_REF => self.tables.type_ref(token.row()).map(|w| {
+                let scope = w.resolution_scope();
+                if scope.table() == TABLE_TYPE_REF && !scope.is_null() && scope.row() != token.row() {
+                    format!("{}.{}", self.type_name_at(scope, context, depth + 1), w.type_name())
+                } else {
+                    join_namespace(w.type_namespace(), w.type_name())
+                }
+            }),
+            TABLE_TYPE_SPEC if !self.expanding.borrow().contains(&token.row()) => self.tables.type_spec(token.row()).and_then(|w| {
+                let blob = self.metadata_root.blobs().get(w.signature()).ok()?;
+                let r#type = TypeSignature::parse(blob).ok()?;
+
+                self.expanding.borrow_mut().push(token.row());
+                let name = self.format_type_at(&r#type, context, depth + 1);
+                self.expanding.borrow_mut().pop();
+
+                Some(name)
+            }),
+            _ => None,
+        };
+
+        name.unwrap_or_else(|| format!("/* {} */", token))
+    }
+
+    pub fn format_type(&self, r#type: &TypeSignature, context: GenericContext) -> String {
+        self.resolved.set(0);
+        self.format_type_at(r#type, context, 0)
+    }
+
+    fn format_type_at(&self, r#type: &TypeSignature, context: GenericContext, depth: usize) -> String {
+        let format = |w: &TypeSignature| self.format_type_at(w, context, depth + 1);
+
+        match r#type {
+            TypeSignature::Void => "void".to_owned(),
+            TypeSignature::Boolean => "bool".to_owned(),
+            TypeSignature::Char => "char".to_owned(),
+            TypeSignature::I1 => "sbyte".to_owned(),
+            TypeSignature::U1 => "byte".to_owned(),
+            TypeSignature::I2 => "short".to_owned(),
+            TypeSignature::U2 => "ushort".to_owned(),
+            TypeSignature::I4 => "int".to_owned(),
+            TypeSignature::U4 => "uint".to_owned(),
+            TypeSignature::I8 => "long".to_owned(),
+            TypeSignature::U8 => "ulong".to_owned(),
+            TypeSignature::R4 => "float".to_owned(),
+            TypeSignature::R8 => "double".to_owned(),
+            TypeSignature::String => "string".to_owned(),
+            TypeSignature::Object => "object".to_owned(),
+            TypeSignature::IntPtr => "nint".to_owned(),
+            TypeSignature::UIntPtr => "nuint".to_owned(),
+            TypeSignature::TypedByRef => "TypedReference".to_owned(),
+            TypeSignature::Class(token) | TypeSignature::ValueType(token) => self.type_name_at(*token, context, depth + 1),
+            TypeSignature::Ptr(inner) => format!("{}*", format(inner)),
+            TypeSignature::ByRef(inner) => format!("ref {}", format(inner)),
+            TypeSignature::Pinned(inner) => format!("{} pinned", format(inner)),
+            TypeSignature::SzArray(inner) => format!("{}[]", format(inner)),
+            TypeSignature::Array(inner, shape) => format!("{}[{}]", format(inner), ",".repeat(shape.rank.saturating_sub(1) as usize)),
+            TypeSignature::GenericInst(generic, arguments) => {
+                // List`1 is written List<T> in C#
+                let name = format(generic);
+                let name = match name.rfind('`') {
+                    Some(position) => name[..position].to_owned(),
+                    None => name,
+                };
+
+                format!("{}<{}>", name, arguments.iter().map(format).collect::<Vec<String>>().join(", "))
+            }
+            TypeSignature::Var(number) => self.generic_param_name(context.type_def.map(|w| Token::new(TABLE_TYPE_DEF, w)), *number, "!"),
+            TypeSignature::MVar(number) => self.generic_param_name(context.method_def.map(|w| Token::new(TABLE_METHOD_DEF, w)), *number, "!!"),
+            TypeSignature::FnPtr(method) => {
+                let mut types: Vec<String> = method.params.iter().map(format).collect();
+                types.push(format(&method.return_type));
+                format!("delegate*<{}>", types.join(", "))
+            }
+            TypeSignature::Modified { required, modifier, inner } => {
+                let keyword = if *required { "modreq" } else { "modopt" };
+                format!("{} {}({})", format(inner), keyword, self.type_name_at(*modifier, context, depth + 1))
+            }
+        }
+    }
+
+    fn generic_param_name(&self, owner: Option<Token>, number: u32, prefix: &str) -> String {
+        let name = owner.and_then(|w| self.tables.generic_params_of(w).into_iter().find(|w| w.number() as u32 == number).map(|w| w.name().to_owned()));
+        name.unwrap_or_else(|| format!("{}{}", prefix, number))
+    }
+
+    // e.g. static void Main(string[] args), parameter names are taken from the Param rows if any
+    pub fn format_method(&self, name: &str, method: &MethodSignature, param_names: &[&str], context: GenericContext) -> String {
+        let generic = match context.method_def {
+            Some(method_def) if method.generic_param_count > 0 => {
+                let names: Vec<String> = (0..method.generic_param_count).map(|w| self.generic_param_name(Some(Token::new(TABLE_METHOD_DEF, method_def)), w, "!!")).collect();
+                format!("<{}>", names.join(", "))
+            }
+            _ => String::new(),
+        };
+
+        let mut params: Vec<String> = Vec::new();
+        for (i, param) in method.params.iter().enumerate() {
+            if method.sentinel == Some(i) {
+                params.push("__arglist".to_owned());
+            }
+
+            let r#type = self.format_type(param, context);
+            match param_names.get(i) {
+                Some(name) if !name.is_empty() => params.push(format!("{} {}", r#type, name)),
+                _ => params.push(r#type),
+            }
+        }
+
+        if method.is_vararg() && method.sentinel.is_none() {
+            params.push("__arglist".to_owned());
+        }
+
+        let static_keyword = if method.has_this() { "" } else { "static " };
+
+        // constructors are named after the type and have no return type
+        let type_name = context.type_def.and_then(|w| self.tables.type_def(w)).map(|w| w.type_name());
+        match (name, type_name) {
+            (".ctor", Some(type_name)) | (".cctor", Some(type_name)) => format!("{}{}({})", static_keyword, type_name, params.join(", ")),
+            _ => format!("{}{} {}{}({})", static_keyword, self.format_type(&method.return_type, context), name, generic, params.join(", ")),
+        }
+    }
+
+    // operand of an instruction, e.g. void System.Console::WriteLine(string) for a method token
+    pub fn format_token(&self, token: Token, context: GenericContext) -> String {
+        self.try_format_token(token, context).unwrap_or_else(|| format!("/* {} */", token))
+    }
+
+    fn try_format_token(&self, token: Token, context: GenericContext) -> Option<String> {
+        let blobs = self.metadata_root.blobs();
+
+        match token.table() {
+            TABLE_TYPE_DEF | TABLE_TYPE_REF | TABLE_TYPE_SPEC => Some(self.type_name(token, context)),
+            TABLE_FIELD => {
+                let field = self.tables.field(token.row())?;
+                let owner = self.tables.owner_of_field(token.row());
+                let context = GenericContext::new(owner, None);
+
+                let signature = FieldSignature::parse(blobs.get(field.signature()).ok()?).ok()?;
+                let class = owner.map(|w| Token::new(TABLE_TYPE_DEF, w));
+                Some(format!("{} {}", self.format_type(&signature.r#type, context), self.member_name(class, field.name(), context)))
+            }
+            TABLE_METHOD_DEF => {
+                let method_def = self.tables.method_def(token.row())?;
+                let owner = self.tables.owner_of_method(token.row());
+                let context = GenericContext::new(owner, Some(token.row()));
+
+                let signature = MethodSignature::parse(blobs.get(method_def.signature()).ok()?).ok()?;
+                let class = owner.map(|w| Token::new(TABLE_TYPE_DEF, w));
+                Some(self.format_method_reference(class, method_def.name(), &signature, None, context))
+            }
+            TABLE_MEMBER_REF => {
+                let member_ref = self.tables.member_ref(token.row())?;
+                let blob = blobs.get(member_ref.signature()).ok()?;
+
+                // generic parameters of a reference are printed by number, e.g. !0
+                let class = Some(member_ref.class());
+
+                if blob.first()? & SIGNATURE_KIND_MASK == SIGNATURE_KIND_FIELD {
+                    let signature = FieldSignature::parse(blob).ok()?;
+                    let name = self.member_name(class, member_ref.name(), context);
+                    Some(format!("{} {}", self.format_type(&signature.r#type, GenericContext::default()), name))
+                } else {
+                    let signature = MethodSignature::parse(blob).ok()?;
+                    Some(self.format_method_reference(class, member_ref.name(), &signature, None, GenericContext::default()))
+                }
+            }
+            TABLE_METHOD_SPEC => {
+                let method_spec = self.tables.method_spec(token.row())?;
+                let instantiation = MethodSpecSignature::parse(blobs.get(method_spec.instantiation()).ok()?).ok()?;
+
+                let method = method_spec.method();
+                let (class, name, signature, method_context) = match method.table() {
+                    TABLE_METHOD_DEF => {
+                        let method_def = self.tables.method_def(method.row())?;
+                        let owner = self.tables.owner_of_method(method.row());
+                        let signature = MethodSignature::parse(blobs.get(method_def.signature()).ok()?).ok()?;
+                        (owner.map(|w| Token::new(TABLE_TYPE_DEF, w)), method_def.name(), signature, GenericContext::new(owner, None))
+                    }
+                    TABLE_MEMBER_REF => {
+                        let member_ref = self.tables.member_ref(method.row())?;
+                        let signature = MethodSignature::parse(blobs.get(member_ref.signature()).ok()?).ok()?;
+                        (Some(member_ref.class()), member_ref.name(), signature, GenericContext::default())
+                    }
+                    _ => return None,
+                };
+
+                let arguments: Vec<String> = instantiation.arguments.iter().map(|w| self.format_type(w, context)).collect();
+                Some(self.format_method_reference(class, name, &signature, Some(&arguments), method_context))
+            }
+            TABLE_STAND_ALONE_SIG => {
+                let stand_alone_sig = self.tables.stand_alone_sig(token.row())?;
+                let signature = MethodSignature::parse(blobs.get(stand_alone_sig.signature()).ok()?).ok()?;
+
+                let params: Vec<String> = signature.params.iter().map(|w| self.format_type(w, context)).collect();
+                Some(format!("{} ({})", self.format_type(&signature.return_type, context), params.join(", ")))
+            }
+            _ => None,
+        }
+    }
+
+    // the literal of a ldstr operand, quoted and escaped as in C#
+    pub fn format_user_string(&self, token: Token) -> String {
+        match self.metadata_root.user_strings().get(token.row()) {
+            Ok(string) => escape_string(&string),
+            Err(_) => format!("/* {} */", token),
+        }
+    }
+
+    fn member_name(&self, class: Option<Token>, name: &str, context: GenericContext) -> String {
+        // the parent of a global member is a ModuleRef, of a vararg call site a MethodDef
+        match class.map(|w| (w, w.table())) {
+            Some((class, TABLE_TYPE_DEF)) | Some((class, TABLE_TYPE_REF)) | Some((class, TABLE_TYPE_SPEC)) if !class.is_null() => format!("{}::{}", self.type_name(class, context), name),
+            _ => name.to_owned(),
+        }
+    }
+
+    fn format_method_reference(&self, class: Option<Token>, name: &str, method: &MethodSignature, arguments: Option<&[String]>, context: GenericContext) -> String {
+        let generic = match arguments {
+            Some(arguments) => format!("<{}>", arguments.join(", ")),
+            None => String::new(),
+        };
+
+        let mut params: Vec<String> = Vec::new();
+        for (i, param) in method.params.iter().enumerate() {
+            if method.sentinel == Some(i) {
+                params.push("...".to_owned());
+            }
+
+            params.push(self.format_type(param, context));
+        }
+
+        let instance = if method.has_this() { "instance " } else { "" };
+        format!(
+            "{}{} {}{}({})",
+            instance,
+            self.format_type(&method.return_type, context),
+            self.member_name(class, name, context),
+            generic,
+            params.join(", ")
+        )
+    }
+}
+
+fn join_namespace(namespace: &str, name: &str) -> String {
+    if namespace.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", namespace, name)
+    }
+}
+
+fn escape_string(string: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in string.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\0' => escaped.push_str("\\0"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
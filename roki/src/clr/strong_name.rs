@@ -0,0 +1,309 @@
+use scroll::{Pread, LE};
+
+use super::AssemblyIdentity;
+use crate::authenticode::{header_exclusions, new_hasher, DigestAlgorithm, RsaPublicKey};
+use crate::headers::Cor20Header;
+use crate::Executable;
+
+// https://www.ecma-international.org/publications-and-standards/standards/ecma-335/
+// II.6.2.1.3 PublicKey and the CryptoAPI PUBLICKEYBLOB it wraps
+pub const CALG_RSA_SIGN: u32 = 0x0000_2400;
+pub const CALG_SHA1: u32 = 0x0000_8004;
+pub const CALG_SHA_256: u32 = 0x0000_800C;
+pub const CALG_SHA_384: u32 = 0x0000_800D;
+pub const CALG_SHA_512: u32 = 0x0000_800E;
+pub const PUBLICKEYBLOB: u8 = 0x06;
+pub const RSA1: u32 = 0x3141_5352; // 'RSA1'
+
+// the placeholder of the framework assemblies, the runtime substitutes its own key
+pub const ECMA_PUBLIC_KEY: [u8; 16] = [0, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0];
+
+// SigAlgID, HashAlgID, cbPublicKey, BLOBHEADER and RSAPUBKEY
+const PUBLIC_KEY_HEADER_SIZE: usize = 32;
+
+// IMAGE_SECTION_HEADER
+const SECTION_HEADER_SIZE: usize = 40;
+
+// the RSA public key of the Assembly table
+#[derive(Debug)]
+pub struct StrongNamePublicKey {
+    signature_algorithm: u32,
+    hash_algorithm: u32,
+    bit_length: u32,
+    exponent: u32,
+    modulus: Vec<u8>,
+}
+
+impl StrongNamePublicKey {
+    pub fn parse(public_key: &[u8]) -> Result<Self, failure::Error> {
+        let read = |_: scroll::Error| failure::err_msg("Failed to read the strong name public key");
+
+        if public_key == ECMA_PUBLIC_KEY {
+            return Err(failure::err_msg("The ECMA public key is a placeholder for the key of the runtime"));
+        }
+
+        let mut offset = 0;
+        let signature_algorithm = public_key.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+        let hash_algorithm = public_key.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+        let _ = public_key.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+
+        // BLOBHEADER
+        let blob_type = public_key.gread::<u8>(&mut offset).map_err(read)?;
+        offset += 3;
+        let key_algorithm = public_key.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+
+        // RSAPUBKEY
+        let magic = public_key.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+        if blob_type != PUBLICKEYBLOB || magic != RSA1 {
+            let msg = format!("Unsupported strong name public key (blob type {:#04X}, algorithm {:#010X})", blob_type, key_algorithm);
+            return Err(failure::err_msg(msg));
+        }
+
+        let bit_length = public_key.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+        let exponent = public_key.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+
+        let modulus_size = (bit_length as usize).div_ceil(8);
+        let modulus = match public_key.get(PUBLIC_KEY_HEADER_SIZE..PUBLIC_KEY_HEADER_SIZE + modulus_size) {
+            // CryptoAPI stores the modulus little-endian
+            Some(modulus) => modulus.iter().rev().copied().collect(),
+            None => {
+                let msg = format!("Strong name public key is shorter than its {}-bit modulus", bit_length);
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        Ok(StrongNamePublicKey {
+            signature_algorithm,
+            hash_algorithm,
+            bit_length,
+            exponent,
+            modulus,
+        })
+    }
+
+    pub fn bit_length(&self) -> u32 {
+        self.bit_length
+    }
+
+    pub fn digest_algorithm(&self) -> DigestAlgorithm {
+        match self.hash_algorithm {
+            CALG_SHA1 => DigestAlgorithm::Sha1,
+            CALG_SHA_256 => DigestAlgorithm::Sha256,
+            CALG_SHA_384 => DigestAlgorithm::Sha384,
+            CALG_SHA_512 => DigestAlgorithm::Sha512,
+            value => DigestAlgorithm::Unknown(format!("{:#06X}", value)),
+        }
+    }
+
+    pub fn exponent(&self) -> u32 {
+        self.exponent
+    }
+
+    // ALG_ID of the hash
+    pub fn hash_algorithm(&self) -> u32 {
+        self.hash_algorithm
+    }
+
+    // big-endian
+    pub fn modulus(&self) -> &[u8] {
+        &self.modulus
+    }
+
+    // ALG_ID of the signature, CALG_RSA_SIGN
+    pub fn signature_algorithm(&self) -> u32 {
+        self.signature_algorithm
+    }
+}
+
+// the StrongNameSignature of the CLR header and the key it is checked against
+#[derive(Debug)]
+pub struct StrongNameSignature {
+    rva: u32,
+    offset: usize,
+    signature: Vec<u8>,
+    public_key: Vec<u8>,
+    is_signed: bool,
+    has_signature_key: bool,
+}
+
+impl StrongNameSignature {
+    // None if the image reserves no space for a signature
+    pub fn parse(executable: &Executable, cor20_header: &Cor20Header, assembly: Option<&AssemblyIdentity>) -> Result<Option<Self>, failure::Error> {
+        let directory = cor20_header.strong_name_signature();
+        if directory.virtual_address() == 0 || directory.size() == 0 {
+            return Ok(None);
+        }
+
+        let offset = match executable.rva_to_offset(directory.virtual_address()) {
+            Some(offset) => offset,
+            None => {
+                let msg = format!("Failed to resolve the strong name signature at RVA {:#010X}", directory.virtual_address());
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        let signature = match executable.buffer().get(offset..offset + directory.size() as usize) {
+            Some(signature) => signature.to_vec(),
+            None => {
+                let msg = format!("Strong name signature at {:#010X} exceeds the end of file", offset);
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        Ok(Some(StrongNameSignature {
+            rva: directory.virtual_address(),
+            offset,
+            signature,
+            public_key: assembly.map_or_else(Vec::new, |w| w.public_key().to_vec()),
+            is_signed: cor20_header.is_strong_name_signed(),
+            has_signature_key: assembly.is_some_and(|w| w.has_signature_key()),
+        }))
+    }
+
+    // enhanced strong naming, the image is signed with the key of AssemblySignatureKeyAttribute rather than the identity key
+    pub fn has_signature_key(&self) -> bool {
+        self.has_signature_key
+    }
+
+    // the space is reserved but the image was built with delay signing and not signed afterwards
+    pub fn is_delay_signed(&self) -> bool {
+        !self.is_signed
+    }
+
+    pub fn is_ecma_key(&self) -> bool {
+        self.public_key == ECMA_PUBLIC_KEY
+    }
+
+    // file offset of the signature
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    // the PublicKey of the Assembly table, empty for modules
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    pub fn rva(&self) -> u32 {
+        self.rva
+    }
+
+    // little-endian, as stored in the image
+    pub fn signature(&self) -> &[u8] {
+        &self.signature
+    }
+
+    pub fn size(&self) -> u32 {
+        self.signature.len() as u32
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct StrongNameVerification {
+    algorithm: DigestAlgorithm,
+    computed: Vec<u8>,
+    signed: Option<Vec<u8>>,
+}
+
+impl StrongNameVerification {
+    pub fn algorithm(&self) -> &DigestAlgorithm {
+        &self.algorithm
+    }
+
+    pub fn computed(&self) -> &[u8] {
+        &self.computed
+    }
+
+    // the image is unchanged since it was signed with the key of the Assembly table
+    pub fn is_valid(&self) -> bool {
+        self.signed.as_deref() == Some(self.computed.as_slice())
+    }
+
+    // the hash recovered from the signature, None if the signature was not made with the public key
+    pub fn signed(&self) -> Option<&[u8]> {
+        self.signed.as_deref()
+    }
+}
+
+// the image hashed like the runtime does, the CheckSum and the certificate table entry are zeroed and the signature is skipped
+pub fn strong_name_hash(executable: &Executable, signature: &StrongNameSignature, algorithm: &DigestAlgorithm) -> Result<Vec<u8>, failure::Error> {
+    let mut hasher = match new_hasher(algorithm) {
+        Some(hasher) => hasher,
+        None => {
+            let msg = format!("Unsupported strong name hash algorithm {}", algorithm);
+            return Err(failure::err_msg(msg));
+        }
+    };
+
+    let buffer = executable.buffer();
+    let file_header = match executable.file_header() {
+        Some(file_header) => file_header,
+        None => return Err(failure::err_msg("The strong name hash requires the FILE_HEADER")),
+    };
+
+    // IMAGE_NT_SIGNATURE + IMAGE_FILE_HEADER, then the optional header and the section table
+    let optional_header_offset = executable.dos_header().unwrap().addr_of_nt_header() as usize + 4 + 20;
+    let end_of_headers = optional_header_offset + file_header.size_of_optional_header() as usize + file_header.number_of_sections() as usize * SECTION_HEADER_SIZE;
+    let exclusions = header_exclusions(executable, end_of_headers)?;
+
+    let mut headers = buffer[..end_of_headers].to_vec();
+    headers[exclusions.checksum..exclusions.checksum + 4].copy_from_slice(&[0; 4]);
+    if let Some(security_entry) = exclusions.security_entry {
+        headers[security_entry..security_entry + 8].copy_from_slice(&[0; 8]);
+    }
+
+    hasher.update(&headers);
+
+    // the sections in the order of the section table, without the signature
+    let excluded = signature.offset..signature.offset + signature.signature.len();
+    for section in executable.section_headers().unwrap_or_default() {
+        if section.pointer_to_raw_data() == 0 || section.size_of_raw_data() == 0 {
+            continue;
+        }
+
+        let start = section.pointer_to_raw_data() as usize;
+        let end = start + section.size_of_raw_data() as usize;
+        if end > buffer.len() {
+            let msg = format!("Raw data of the section {} exceeds the end of file", section.name());
+            return Err(failure::err_msg(msg));
+        }
+
+        if excluded.start >= start && excluded.end <= end {
+            hasher.update(&buffer[start..excluded.start]);
+            hasher.update(&buffer[excluded.end..end]);
+        } else {
+            hasher.update(&buffer[start..end]);
+        }
+    }
+
+    Ok(hasher.finalize().into_vec())
+}
+
+pub fn verify_strong_name(executable: &Executable, signature: &StrongNameSignature) -> Result<StrongNameVerification, failure::Error> {
+    if signature.public_key.is_empty() {
+        return Err(failure::err_msg("The image has no assembly public key to verify the signature with"));
+    }
+
+    // the signature key and its countersignature by the identity key are not checked
+    if signature.has_signature_key {
+        return Err(failure::err_msg("The image is signed with the signature key of AssemblySignatureKeyAttribute, not with the assembly public key"));
+    }
+
+    let public_key = StrongNamePublicKey::parse(&signature.public_key)?;
+    if signature.signature.len() != public_key.modulus.len() {
+        let msg = format!("Signature of {} bytes does not fit the {}-bit key", signature.signature.len(), public_key.bit_length);
+        return Err(failure::err_msg(msg));
+    }
+
+    let algorithm = public_key.digest_algorithm();
+    let computed = strong_name_hash(executable, signature, &algorithm)?;
+
+    // RSASSA-PKCS1-v1_5, the signature is stored little-endian
+    let key = RsaPublicKey {
+        modulus: public_key.modulus.clone(),
+        exponent: public_key.exponent.to_be_bytes().to_vec(),
+    };
+    let signed = key.signed_digest(&signature.signature.iter().rev().copied().collect::<Vec<u8>>(), &algorithm);
+
+    Ok(StrongNameVerification { algorithm, computed, signed })
+}
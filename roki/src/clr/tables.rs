@@ -0,0 +1,1525 @@
+use std::ops::Range;
+
+use scroll::{Pread, LE};
+
+use super::{MetadataRoot, StringsHeap, Token};
+
+// https://www.ecma-international.org/publications-and-standards/standards/ecma-335/
+// II.22 Metadata logical format: tables, II.24.2.6 #~ stream
+pub const TABLE_MODULE: u8 = 0x00;
+pub const TABLE_TYPE_REF: u8 = 0x01;
+pub const TABLE_TYPE_DEF: u8 = 0x02;
+pub const TABLE_FIELD_PTR: u8 = 0x03;
+pub const TABLE_FIELD: u8 = 0x04;
+pub const TABLE_METHOD_PTR: u8 = 0x05;
+pub const TABLE_METHOD_DEF: u8 = 0x06;
+pub const TABLE_PARAM_PTR: u8 = 0x07;
+pub const TABLE_PARAM: u8 = 0x08;
+pub const TABLE_INTERFACE_IMPL: u8 = 0x09;
+pub const TABLE_MEMBER_REF: u8 = 0x0A;
+pub const TABLE_CONSTANT: u8 = 0x0B;
+pub const TABLE_CUSTOM_ATTRIBUTE: u8 = 0x0C;
+pub const TABLE_FIELD_MARSHAL: u8 = 0x0D;
+pub const TABLE_DECL_SECURITY: u8 = 0x0E;
+pub const TABLE_CLASS_LAYOUT: u8 = 0x0F;
+pub const TABLE_FIELD_LAYOUT: u8 = 0x10;
+pub const TABLE_STAND_ALONE_SIG: u8 = 0x11;
+pub const TABLE_EVENT_MAP: u8 = 0x12;
+pub const TABLE_EVENT_PTR: u8 = 0x13;
+pub const TABLE_EVENT: u8 = 0x14;
+pub const TABLE_PROPERTY_MAP: u8 = 0x15;
+pub const TABLE_PROPERTY_PTR: u8 = 0x16;
+pub const TABLE_PROPERTY: u8 = 0x17;
+pub const TABLE_METHOD_SEMANTICS: u8 = 0x18;
+pub const TABLE_METHOD_IMPL: u8 = 0x19;
+pub const TABLE_MODULE_REF: u8 = 0x1A;
+pub const TABLE_TYPE_SPEC: u8 = 0x1B;
+pub const TABLE_IMPL_MAP: u8 = 0x1C;
+pub const TABLE_FIELD_RVA: u8 = 0x1D;
+pub const TABLE_ENC_LOG: u8 = 0x1E;
+pub const TABLE_ENC_MAP: u8 = 0x1F;
+pub const TABLE_ASSEMBLY: u8 = 0x20;
+pub const TABLE_ASSEMBLY_PROCESSOR: u8 = 0x21;
+pub const TABLE_ASSEMBLY_OS: u8 = 0x22;
+pub const TABLE_ASSEMBLY_REF: u8 = 0x23;
+pub const TABLE_ASSEMBLY_REF_PROCESSOR: u8 = 0x24;
+pub const TABLE_ASSEMBLY_REF_OS: u8 = 0x25;
+pub const TABLE_FILE: u8 = 0x26;
+pub const TABLE_EXPORTED_TYPE: u8 = 0x27;
+pub const TABLE_MANIFEST_RESOURCE: u8 = 0x28;
+pub const TABLE_NESTED_CLASS: u8 = 0x29;
+pub const TABLE_GENERIC_PARAM: u8 = 0x2A;
+pub const TABLE_METHOD_SPEC: u8 = 0x2B;
+pub const TABLE_GENERIC_PARAM_CONSTRAINT: u8 = 0x2C;
+
+const NUMBER_OF_TABLES: usize = 0x2D;
+
+// HeapSizes flags
+const HEAP_STRING_4: u8 = 0x01;
+const HEAP_GUID_4: u8 = 0x02;
+const HEAP_BLOB_4: u8 = 0x04;
+const HEAP_EXTRA_DATA: u8 = 0x40; // 4 more bytes after the row counts
+
+const UNUSED: u8 = 0xFF;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CodedIndex {
+    TypeDefOrRef,
+    HasConstant,
+    HasCustomAttribute,
+    HasFieldMarshal,
+    HasDeclSecurity,
+    MemberRefParent,
+    HasSemantics,
+    MethodDefOrRef,
+    MemberForwarded,
+    Implementation,
+    CustomAttributeType,
+    ResolutionScope,
+    TypeOrMethodDef,
+}
+
+impl CodedIndex {
+    // II.24.2.6, the position in the list is the tag
+    pub fn tables(self) -> &'static [u8] {
+        match self {
+            CodedIndex::TypeDefOrRef => &[TABLE_TYPE_DEF, TABLE_TYPE_REF, TABLE_TYPE_SPEC],
+            CodedIndex::HasConstant => &[TABLE_FIELD, TABLE_PARAM, TABLE_PROPERTY],
+            CodedIndex::HasCustomAttribute => &[
+                TABLE_METHOD_DEF,
+                TABLE_FIELD,
+                TABLE_TYPE_REF,
+                TABLE_TYPE_DEF,
+                TABLE_PARAM,
+                TABLE_INTERFACE_IMPL,
+                TABLE_MEMBER_REF,
+                TABLE_MODULE,
+                TABLE_DECL_SECURITY,
+                TABLE_PROPERTY,
+                TABLE_EVENT,
+                TABLE_STAND_ALONE_SIG,
+                TABLE_MODULE_REF,
+                TABLE_TYPE_SPEC,
+                TABLE_ASSEMBLY,
+                TABLE_ASSEMBLY_REF,
+                TABLE_FILE,
+                TABLE_EXPORTED_TYPE,
+                TABLE_MANIFEST_RESOURCE,
+                TABLE_GENERIC_PARAM,
+                TABLE_GENERIC_PARAM_CONSTRAINT,
+                TABLE_METHOD_SPEC,
+            ],
+            CodedIndex::HasFieldMarshal => &[TABLE_FIELD, TABLE_PARAM],
+            CodedIndex::HasDeclSecurity => &[TABLE_TYPE_DEF, TABLE_METHOD_DEF, TABLE_ASSEMBLY],
+            CodedIndex::MemberRefParent => &[TABLE_TYPE_DEF, TABLE_TYPE_REF, TABLE_MODULE_REF, TABLE_METHOD_DEF, TABLE_TYPE_SPEC],
+            CodedIndex::HasSemantics => &[TABLE_EVENT, TABLE_PROPERTY],
+            CodedIndex::MethodDefOrRef => &[TABLE_METHOD_DEF, TABLE_MEMBER_REF],
+            CodedIndex::MemberForwarded => &[TABLE_FIELD, TABLE_METHOD_DEF],
+            CodedIndex::Implementation => &[TABLE_FILE, TABLE_ASSEMBLY_REF, TABLE_EXPORTED_TYPE],
+            CodedIndex::CustomAttributeType => &[UNUSED, UNUSED, TABLE_METHOD_DEF, TABLE_MEMBER_REF, UNUSED],
+            CodedIndex::ResolutionScope => &[TABLE_MODULE, TABLE_MODULE_REF, TABLE_ASSEMBLY_REF, TABLE_TYPE_REF],
+            CodedIndex::TypeOrMethodDef => &[TABLE_TYPE_DEF, TABLE_METHOD_DEF],
+        }
+    }
+
+    pub fn tag_bits(self) -> u32 {
+        let count = self.tables().len() as u32;
+        32 - (count - 1).leading_zeros()
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Column {
+    U16,
+    U32,
+    String,
+    Guid,
+    Blob,
+    Table(u8),
+    Coded(CodedIndex),
+}
+
+// II.22.2 - II.22.39, ordered by the table number
+#[rustfmt::skip]
+const SCHEMA: [&[Column]; NUMBER_OF_TABLES] = {
+    use CodedIndex::*;
+    use Column::*;
+    [
+        /* Module                 */ &[U16, String, Guid, Guid, Guid],
+        /* TypeRef                */ &[Coded(ResolutionScope), String, String],
+        /* TypeDef                */ &[U32, String, String, Coded(TypeDefOrRef), Table(TABLE_FIELD), Table(TABLE_METHOD_DEF)],
+        /* FieldPtr               */ &[Table(TABLE_FIELD)],
+        /* Field                  */ &[U16, String, Blob],
+        /* MethodPtr              */ &[Table(TABLE_METHOD_DEF)],
+        /* MethodDef              */ &[U32, U16, U16, String, Blob, Table(TABLE_PARAM)],
+        /* ParamPtr               */ &[Table(TABLE_PARAM)],
+        /* Param                  */ &[U16, U16, String],
+        /* InterfaceImpl          */ &[Table(TABLE_TYPE_DEF), Coded(TypeDefOrRef)],
+        /* MemberRef              */ &[Coded(MemberRefParent), String, Blob],
+        /* Constant               */ &[U16, Coded(HasConstant), Blob],
+        /* CustomAttribute        */ &[Coded(HasCustomAttribute), Coded(CustomAttributeType), Blob],
+        /* FieldMarshal           */ &[Coded(HasFieldMarshal), Blob],
+        /* DeclSecurity           */ &[U16, Coded(HasDeclSecurity), Blob],
+        /* ClassLayout            */ &[U16, U32, Table(TABLE_TYPE_DEF)],
+        /* FieldLayout            */ &[U32, Table(TABLE_FIELD)],
+        /* StandAloneSig          */ &[Blob],
+        /* EventMap               */ &[Table(TABLE_TYPE_DEF), Table(TABLE_EVENT)],
+        /* EventPtr               */ &[Table(TABLE_EVENT)],
+        /* Event                  */ &[U16, String, Coded(TypeDefOrRef)],
+        /* PropertyMap            */ &[Table(TABLE_TYPE_DEF), Table(TABLE_PROPERTY)],
+        /* PropertyPtr            */ &[Table(TABLE_PROPERTY)],
+        /* Property               */ &[U16, String, Blob],
+        /* MethodSemantics        */ &[U16, Table(TABLE_METHOD_DEF), Coded(HasSemantics)],
+        /* MethodImpl             */ &[Table(TABLE_TYPE_DEF), Coded(MethodDefOrRef), Coded(MethodDefOrRef)],
+        /* ModuleRef              */ &[String],
+        /* TypeSpec               */ &[Blob],
+        /* ImplMap                */ &[U16, Coded(MemberForwarded), String, Table(TABLE_MODULE_REF)],
+        /* FieldRVA               */ &[U32, Table(TABLE_FIELD)],
+        /* EncLog                 */ &[U32, U32],
+        /* EncMap                 */ &[U32],
+        /* Assembly               */ &[U32, U16, U16, U16, U16, U32, Blob, String, String],
+        /* AssemblyProcessor      */ &[U32],
+        /* AssemblyOS             */ &[U32, U32, U32],
+        /* AssemblyRef            */ &[U16, U16, U16, U16, U32, Blob, String, String, Blob],
+        /* AssemblyRefProcessor   */ &[U32, Table(TABLE_ASSEMBLY_REF)],
+        /* AssemblyRefOS          */ &[U32, U32, U32, Table(TABLE_ASSEMBLY_REF)],
+        /* File                   */ &[U32, String, Blob],
+        /* ExportedType           */ &[U32, U32, String, String, Coded(Implementation)],
+        /* ManifestResource       */ &[U32, U32, String, Coded(Implementation)],
+        /* NestedClass            */ &[Table(TABLE_TYPE_DEF), Table(TABLE_TYPE_DEF)],
+        /* GenericParam           */ &[U16, U16, Coded(TypeOrMethodDef), String],
+        /* MethodSpec             */ &[Coded(MethodDefOrRef), Blob],
+        /* GenericParamConstraint */ &[Table(TABLE_GENERIC_PARAM), Coded(TypeDefOrRef)],
+    ]
+};
+
+pub fn table_name(table: u8) -> Option<&'static str> {
+    let name = match table {
+        TABLE_MODULE => "Module",
+        TABLE_TYPE_REF => "TypeRef",
+        TABLE_TYPE_DEF => "TypeDef",
+        TABLE_FIELD_PTR => "FieldPtr",
+        TABLE_FIELD => "Field",
+        TABLE_METHOD_PTR => "MethodPtr",
+        TABLE_METHOD_DEF => "MethodDef",
+        TABLE_PARAM_PTR => "ParamPtr",
+        TABLE_PARAM => "Param",
+        TABLE_INTERFACE_IMPL => "InterfaceImpl",
+        TABLE_MEMBER_REF => "MemberRef",
+        TABLE_CONSTANT => "Constant",
+        TABLE_CUSTOM_ATTRIBUTE => "CustomAttribute",
+        TABLE_FIELD_MARSHAL => "FieldMarshal",
+        TABLE_DECL_SECURITY => "DeclSecurity",
+        TABLE_CLASS_LAYOUT => "ClassLayout",
+        TABLE_FIELD_LAYOUT => "FieldLayout",
+        TABLE_STAND_ALONE_SIG => "StandAloneSig",
+        TABLE_EVENT_MAP => "EventMap",
+        TABLE_EVENT_PTR => "EventPtr",
+        TABLE_EVENT => "Event",
+        TABLE_PROPERTY_MAP => "PropertyMap",
+        TABLE_PROPERTY_PTR => "PropertyPtr",
+        TABLE_PROPERTY => "Property",
+        TABLE_METHOD_SEMANTICS => "MethodSemantics",
+        TABLE_METHOD_IMPL => "MethodImpl",
+        TABLE_MODULE_REF => "ModuleRef",
+        TABLE_TYPE_SPEC => "TypeSpec",
+        TABLE_IMPL_MAP => "ImplMap",
+        TABLE_FIELD_RVA => "FieldRVA",
+        TABLE_ENC_LOG => "EncLog",
+        TABLE_ENC_MAP => "EncMap",
+        TABLE_ASSEMBLY => "Assembly",
+        TABLE_ASSEMBLY_PROCESSOR => "AssemblyProcessor",
+        TABLE_ASSEMBLY_OS => "AssemblyOS",
+        TABLE_ASSEMBLY_REF => "AssemblyRef",
+        TABLE_ASSEMBLY_REF_PROCESSOR => "AssemblyRefProcessor",
+        TABLE_ASSEMBLY_REF_OS => "AssemblyRefOS",
+        TABLE_FILE => "File",
+        TABLE_EXPORTED_TYPE => "ExportedType",
+        TABLE_MANIFEST_RESOURCE => "ManifestResource",
+        TABLE_NESTED_CLASS => "NestedClass",
+        TABLE_GENERIC_PARAM => "GenericParam",
+        TABLE_METHOD_SPEC => "MethodSpec",
+        TABLE_GENERIC_PARAM_CONSTRAINT => "GenericParamConstraint",
+        _ => return None,
+    };
+
+    Some(name)
+}
+
+// column widths that depend on the heap sizes and the row counts
+#[derive(Clone, Copy, Debug)]
+struct Sizes {
+    heap_sizes: u8,
+    row_counts: [u32; 64],
+}
+
+impl Default for Sizes {
+    fn default() -> Self {
+        Sizes { heap_sizes: 0, row_counts: [0; 64] }
+    }
+}
+
+impl Sizes {
+    fn column(&self, column: Column) -> usize {
+        match column {
+            Column::U16 => 2,
+            Column::U32 => 4,
+            Column::String => self.heap(HEAP_STRING_4),
+            Column::Guid => self.heap(HEAP_GUID_4),
+            Column::Blob => self.heap(HEAP_BLOB_4),
+            Column::Table(table) => self.table(table),
+            Column::Coded(coded_index) => self.coded(coded_index),
+        }
+    }
+
+    fn heap(&self, flag: u8) -> usize {
+        if self.heap_sizes & flag != 0 {
+            4
+        } else {
+            2
+        }
+    }
+
+    fn table(&self, table: u8) -> usize {
+        if self.row_counts[table as usize] < 0x10000 {
+            2
+        } else {
+            4
+        }
+    }
+
+    // 2 bytes while the largest table still fits in the bits left over by the tag
+    fn coded(&self, coded_index: CodedIndex) -> usize {
+        let max = coded_index.tables().iter().filter(|w| **w != UNUSED).map(|w| self.row_counts[*w as usize]).max().unwrap_or(0);
+        if (max as u64) < 1 << (16 - coded_index.tag_bits()) {
+            2
+        } else {
+            4
+        }
+    }
+
+    fn row(&self, table: u8) -> usize {
+        SCHEMA[table as usize].iter().map(|w| self.column(*w)).sum()
+    }
+}
+
+// reads the columns of a row in order
+struct Cursor<'a> {
+    data: &'a [u8],
+    offset: usize,
+    sizes: &'a Sizes,
+    strings: StringsHeap<'a>,
+}
+
+impl<'a> Cursor<'a> {
+    fn read(&mut self, size: usize) -> Result<u32, failure::Error> {
+        let address = self.offset;
+        let value = match size {
+            2 => self.data.gread_with::<u16>(&mut self.offset, LE).map(|w| w as u32),
+            _ => self.data.gread_with::<u32>(&mut self.offset, LE),
+        };
+
+        value.map_err(|_| {
+            let msg = format!("Failed to read the metadata table row at {:#X}", address);
+            failure::err_msg(msg)
+        })
+    }
+
+    fn u16(&mut self) -> Result<u16, failure::Error> {
+        Ok(self.read(2)? as u16)
+    }
+
+    fn u32(&mut self) -> Result<u32, failure::Error> {
+        self.read(4)
+    }
+
+    fn string(&mut self) -> Result<String, failure::Error> {
+        let index = self.read(self.sizes.column(Column::String))?;
+        Ok(self.strings.get(index)?.to_owned())
+    }
+
+    fn guid(&mut self) -> Result<u32, failure::Error> {
+        self.read(self.sizes.column(Column::Guid))
+    }
+
+    fn blob(&mut self) -> Result<u32, failure::Error> {
+        self.read(self.sizes.column(Column::Blob))
+    }
+
+    fn table(&mut self, table: u8) -> Result<u32, failure::Error> {
+        self.read(self.sizes.column(Column::Table(table)))
+    }
+
+    fn coded(&mut self, coded_index: CodedIndex) -> Result<Token, failure::Error> {
+        let value = self.read(self.sizes.column(Column::Coded(coded_index)))?;
+        decode_coded_index(coded_index, value)
+    }
+}
+
+pub fn decode_coded_index(coded_index: CodedIndex, value: u32) -> Result<Token, failure::Error> {
+    let bits = coded_index.tag_bits();
+    let tag = value & ((1 << bits) - 1);
+
+    match coded_index.tables().get(tag as usize) {
+        Some(table) if *table != UNUSED => Ok(Token::new(*table, value >> bits)),
+        _ => {
+            let msg = format!("Invalid {:?} coded index {:#X}", coded_index, value);
+            Err(failure::err_msg(msg))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ModuleRow {
+    generation: u16,
+    name: String,
+    mvid: u32,
+    enc_id: u32,
+    enc_base_id: u32,
+}
+
+impl ModuleRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(ModuleRow {
+            generation: cursor.u16()?,
+            name: cursor.string()?,
+            mvid: cursor.guid()?,
+            enc_id: cursor.guid()?,
+            enc_base_id: cursor.guid()?,
+        })
+    }
+
+    // #GUID index
+    pub fn enc_base_id(&self) -> u32 {
+        self.enc_base_id
+    }
+
+    // #GUID index
+    pub fn enc_id(&self) -> u32 {
+        self.enc_id
+    }
+
+    pub fn generation(&self) -> u16 {
+        self.generation
+    }
+
+    // #GUID index of the module version identifier
+    pub fn mvid(&self) -> u32 {
+        self.mvid
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[derive(Debug)]
+pub struct TypeRefRow {
+    resolution_scope: Token,
+    type_name: String,
+    type_namespace: String,
+}
+
+impl TypeRefRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(TypeRefRow {
+            resolution_scope: cursor.coded(CodedIndex::ResolutionScope)?,
+            type_name: cursor.string()?,
+            type_namespace: cursor.string()?,
+        })
+    }
+
+    pub fn resolution_scope(&self) -> Token {
+        self.resolution_scope
+    }
+
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    pub fn type_namespace(&self) -> &str {
+        &self.type_namespace
+    }
+}
+
+#[derive(Debug)]
+pub struct TypeDefRow {
+    flags: u32,
+    type_name: String,
+    type_namespace: String,
+    extends: Token,
+    field_list: u32,
+    method_list: u32,
+}
+
+impl TypeDefRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(TypeDefRow {
+            flags: cursor.u32()?,
+            type_name: cursor.string()?,
+            type_namespace: cursor.string()?,
+            extends: cursor.coded(CodedIndex::TypeDefOrRef)?,
+            field_list: cursor.table(TABLE_FIELD)?,
+            method_list: cursor.table(TABLE_METHOD_DEF)?,
+        })
+    }
+
+    pub fn extends(&self) -> Token {
+        self.extends
+    }
+
+    // first row of the fields owned by the type, they run up to the field list of the next type
+    pub fn field_list(&self) -> u32 {
+        self.field_list
+    }
+
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    // first row of the methods owned by the type, they run up to the method list of the next type
+    pub fn method_list(&self) -> u32 {
+        self.method_list
+    }
+
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    pub fn type_namespace(&self) -> &str {
+        &self.type_namespace
+    }
+}
+
+#[derive(Debug)]
+pub struct FieldRow {
+    flags: u16,
+    name: String,
+    signature: u32,
+}
+
+impl FieldRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(FieldRow {
+            flags: cursor.u16()?,
+            name: cursor.string()?,
+            signature: cursor.blob()?,
+        })
+    }
+
+    pub fn flags(&self) -> u16 {
+        self.flags
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // #Blob index
+    pub fn signature(&self) -> u32 {
+        self.signature
+    }
+}
+
+#[derive(Debug)]
+pub struct MethodDefRow {
+    rva: u32,
+    impl_flags: u16,
+    flags: u16,
+    name: String,
+    signature: u32,
+    param_list: u32,
+}
+
+impl MethodDefRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(MethodDefRow {
+            rva: cursor.u32()?,
+            impl_flags: cursor.u16()?,
+            flags: cursor.u16()?,
+            name: cursor.string()?,
+            signature: cursor.blob()?,
+            param_list: cursor.table(TABLE_PARAM)?,
+        })
+    }
+
+    pub fn flags(&self) -> u16 {
+        self.flags
+    }
+
+    pub fn impl_flags(&self) -> u16 {
+        self.impl_flags
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // first row of the parameters, they run up to the param list of the next method
+    pub fn param_list(&self) -> u32 {
+        self.param_list
+    }
+
+    // 0 for abstract, runtime implemented and P/Invoke methods
+    pub fn rva(&self) -> u32 {
+        self.rva
+    }
+
+    // #Blob index
+    pub fn signature(&self) -> u32 {
+        self.signature
+    }
+}
+
+#[derive(Debug)]
+pub struct ParamRow {
+    flags: u16,
+    sequence: u16,
+    name: String,
+}
+
+impl ParamRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(ParamRow {
+            flags: cursor.u16()?,
+            sequence: cursor.u16()?,
+            name: cursor.string()?,
+        })
+    }
+
+    pub fn flags(&self) -> u16 {
+        self.flags
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // 0 for the return value, parameters are numbered from 1
+    pub fn sequence(&self) -> u16 {
+        self.sequence
+    }
+}
+
+#[derive(Debug)]
+pub struct MemberRefRow {
+    class: Token,
+    name: String,
+    signature: u32,
+}
+
+impl MemberRefRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(MemberRefRow {
+            class: cursor.coded(CodedIndex::MemberRefParent)?,
+            name: cursor.string()?,
+            signature: cursor.blob()?,
+        })
+    }
+
+    pub fn class(&self) -> Token {
+        self.class
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // #Blob index
+    pub fn signature(&self) -> u32 {
+        self.signature
+    }
+}
+
+#[derive(Debug)]
+pub struct CustomAttributeRow {
+    parent: Token,
+    r#type: Token,
+    value: u32,
+}
+
+impl CustomAttributeRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(CustomAttributeRow {
+            parent: cursor.coded(CodedIndex::HasCustomAttribute)?,
+            r#type: cursor.coded(CodedIndex::CustomAttributeType)?,
+            value: cursor.blob()?,
+        })
+    }
+
+    pub fn parent(&self) -> Token {
+        self.parent
+    }
+
+    // constructor of the attribute, a MethodDef or a MemberRef
+    pub fn r#type(&self) -> Token {
+        self.r#type
+    }
+
+    // #Blob index
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+#[derive(Debug)]
+pub struct StandAloneSigRow {
+    signature: u32,
+}
+
+impl StandAloneSigRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(StandAloneSigRow { signature: cursor.blob()? })
+    }
+
+    // #Blob index
+    pub fn signature(&self) -> u32 {
+        self.signature
+    }
+}
+
+#[derive(Debug)]
+pub struct PropertyRow {
+    flags: u16,
+    name: String,
+    signature: u32,
+}
+
+impl PropertyRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(PropertyRow {
+            flags: cursor.u16()?,
+            name: cursor.string()?,
+            signature: cursor.blob()?,
+        })
+    }
+
+    pub fn flags(&self) -> u16 {
+        self.flags
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // #Blob index, the Type column
+    pub fn signature(&self) -> u32 {
+        self.signature
+    }
+}
+
+#[derive(Debug)]
+pub struct PropertyMapRow {
+    parent: u32,
+    property_list: u32,
+}
+
+impl PropertyMapRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(PropertyMapRow {
+            parent: cursor.table(TABLE_TYPE_DEF)?,
+            property_list: cursor.table(TABLE_PROPERTY)?,
+        })
+    }
+
+    // TypeDef row
+    pub fn parent(&self) -> u32 {
+        self.parent
+    }
+
+    // first row of the properties, they run up to the property list of the next map
+    pub fn property_list(&self) -> u32 {
+        self.property_list
+    }
+}
+
+#[derive(Debug)]
+pub struct EventMapRow {
+    parent: u32,
+    event_list: u32,
+}
+
+impl EventMapRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(EventMapRow {
+            parent: cursor.table(TABLE_TYPE_DEF)?,
+            event_list: cursor.table(TABLE_EVENT)?,
+        })
+    }
+
+    // first row of the events, they run up to the event list of the next map
+    pub fn event_list(&self) -> u32 {
+        self.event_list
+    }
+
+    // TypeDef row
+    pub fn parent(&self) -> u32 {
+        self.parent
+    }
+}
+
+#[derive(Debug)]
+pub struct EventRow {
+    event_flags: u16,
+    name: String,
+    event_type: Token,
+}
+
+impl EventRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(EventRow {
+            event_flags: cursor.u16()?,
+            name: cursor.string()?,
+            event_type: cursor.coded(CodedIndex::TypeDefOrRef)?,
+        })
+    }
+
+    pub fn event_flags(&self) -> u16 {
+        self.event_flags
+    }
+
+    pub fn event_type(&self) -> Token {
+        self.event_type
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[derive(Debug)]
+pub struct ModuleRefRow {
+    name: String,
+}
+
+impl ModuleRefRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(ModuleRefRow { name: cursor.string()? })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[derive(Debug)]
+pub struct TypeSpecRow {
+    signature: u32,
+}
+
+impl TypeSpecRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(TypeSpecRow { signature: cursor.blob()? })
+    }
+
+    // #Blob index
+    pub fn signature(&self) -> u32 {
+        self.signature
+    }
+}
+
+#[derive(Debug)]
+pub struct ImplMapRow {
+    mapping_flags: u16,
+    member_forwarded: Token,
+    import_name: String,
+    import_scope: u32,
+}
+
+impl ImplMapRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(ImplMapRow {
+            mapping_flags: cursor.u16()?,
+            member_forwarded: cursor.coded(CodedIndex::MemberForwarded)?,
+            import_name: cursor.string()?,
+            import_scope: cursor.table(TABLE_MODULE_REF)?,
+        })
+    }
+
+    pub fn import_name(&self) -> &str {
+        &self.import_name
+    }
+
+    // ModuleRef row
+    pub fn import_scope(&self) -> u32 {
+        self.import_scope
+    }
+
+    pub fn mapping_flags(&self) -> u16 {
+        self.mapping_flags
+    }
+
+    pub fn member_forwarded(&self) -> Token {
+        self.member_forwarded
+    }
+}
+
+#[derive(Debug)]
+pub struct AssemblyRow {
+    hash_alg_id: u32,
+    major_version: u16,
+    minor_version: u16,
+    build_number: u16,
+    revision_number: u16,
+    flags: u32,
+    public_key: u32,
+    name: String,
+    culture: String,
+}
+
+impl AssemblyRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(AssemblyRow {
+            hash_alg_id: cursor.u32()?,
+            major_version: cursor.u16()?,
+            minor_version: cursor.u16()?,
+            build_number: cursor.u16()?,
+            revision_number: cursor.u16()?,
+            flags: cursor.u32()?,
+            public_key: cursor.blob()?,
+            name: cursor.string()?,
+            culture: cursor.string()?,
+        })
+    }
+
+    pub fn build_number(&self) -> u16 {
+        self.build_number
+    }
+
+    pub fn culture(&self) -> &str {
+        &self.culture
+    }
+
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    pub fn hash_alg_id(&self) -> u32 {
+        self.hash_alg_id
+    }
+
+    pub fn major_version(&self) -> u16 {
+        self.major_version
+    }
+
+    pub fn minor_version(&self) -> u16 {
+        self.minor_version
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // #Blob index
+    pub fn public_key(&self) -> u32 {
+        self.public_key
+    }
+
+    pub fn revision_number(&self) -> u16 {
+        self.revision_number
+    }
+}
+
+#[derive(Debug)]
+pub struct AssemblyRefRow {
+    major_version: u16,
+    minor_version: u16,
+    build_number: u16,
+    revision_number: u16,
+    flags: u32,
+    public_key_or_token: u32,
+    name: String,
+    culture: String,
+    hash_value: u32,
+}
+
+impl AssemblyRefRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(AssemblyRefRow {
+            major_version: cursor.u16()?,
+            minor_version: cursor.u16()?,
+            build_number: cursor.u16()?,
+            revision_number: cursor.u16()?,
+            flags: cursor.u32()?,
+            public_key_or_token: cursor.blob()?,
+            name: cursor.string()?,
+            culture: cursor.string()?,
+            hash_value: cursor.blob()?,
+        })
+    }
+
+    pub fn build_number(&self) -> u16 {
+        self.build_number
+    }
+
+    pub fn culture(&self) -> &str {
+        &self.culture
+    }
+
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    // #Blob index
+    pub fn hash_value(&self) -> u32 {
+        self.hash_value
+    }
+
+    pub fn major_version(&self) -> u16 {
+        self.major_version
+    }
+
+    pub fn minor_version(&self) -> u16 {
+        self.minor_version
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // #Blob index, the full key if the PublicKey flag is set, otherwise the token
+    pub fn public_key_or_token(&self) -> u32 {
+        self.public_key_or_token
+    }
+
+    pub fn revision_number(&self) -> u16 {
+        self.revision_number
+    }
+}
+
+#[derive(Debug)]
+pub struct ManifestResourceRow {
+    offset: u32,
+    flags: u32,
+    name: String,
+    implementation: Token,
+}
+
+impl ManifestResourceRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(ManifestResourceRow {
+            offset: cursor.u32()?,
+            flags: cursor.u32()?,
+            name: cursor.string()?,
+            implementation: cursor.coded(CodedIndex::Implementation)?,
+        })
+    }
+
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    // null if the resource is embedded in this image
+    pub fn implementation(&self) -> Token {
+        self.implementation
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // from the beginning of the resources of the CLR header
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+}
+
+#[derive(Debug)]
+pub struct NestedClassRow {
+    nested_class: u32,
+    enclosing_class: u32,
+}
+
+impl NestedClassRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(NestedClassRow {
+            nested_class: cursor.table(TABLE_TYPE_DEF)?,
+            enclosing_class: cursor.table(TABLE_TYPE_DEF)?,
+        })
+    }
+
+    // TypeDef row
+    pub fn enclosing_class(&self) -> u32 {
+        self.enclosing_class
+    }
+
+    // TypeDef row
+    pub fn nested_class(&self) -> u32 {
+        self.nested_class
+    }
+}
+
+#[derive(Debug)]
+pub struct GenericParamRow {
+    number: u16,
+    flags: u16,
+    owner: Token,
+    name: String,
+}
+
+impl GenericParamRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(GenericParamRow {
+            number: cursor.u16()?,
+            flags: cursor.u16()?,
+            owner: cursor.coded(CodedIndex::TypeOrMethodDef)?,
+            name: cursor.string()?,
+        })
+    }
+
+    pub fn flags(&self) -> u16 {
+        self.flags
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn number(&self) -> u16 {
+        self.number
+    }
+
+    pub fn owner(&self) -> Token {
+        self.owner
+    }
+}
+
+#[derive(Debug)]
+pub struct MethodSpecRow {
+    method: Token,
+    instantiation: u32,
+}
+
+impl MethodSpecRow {
+    fn read(cursor: &mut Cursor) -> Result<Self, failure::Error> {
+        Ok(MethodSpecRow {
+            method: cursor.coded(CodedIndex::MethodDefOrRef)?,
+            instantiation: cursor.blob()?,
+        })
+    }
+
+    // #Blob index
+    pub fn instantiation(&self) -> u32 {
+        self.instantiation
+    }
+
+    pub fn method(&self) -> Token {
+        self.method
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct MetadataTables {
+    major_version: u8,
+    minor_version: u8,
+    valid: u64,
+    sorted: u64,
+    sizes: Sizes,
+
+    modules: Vec<ModuleRow>,
+    type_refs: Vec<TypeRefRow>,
+    type_defs: Vec<TypeDefRow>,
+    // the *Ptr tables of uncompressed (#-) streams, the row of the target table for each row
+    field_ptrs: Vec<u32>,
+    fields: Vec<FieldRow>,
+    method_ptrs: Vec<u32>,
+    method_defs: Vec<MethodDefRow>,
+    param_ptrs: Vec<u32>,
+    params: Vec<ParamRow>,
+    member_refs: Vec<MemberRefRow>,
+    custom_attributes: Vec<CustomAttributeRow>,
+    stand_alone_sigs: Vec<StandAloneSigRow>,
+    event_maps: Vec<EventMapRow>,
+    event_ptrs: Vec<u32>,
+    events: Vec<EventRow>,
+    property_maps: Vec<PropertyMapRow>,
+    property_ptrs: Vec<u32>,
+    properties: Vec<PropertyRow>,
+    module_refs: Vec<ModuleRefRow>,
+    type_specs: Vec<TypeSpecRow>,
+    impl_maps: Vec<ImplMapRow>,
+    assemblies: Vec<AssemblyRow>,
+    assembly_refs: Vec<AssemblyRefRow>,
+    manifest_resources: Vec<ManifestResourceRow>,
+    nested_classes: Vec<NestedClassRow>,
+    generic_params: Vec<GenericParamRow>,
+    method_specs: Vec<MethodSpecRow>,
+}
+
+impl MetadataTables {
+    pub fn parse(metadata_root: &MetadataRoot) -> Result<Option<Self>, failure::Error> {
+        let stream = match metadata_root.tables_stream() {
+            Some(stream) => stream,
+            None => return Ok(None),
+        };
+
+        let data = stream.data();
+        let read = |_: scroll::Error| failure::err_msg("Failed to read the metadata tables header");
+
+        // reserved, always 0
+        let mut offset = 4;
+        let major_version = data.gread_with::<u8>(&mut offset, LE).map_err(read)?;
+        let minor_version = data.gread_with::<u8>(&mut offset, LE).map_err(read)?;
+        let heap_sizes = data.gread_with::<u8>(&mut offset, LE).map_err(read)?;
+        offset += 1; // reserved, always 1
+        let valid = data.gread_with::<u64>(&mut offset, LE).map_err(read)?;
+        let sorted = data.gread_with::<u64>(&mut offset, LE).map_err(read)?;
+
+        let mut row_counts = [0u32; 64];
+        for (table, row_count) in row_counts.iter_mut().enumerate() {
+            if valid & (1 << table) != 0 {
+                *row_count = data.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+            }
+        }
+
+        // the layout of tables beyond GenericParamConstraint is unknown, e.g. portable PDB tables
+        if valid >> NUMBER_OF_TABLES != 0 {
+            let msg = format!("Unsupported metadata tables in the valid mask {:#018X}", valid);
+            return Err(failure::err_msg(msg));
+        }
+
+        if heap_sizes & HEAP_EXTRA_DATA != 0 {
+            offset += 4;
+        }
+
+        let sizes = Sizes { heap_sizes, row_counts };
+        let mut tables = MetadataTables {
+            major_version,
+            minor_version,
+            valid,
+            sorted,
+            sizes,
+            ..Default::default()
+        };
+
+        // checked before reading any row, a crafted row count would otherwise loop billions of times
+        let size = (0..NUMBER_OF_TABLES as u8).try_fold(offset, |size, table| (row_counts[table as usize] as usize).checked_mul(sizes.row(table)).and_then(|w| size.checked_add(w)));
+        match size {
+            Some(size) if size <= data.len() => (),
+            _ => {
+                let msg = format!("Metadata tables exceed the {} stream ({:#X} bytes)", stream.name(), data.len());
+                return Err(failure::err_msg(msg));
+            }
+        }
+
+        // tables follow each other in the order of the table number
+        let mut cursor = Cursor {
+            data,
+            offset,
+            sizes: &sizes,
+            strings: metadata_root.strings(),
+        };
+
+        for table in 0..NUMBER_OF_TABLES as u8 {
+            let row_size = sizes.row(table);
+            let start = cursor.offset;
+
+            for row in 0..row_counts[table as usize] as usize {
+                cursor.offset = start + row * row_size;
+                tables.read_row(table, &mut cursor)?;
+            }
+
+            cursor.offset = start + row_counts[table as usize] as usize * row_size;
+        }
+
+        Ok(Some(tables))
+    }
+
+    fn read_row(&mut self, table: u8, cursor: &mut Cursor) -> Result<(), failure::Error> {
+        match table {
+            TABLE_MODULE => self.modules.push(ModuleRow::read(cursor)?),
+            TABLE_TYPE_REF => self.type_refs.push(TypeRefRow::read(cursor)?),
+            TABLE_TYPE_DEF => self.type_defs.push(TypeDefRow::read(cursor)?),
+            TABLE_FIELD_PTR => self.field_ptrs.push(cursor.table(TABLE_FIELD)?),
+            TABLE_FIELD => self.fields.push(FieldRow::read(cursor)?),
+            TABLE_METHOD_PTR => self.method_ptrs.push(cursor.table(TABLE_METHOD_DEF)?),
+            TABLE_METHOD_DEF => self.method_defs.push(MethodDefRow::read(cursor)?),
+            TABLE_PARAM_PTR => self.param_ptrs.push(cursor.table(TABLE_PARAM)?),
+            TABLE_PARAM => self.params.push(ParamRow::read(cursor)?),
+            TABLE_MEMBER_REF => self.member_refs.push(MemberRefRow::read(cursor)?),
+            TABLE_CUSTOM_ATTRIBUTE => self.custom_attributes.push(CustomAttributeRow::read(cursor)?),
+            TABLE_STAND_ALONE_SIG => self.stand_alone_sigs.push(StandAloneSigRow::read(cursor)?),
+            TABLE_EVENT_MAP => self.event_maps.push(EventMapRow::read(cursor)?),
+            TABLE_EVENT_PTR => self.event_ptrs.push(cursor.table(TABLE_EVENT)?),
+            TABLE_EVENT => self.events.push(EventRow::read(cursor)?),
+            TABLE_PROPERTY_MAP => self.property_maps.push(PropertyMapRow::read(cursor)?),
+            TABLE_PROPERTY_PTR => self.property_ptrs.push(cursor.table(TABLE_PROPERTY)?),
+            TABLE_PROPERTY => self.properties.push(PropertyRow::read(cursor)?),
+            TABLE_MODULE_REF => self.module_refs.push(ModuleRefRow::read(cursor)?),
+            TABLE_TYPE_SPEC => self.type_specs.push(TypeSpecRow::read(cursor)?),
+            TABLE_IMPL_MAP => self.impl_maps.push(ImplMapRow::read(cursor)?),
+            TABLE_ASSEMBLY => self.assemblies.push(AssemblyRow::read(cursor)?),
+            TABLE_ASSEMBLY_REF => self.assembly_refs.push(AssemblyRefRow::read(cursor)?),
+            TABLE_MANIFEST_RESOURCE => self.manifest_resources.push(ManifestResourceRow::read(cursor)?),
+            TABLE_NESTED_CLASS => self.nested_classes.push(NestedClassRow::read(cursor)?),
+            TABLE_GENERIC_PARAM => self.generic_params.push(GenericParamRow::read(cursor)?),
+            TABLE_METHOD_SPEC => self.method_specs.push(MethodSpecRow::read(cursor)?),
+            // only counted, rows are not decoded
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    // in bytes, 2 or 4 depending on the row counts of the referenced tables
+    pub fn coded_index_size(&self, coded_index: CodedIndex) -> usize {
+        self.sizes.coded(coded_index)
+    }
+
+    pub fn heap_sizes(&self) -> u8 {
+        self.sizes.heap_sizes
+    }
+
+    pub fn is_sorted(&self, table: u8) -> bool {
+        self.sorted & (1 << table) != 0
+    }
+
+    pub fn major_version(&self) -> u8 {
+        self.major_version
+    }
+
+    pub fn minor_version(&self) -> u8 {
+        self.minor_version
+    }
+
+    pub fn row_count(&self, table: u8) -> u32 {
+        self.sizes.row_counts.get(table as usize).copied().unwrap_or(0)
+    }
+
+    // in bytes
+    pub fn row_size(&self, table: u8) -> usize {
+        match table as usize {
+            table if table < NUMBER_OF_TABLES => self.sizes.row(table as u8),
+            _ => 0,
+        }
+    }
+
+    pub fn sorted(&self) -> u64 {
+        self.sorted
+    }
+
+    pub fn valid(&self) -> u64 {
+        self.valid
+    }
+
+    // the Assembly table has a single row at most
+    pub fn assembly(&self) -> Option<&AssemblyRow> {
+        self.assemblies.first()
+    }
+
+    pub fn assembly_refs(&self) -> Vec<&AssemblyRefRow> {
+        self.assembly_refs.iter().collect()
+    }
+
+    pub fn custom_attributes(&self) -> Vec<&CustomAttributeRow> {
+        self.custom_attributes.iter().collect()
+    }
+
+    pub fn event_maps(&self) -> Vec<&EventMapRow> {
+        self.event_maps.iter().collect()
+    }
+
+    pub fn events(&self) -> Vec<&EventRow> {
+        self.events.iter().collect()
+    }
+
+    pub fn fields(&self) -> Vec<&FieldRow> {
+        self.fields.iter().collect()
+    }
+
+    pub fn generic_params(&self) -> Vec<&GenericParamRow> {
+        self.generic_params.iter().collect()
+    }
+
+    pub fn impl_maps(&self) -> Vec<&ImplMapRow> {
+        self.impl_maps.iter().collect()
+    }
+
+    pub fn manifest_resources(&self) -> Vec<&ManifestResourceRow> {
+        self.manifest_resources.iter().collect()
+    }
+
+    pub fn member_refs(&self) -> Vec<&MemberRefRow> {
+        self.member_refs.iter().collect()
+    }
+
+    pub fn method_defs(&self) -> Vec<&MethodDefRow> {
+        self.method_defs.iter().collect()
+    }
+
+    pub fn method_specs(&self) -> Vec<&MethodSpecRow> {
+        self.method_specs.iter().collect()
+    }
+
+    pub fn module(&self) -> Option<&ModuleRow> {
+        self.modules.first()
+    }
+
+    pub fn module_refs(&self) -> Vec<&ModuleRefRow> {
+        self.module_refs.iter().collect()
+    }
+
+    pub fn nested_classes(&self) -> Vec<&NestedClassRow> {
+        self.nested_classes.iter().collect()
+    }
+
+    pub fn params(&self) -> Vec<&ParamRow> {
+        self.params.iter().collect()
+    }
+
+    pub fn properties(&self) -> Vec<&PropertyRow> {
+        self.properties.iter().collect()
+    }
+
+    pub fn property_maps(&self) -> Vec<&PropertyMapRow> {
+        self.property_maps.iter().collect()
+    }
+
+    pub fn stand_alone_sigs(&self) -> Vec<&StandAloneSigRow> {
+        self.stand_alone_sigs.iter().collect()
+    }
+
+    pub fn type_defs(&self) -> Vec<&TypeDefRow> {
+        self.type_defs.iter().collect()
+    }
+
+    pub fn type_refs(&self) -> Vec<&TypeRefRow> {
+        self.type_refs.iter().collect()
+    }
+
+    pub fn type_specs(&self) -> Vec<&TypeSpecRow> {
+        self.type_specs.iter().collect()
+    }
+
+    // lookups by the 1-based row of a token, None if the row is out of the table
+    pub fn field(&self, row: u32) -> Option<&FieldRow> {
+        get_row(&self.fields, row)
+    }
+
+    pub fn member_ref(&self, row: u32) -> Option<&MemberRefRow> {
+        get_row(&self.member_refs, row)
+    }
+
+    pub fn method_def(&self, row: u32) -> Option<&MethodDefRow> {
+        get_row(&self.method_defs, row)
+    }
+
+    pub fn method_spec(&self, row: u32) -> Option<&MethodSpecRow> {
+        get_row(&self.method_specs, row)
+    }
+
+    pub fn module_ref(&self, row: u32) -> Option<&ModuleRefRow> {
+        get_row(&self.module_refs, row)
+    }
+
+    pub fn stand_alone_sig(&self, row: u32) -> Option<&StandAloneSigRow> {
+        get_row(&self.stand_alone_sigs, row)
+    }
+
+    pub fn type_def(&self, row: u32) -> Option<&TypeDefRow> {
+        get_row(&self.type_defs, row)
+    }
+
+    pub fn type_ref(&self, row: u32) -> Option<&TypeRefRow> {
+        get_row(&self.type_refs, row)
+    }
+
+    pub fn type_spec(&self, row: u32) -> Option<&TypeSpecRow> {
+        get_row(&self.type_specs, row)
+    }
+
+    // TypeDef row that encloses the nested TypeDef row
+    pub fn enclosing_class_of(&self, type_def: u32) -> Option<u32> {
+        self.nested_classes.iter().find(|w| w.nested_class == type_def).map(|w| w.enclosing_class)
+    }
+
+    pub fn events_of(&self, type_def: u32) -> Vec<&EventRow> {
+        let map = match self.event_maps.iter().position(|w| w.parent == type_def) {
+            Some(map) => map as u32 + 1,
+            None => return Vec::new(),
+        };
+
+        let list = |w: &EventMapRow| w.event_list;
+        owned_rows(&self.event_maps, map, list, &self.event_ptrs, self.events.len())
+            .into_iter()
+            .filter_map(|w| get_row(&self.events, w))
+            .collect()
+    }
+
+    // rows of the fields owned by the TypeDef row
+    pub fn fields_of(&self, type_def: u32) -> Vec<u32> {
+        let list = |w: &TypeDefRow| w.field_list;
+        owned_rows(&self.type_defs, type_def, list, &self.field_ptrs, self.fields.len())
+    }
+
+    // ordered by the number of the parameter
+    pub fn generic_params_of(&self, owner: Token) -> Vec<&GenericParamRow> {
+        let mut generic_params: Vec<&GenericParamRow> = self.generic_params.iter().filter(|w| w.owner == owner).collect();
+        generic_params.sort_by_key(|w| w.number);
+        generic_params
+    }
+
+    // rows of the methods owned by the TypeDef row
+    pub fn methods_of(&self, type_def: u32) -> Vec<u32> {
+        let list = |w: &TypeDefRow| w.method_list;
+        owned_rows(&self.type_defs, type_def, list, &self.method_ptrs, self.method_defs.len())
+    }
+
+    // TypeDef row that owns the Field row
+    pub fn owner_of_field(&self, field: u32) -> Option<u32> {
+        (1..=self.type_defs.len() as u32).find(|w| self.fields_of(*w).contains(&field))
+    }
+
+    // TypeDef row that owns the MethodDef row
+    pub fn owner_of_method(&self, method_def: u32) -> Option<u32> {
+        (1..=self.type_defs.len() as u32).find(|w| self.methods_of(*w).contains(&method_def))
+    }
+
+    pub fn params_of(&self, method_def: u32) -> Vec<&ParamRow> {
+        let list = |w: &MethodDefRow| w.param_list;
+        owned_rows(&self.method_defs, method_def, list, &self.param_ptrs, self.params.len())
+            .into_iter()
+            .filter_map(|w| get_row(&self.params, w))
+            .collect()
+    }
+
+    pub fn properties_of(&self, type_def: u32) -> Vec<&PropertyRow> {
+        let map = match self.property_maps.iter().position(|w| w.parent == type_def) {
+            Some(map) => map as u32 + 1,
+            None => return Vec::new(),
+        };
+
+        let list = |w: &PropertyMapRow| w.property_list;
+        owned_rows(&self.property_maps, map, list, &self.property_ptrs, self.properties.len())
+            .into_iter()
+            .filter_map(|w| get_row(&self.properties, w))
+            .collect()
+    }
+}
+
+fn get_row<T>(rows: &[T], row: u32) -> Option<&T> {
+    row.checked_sub(1).and_then(|w| rows.get(w as usize))
+}
+
+// II.22, a list column points to the first owned row, the run ends at the list of the next owner or at the end of the table
+// in #- streams with a *Ptr table, the run is in the rows of the *Ptr table, which hold the rows of the target table
+fn owned_rows<T>(owners: &[T], owner: u32, list: impl Fn(&T) -> u32, ptrs: &[u32], number_of_rows: usize) -> Vec<u32> {
+    let number_of_rows = if ptrs.is_empty() { number_of_rows } else { ptrs.len() };
+
+    let start = match get_row(owners, owner) {
+        Some(row) => list(row),
+        None => return Vec::new(),
+    };
+
+    let end = match get_row(owners, owner + 1) {
+        Some(row) => list(row),
+        None => number_of_rows as u32 + 1,
+    };
+
+    let rows: Range<u32> = start..end.max(start).min(number_of_rows as u32 + 1);
+    if ptrs.is_empty() {
+        rows.collect()
+    } else {
+        rows.filter_map(|w| get_row(ptrs, w).copied()).collect()
+    }
+}
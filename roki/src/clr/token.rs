@@ -0,0 +1,41 @@
+use std::fmt::{Display, Formatter};
+
+// the table of a ldstr operand, the row is an offset into the #US heap
+pub const TOKEN_USER_STRING: u8 = 0x70;
+
+// metadata token, the table in the high byte and the 1-based row in the low 3 bytes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Token(u32);
+
+impl Token {
+    pub fn new(table: u8, row: u32) -> Self {
+        Token((table as u32) << 24 | (row & 0x00FF_FFFF))
+    }
+
+    pub fn from_raw(value: u32) -> Self {
+        Token(value)
+    }
+
+    // no row is referenced
+    pub fn is_null(&self) -> bool {
+        self.row() == 0
+    }
+
+    pub fn row(&self) -> u32 {
+        self.0 & 0x00FF_FFFF
+    }
+
+    pub fn table(&self) -> u8 {
+        (self.0 >> 24) as u8
+    }
+
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:#010X}", self.0)
+    }
+}
@@ -33,3 +33,59 @@ pub const IMAGE_DIRECTORY_ENTRY_BOUND_IMPORT: u32 = 11;
 pub const IMAGE_DIRECTORY_ENTRY_IAT: u32 = 12;
 pub const IMAGE_DIRECTORY_ENTRY_DELAY_IMPORT: u32 = 13;
 pub const IMAGE_DIRECTORY_ENTRY_COM_DESCRIPTOR: u32 = 14;
+
+pub const IMAGE_FILE_MACHINE_I386: u16 = 0x014C;
+pub const IMAGE_FILE_MACHINE_R4000: u16 = 0x0166;
+pub const IMAGE_FILE_MACHINE_WCEMIPSV2: u16 = 0x0169;
+pub const IMAGE_FILE_MACHINE_ARMNT: u16 = 0x01C4;
+pub const IMAGE_FILE_MACHINE_IA64: u16 = 0x0200;
+pub const IMAGE_FILE_MACHINE_MIPS16: u16 = 0x0266;
+pub const IMAGE_FILE_MACHINE_MIPSFPU: u16 = 0x0366;
+pub const IMAGE_FILE_MACHINE_MIPSFPU16: u16 = 0x0466;
+pub const IMAGE_FILE_MACHINE_RISCV32: u16 = 0x5032;
+pub const IMAGE_FILE_MACHINE_RISCV64: u16 = 0x5064;
+pub const IMAGE_FILE_MACHINE_RISCV128: u16 = 0x5128;
+pub const IMAGE_FILE_MACHINE_AMD64: u16 = 0x8664;
+pub const IMAGE_FILE_MACHINE_ARM64: u16 = 0xAA64;
+
+pub const IMAGE_REL_BASED_ABSOLUTE: u8 = 0;
+pub const IMAGE_REL_BASED_HIGH: u8 = 1;
+pub const IMAGE_REL_BASED_LOW: u8 = 2;
+pub const IMAGE_REL_BASED_HIGHLOW: u8 = 3;
+pub const IMAGE_REL_BASED_HIGHADJ: u8 = 4;
+pub const IMAGE_REL_BASED_MACHINE_SPECIFIC_5: u8 = 5;
+pub const IMAGE_REL_BASED_RESERVED: u8 = 6;
+pub const IMAGE_REL_BASED_MACHINE_SPECIFIC_7: u8 = 7;
+pub const IMAGE_REL_BASED_MACHINE_SPECIFIC_8: u8 = 8;
+pub const IMAGE_REL_BASED_MACHINE_SPECIFIC_9: u8 = 9;
+pub const IMAGE_REL_BASED_DIR64: u8 = 10;
+
+pub const RT_CURSOR: u16 = 1;
+pub const RT_BITMAP: u16 = 2;
+pub const RT_ICON: u16 = 3;
+pub const RT_MENU: u16 = 4;
+pub const RT_DIALOG: u16 = 5;
+pub const RT_STRING: u16 = 6;
+pub const RT_FONTDIR: u16 = 7;
+pub const RT_FONT: u16 = 8;
+pub const RT_ACCELERATOR: u16 = 9;
+pub const RT_RCDATA: u16 = 10;
+pub const RT_MESSAGETABLE: u16 = 11;
+pub const RT_GROUP_CURSOR: u16 = 12;
+pub const RT_GROUP_ICON: u16 = 14;
+pub const RT_VERSION: u16 = 16;
+pub const RT_DLGINCLUDE: u16 = 17;
+pub const RT_PLUGPLAY: u16 = 19;
+pub const RT_VXD: u16 = 20;
+pub const RT_ANICURSOR: u16 = 21;
+pub const RT_ANIICON: u16 = 22;
+pub const RT_HTML: u16 = 23;
+pub const RT_MANIFEST: u16 = 24;
+
+pub const WIN_CERT_REVISION_1_0: u16 = 0x0100;
+pub const WIN_CERT_REVISION_2_0: u16 = 0x0200;
+
+pub const WIN_CERT_TYPE_X509: u16 = 0x0001;
+pub const WIN_CERT_TYPE_PKCS_SIGNED_DATA: u16 = 0x0002;
+pub const WIN_CERT_TYPE_RESERVED_1: u16 = 0x0003;
+pub const WIN_CERT_TYPE_TS_STACK_SIGNED: u16 = 0x0004;
@@ -0,0 +1,212 @@
+use std::fmt::{Display, Formatter};
+
+use scroll::Pread;
+
+use crate::constant::IMAGE_DIRECTORY_ENTRY_BOUND_IMPORT;
+use crate::directories::{BoundForwarderRef, BoundImportDescriptor};
+use crate::Executable;
+
+const MAX_DESCRIPTORS: usize = 0x1000;
+
+#[derive(Debug)]
+pub struct BoundForwarder {
+    forwarder_ref: BoundForwarderRef,
+    name: String,
+}
+
+impl BoundForwarder {
+    pub fn forwarder_ref(&self) -> &BoundForwarderRef {
+        &self.forwarder_ref
+    }
+
+    pub fn name(&self) -> String {
+        self.name.to_owned()
+    }
+
+    pub fn time_date_stamp(&self) -> u32 {
+        self.forwarder_ref.time_date_stamp()
+    }
+}
+
+#[derive(Debug)]
+pub struct BoundImport {
+    descriptor: BoundImportDescriptor,
+    name: String,
+    forwarders: Vec<BoundForwarder>,
+}
+
+impl BoundImport {
+    pub fn descriptor(&self) -> &BoundImportDescriptor {
+        &self.descriptor
+    }
+
+    // DLLs that the bound module forwards some of the imported functions to
+    pub fn forwarders(&self) -> Vec<&BoundForwarder> {
+        self.forwarders.iter().collect()
+    }
+
+    pub fn name(&self) -> String {
+        self.name.to_owned()
+    }
+
+    // of the DLL that the imports were bound against
+    pub fn time_date_stamp(&self) -> u32 {
+        self.descriptor.time_date_stamp()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BindingIssue {
+    // a new style (-1) import descriptor without an entry in the bound import table, the loader resolves its imports again
+    MissingBoundImport(String),
+    // an entry of the bound import table that no import or delay import descriptor refers to
+    UnreferencedBoundImport(String),
+    // a forwarder that is also bound directly, against another timestamp
+    InconsistentForwarder { module: String, forwarder: String, time_date_stamp: u32, expected: u32 },
+    // the DLL was rebuilt after the image was bound, so the bound addresses are discarded at load time
+    StaleBinding { module: String, bound: u32, actual: u32 },
+}
+
+impl Display for BindingIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BindingIssue::MissingBoundImport(module) => write!(f, "{} is bound new style but missing in the bound import table", module),
+            BindingIssue::UnreferencedBoundImport(module) => write!(f, "{} is in the bound import table but not imported", module),
+            BindingIssue::InconsistentForwarder {
+                module,
+                forwarder,
+                time_date_stamp,
+                expected,
+            } => write!(f, "{} forwards to {} bound at {:#010X}, but {} is bound at {:#010X}", module, forwarder, time_date_stamp, forwarder, expected),
+            BindingIssue::StaleBinding { module, bound, actual } => write!(f, "{} is bound at {:#010X}, but the DLL is stamped {:#010X}", module, bound, actual),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BoundImportContainer {
+    imports: Vec<BoundImport>,
+}
+
+impl BoundImportContainer {
+    pub fn parse(executable: &Executable) -> Result<Option<Self>, failure::Error> {
+        let data_directory = executable.optional_header().unwrap().data_directories()[IMAGE_DIRECTORY_ENTRY_BOUND_IMPORT as usize];
+        if data_directory.size() == 0 {
+            return Ok(None);
+        }
+
+        // the table is usually placed right after the section headers, where RVA is equal to the file offset
+        let rva = data_directory.virtual_address();
+        let start = match executable.rva_to_offset(rva) {
+            Some(offset) => offset,
+            None if rva < executable.optional_header().unwrap().size_of_headers() => rva as usize,
+            None => {
+                let msg = "Failed to read bound import descriptor";
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        let mut offset = start;
+        let mut imports: Vec<BoundImport> = Vec::new();
+
+        loop {
+            let descriptor = BoundImportDescriptor::parse(executable, &mut offset)?;
+            if descriptor.time_date_stamp() == 0 && descriptor.offset_module_name() == 0 {
+                break;
+            }
+
+            if imports.len() >= MAX_DESCRIPTORS {
+                let msg = "Bound import descriptor table is not terminated";
+                return Err(failure::err_msg(msg));
+            }
+
+            let mut forwarders: Vec<BoundForwarder> = Vec::new();
+            for _ in 0..descriptor.number_of_module_forwarder_refs() {
+                let forwarder_ref = BoundForwarderRef::parse(executable, &mut offset)?;
+                let name = BoundImportContainer::read_name(executable, start, forwarder_ref.offset_module_name())?;
+
+                forwarders.push(BoundForwarder { forwarder_ref, name });
+            }
+
+            let name = BoundImportContainer::read_name(executable, start, descriptor.offset_module_name())?;
+            imports.push(BoundImport { descriptor, name, forwarders });
+        }
+
+        Ok(Some(BoundImportContainer { imports }))
+    }
+
+    fn read_name(executable: &Executable, start: usize, offset_module_name: u16) -> Result<String, failure::Error> {
+        let address = start + offset_module_name as usize;
+        let name = executable.buffer().pread::<&str>(address).map_err(|_| {
+            let msg = format!("Failed to read the bound module name at {:#010X}", address);
+            failure::err_msg(msg)
+        })?;
+
+        Ok(name.to_owned())
+    }
+
+    // DLL names are case insensitive
+    pub fn import_of(&self, name: &str) -> Option<&BoundImport> {
+        self.imports.iter().find(|w| w.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn imports(&self) -> Vec<&BoundImport> {
+        self.imports.iter().collect()
+    }
+}
+
+// `time_date_stamp_of` returns the TimeDateStamp of the DLL by its name, None if it is not available
+// stale bindings are only detected for the DLLs it resolves
+pub fn verify_binding(executable: &Executable, mut time_date_stamp_of: impl FnMut(&str) -> Option<u32>) -> Vec<BindingIssue> {
+    let mut issues: Vec<BindingIssue> = Vec::new();
+    let mut stale = |module: &str, bound: u32, issues: &mut Vec<BindingIssue>| {
+        if let Some(actual) = time_date_stamp_of(module).filter(|w| *w != bound) {
+            let issue = BindingIssue::StaleBinding { module: module.to_owned(), bound, actual };
+            if !issues.contains(&issue) {
+                issues.push(issue);
+            }
+        }
+    };
+
+    let descriptors = executable.import_data().map(|w| w.descriptors()).unwrap_or_default();
+    for descriptor in descriptors.iter() {
+        match descriptor.bound_time_date_stamp() {
+            Some(bound) => stale(&descriptor.name(), bound, &mut issues),
+            None if descriptor.is_new_style_bound() => issues.push(BindingIssue::MissingBoundImport(descriptor.name())),
+            None => (),
+        }
+    }
+
+    // bound delay imports have the TimeDateStamp of the DLL in their descriptor
+    let delay_descriptors = executable.delay_import_data().map(|w| w.descriptors()).unwrap_or_default();
+    for descriptor in delay_descriptors.iter().filter(|w| w.time_date_stamp() != 0) {
+        stale(&descriptor.name(), descriptor.time_date_stamp(), &mut issues);
+    }
+
+    let bound_imports = executable.bound_import_data().map(|w| w.imports()).unwrap_or_default();
+    for import in bound_imports.iter() {
+        let is_imported =
+            descriptors.iter().any(|w| w.is_bound() && w.name().eq_ignore_ascii_case(&import.name)) || delay_descriptors.iter().any(|w| w.time_date_stamp() != 0 && w.name().eq_ignore_ascii_case(&import.name));
+        if !is_imported {
+            issues.push(BindingIssue::UnreferencedBoundImport(import.name()));
+        }
+
+        // a forwarder that is also imported directly must be bound against the same DLL
+        for forwarder in import.forwarders.iter() {
+            let time_date_stamp = forwarder.time_date_stamp();
+            let direct = bound_imports.iter().find(|w| w.name.eq_ignore_ascii_case(&forwarder.name));
+
+            match direct.map(|w| w.time_date_stamp()) {
+                Some(expected) if expected != time_date_stamp => issues.push(BindingIssue::InconsistentForwarder {
+                    module: import.name(),
+                    forwarder: forwarder.name(),
+                    time_date_stamp,
+                    expected,
+                }),
+                _ => stale(&forwarder.name, time_date_stamp, &mut issues),
+            }
+        }
+    }
+
+    issues
+}
@@ -1,9 +1,26 @@
-use crate::headers::Cor20Header;
+use crate::clr::{ready_to_run_machine, AssemblyIdentity, AssemblyReference, ManagedResource, MetadataRoot, MetadataTables, NativeHeader, PInvokeImport, StrongNameSignature};
+use crate::headers::{Cor20Header, PlatformTarget};
 use crate::Executable;
 
+// the CLR header and the metadata root are required, the views derived from the metadata keep their own parse result
+// so that one malformed table or blob does not hide the rest of the image
 #[derive(Debug)]
 pub struct ClrContainer {
   cor20_header: Cor20Header,
+  metadata_root: MetadataRoot,
+  metadata_tables: Result<Option<MetadataTables>, String>,
+  assembly: Result<Option<AssemblyIdentity>, String>,
+  assembly_refs: Result<Vec<AssemblyReference>, String>,
+  managed_resources: Result<Vec<ManagedResource>, String>,
+  native_header: Result<Option<NativeHeader>, String>,
+  pinvoke_imports: Result<Vec<PInvokeImport>, String>,
+  platform_target: PlatformTarget,
+  strong_name_signature: Result<Option<StrongNameSignature>, String>,
+}
+
+// the error message of a view, failure::Error is not Clone
+fn view<T>(result: &Result<T, String>) -> Result<&T, failure::Error> {
+  result.as_ref().map_err(|w| failure::err_msg(w.clone()))
 }
 
 impl ClrContainer {
@@ -13,10 +30,93 @@ impl ClrContainer {
       None => return Ok(None),
     };
 
-    Ok(Some(ClrContainer { cor20_header }))
+    let meta_data = cor20_header.meta_data();
+    let metadata_root = MetadataRoot::parse(executable, meta_data.virtual_address(), meta_data.size())?;
+    let metadata_tables = MetadataTables::parse(&metadata_root).map_err(|e| e.to_string());
+
+    // the views of the tables inherit the error of the tables
+    let (assembly, assembly_refs, managed_resources, pinvoke_imports) = match &metadata_tables {
+      Ok(Some(tables)) => (
+        AssemblyIdentity::parse(&metadata_root, tables).map_err(|e| e.to_string()),
+        AssemblyReference::parse(&metadata_root, tables).map_err(|e| e.to_string()),
+        ManagedResource::parse(executable, &cor20_header, tables).map_err(|e| e.to_string()),
+        PInvokeImport::parse(tables).map_err(|e| e.to_string()),
+      ),
+      Ok(None) => (Ok(None), Ok(Vec::new()), Ok(Vec::new()), Ok(Vec::new())),
+      Err(e) => (Err(e.clone()), Err(e.clone()), Err(e.clone()), Err(e.clone())),
+    };
+
+    // without a readable Assembly row the signature is kept but cannot be checked against a key
+    let strong_name_signature = StrongNameSignature::parse(executable, &cor20_header, assembly.as_ref().ok().and_then(|w| w.as_ref())).map_err(|e| e.to_string());
+    let native_header = NativeHeader::parse(executable, &cor20_header).map_err(|e| e.to_string());
+
+    // ReadyToRun images for other operating systems have the OS folded into the machine
+    let machine = executable.file_header().map_or(0, |w| w.machine());
+    let machine = match &native_header {
+      Ok(Some(NativeHeader::ReadyToRun(_))) => ready_to_run_machine(machine).0,
+      _ => machine,
+    };
+    let is_pe32_plus = executable.optional_header().is_some_and(|w| w.is_pe32_plus());
+    let platform_target = cor20_header.platform_target(machine, is_pe32_plus);
+
+    Ok(Some(ClrContainer {
+      cor20_header,
+      metadata_root,
+      metadata_tables,
+      assembly,
+      assembly_refs,
+      managed_resources,
+      native_header,
+      pinvoke_imports,
+      platform_target,
+      strong_name_signature,
+    }))
+  }
+
+  // None for modules without an assembly manifest
+  pub fn assembly(&self) -> Result<Option<&AssemblyIdentity>, failure::Error> {
+    Ok(view(&self.assembly)?.as_ref())
+  }
+
+  pub fn assembly_refs(&self) -> Result<Vec<&AssemblyReference>, failure::Error> {
+    Ok(view(&self.assembly_refs)?.iter().collect())
   }
 
   pub fn cor20_header(&self) -> &Cor20Header {
     &self.cor20_header
   }
+
+  // the ManifestResource rows paired with their data
+  pub fn managed_resources(&self) -> Result<Vec<&ManagedResource>, failure::Error> {
+    Ok(view(&self.managed_resources)?.iter().collect())
+  }
+
+  pub fn metadata_root(&self) -> &MetadataRoot {
+    &self.metadata_root
+  }
+
+  // None if the metadata has neither #~ nor #- stream
+  pub fn metadata_tables(&self) -> Result<Option<&MetadataTables>, failure::Error> {
+    Ok(view(&self.metadata_tables)?.as_ref())
+  }
+
+  // None for IL-only images, the header of the code precompiled by crossgen or ngen otherwise
+  pub fn native_header(&self) -> Result<Option<&NativeHeader>, failure::Error> {
+    Ok(view(&self.native_header)?.as_ref())
+  }
+
+  // the native functions called through P/Invoke, not listed in the import directory
+  pub fn pinvoke_imports(&self) -> Result<Vec<&PInvokeImport>, failure::Error> {
+    Ok(view(&self.pinvoke_imports)?.iter().collect())
+  }
+
+  // derived from the machine, the optional header magic and the CLR header flags
+  pub fn platform_target(&self) -> PlatformTarget {
+    self.platform_target
+  }
+
+  // None if the image reserves no space for a signature, verified with verify_strong_name
+  pub fn strong_name_signature(&self) -> Result<Option<&StrongNameSignature>, failure::Error> {
+    Ok(view(&self.strong_name_signature)?.as_ref())
+  }
 }
@@ -0,0 +1,274 @@
+use scroll::{Pread, LE};
+
+use crate::constant::IMAGE_DIRECTORY_ENTRY_DELAY_IMPORT;
+use crate::directories::DelayLoadDescriptor;
+use crate::Executable;
+
+const MAX_DESCRIPTORS: usize = 0x1000;
+const MAX_FUNCTIONS: usize = 0x10000;
+
+#[derive(Debug)]
+pub struct DelayImportFunction {
+    name: String,
+    hint: Option<u16>,
+    ordinal: Option<u16>,
+    thunk_rva: u32,
+    address: u64,
+    bound_address: Option<u64>,
+    unload_address: Option<u64>,
+}
+
+impl DelayImportFunction {
+    // value in the delay IAT, the VA of the delay-load helper thunk until the function is resolved
+    pub fn address(&self) -> u64 {
+        self.address
+    }
+
+    // value in the bound delay IAT
+    pub fn bound_address(&self) -> Option<u64> {
+        self.bound_address
+    }
+
+    pub fn hint(&self) -> Option<u16> {
+        self.hint
+    }
+
+    pub fn name(&self) -> String {
+        self.name.to_owned()
+    }
+
+    pub fn ordinal(&self) -> Option<u16> {
+        self.ordinal
+    }
+
+    // RVA of the delay IAT slot, as referenced by the code
+    pub fn thunk_rva(&self) -> u32 {
+        self.thunk_rva
+    }
+
+    // value in the unload delay IAT, a copy of the original IAT entry
+    pub fn unload_address(&self) -> Option<u64> {
+        self.unload_address
+    }
+}
+
+#[derive(Debug)]
+pub struct DelayImportDescriptor {
+    descriptor: DelayLoadDescriptor,
+    name: String,
+    // RVAs, converted from VAs for the legacy layout
+    module_handle: u32,
+    import_address_table: u32,
+    import_name_table: u32,
+    bound_import_address_table: u32,
+    unload_information_table: u32,
+    functions: Vec<DelayImportFunction>,
+}
+
+impl DelayImportDescriptor {
+    pub fn bound_import_address_table(&self) -> u32 {
+        self.bound_import_address_table
+    }
+
+    pub fn descriptor(&self) -> &DelayLoadDescriptor {
+        &self.descriptor
+    }
+
+    pub fn functions(&self) -> Vec<&DelayImportFunction> {
+        self.functions.iter().collect()
+    }
+
+    pub fn import_address_table(&self) -> u32 {
+        self.import_address_table
+    }
+
+    pub fn import_name_table(&self) -> u32 {
+        self.import_name_table
+    }
+
+    pub fn is_rva_based(&self) -> bool {
+        self.descriptor.is_rva_based()
+    }
+
+    pub fn module_handle(&self) -> u32 {
+        self.module_handle
+    }
+
+    pub fn name(&self) -> String {
+        self.name.to_owned()
+    }
+
+    // 0 if not bound, otherwise the timestamp of the bound DLL
+    pub fn time_date_stamp(&self) -> u32 {
+        self.descriptor.time_date_stamp()
+    }
+
+    pub fn unload_information_table(&self) -> u32 {
+        self.unload_information_table
+    }
+}
+
+#[derive(Debug)]
+pub struct DelayImportContainer {
+    descriptors: Vec<DelayImportDescriptor>,
+}
+
+impl DelayImportContainer {
+    pub fn parse(executable: &Executable) -> Result<Option<Self>, failure::Error> {
+        let data_directory = executable.optional_header().unwrap().data_directories()[IMAGE_DIRECTORY_ENTRY_DELAY_IMPORT as usize];
+        if data_directory.size() == 0 {
+            return Ok(None);
+        }
+
+        let section = match executable.in_section(data_directory) {
+            Some(section) => section,
+            None => {
+                let msg = "Failed to read delay import descriptor";
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        let mut offset = executable.rva_to_file_pointer(data_directory.virtual_address(), section);
+        let mut descriptors: Vec<DelayImportDescriptor> = Vec::new();
+
+        // terminated by a zero filled descriptor
+        loop {
+            let descriptor = DelayLoadDescriptor::parse(executable, &mut offset)?;
+            if descriptor.is_empty() {
+                break;
+            }
+
+            if descriptors.len() >= MAX_DESCRIPTORS {
+                let msg = "Delay import descriptor table is not terminated";
+                return Err(failure::err_msg(msg));
+            }
+
+            descriptors.push(DelayImportContainer::parse_descriptor(executable, descriptor)?);
+        }
+
+        Ok(Some(DelayImportContainer { descriptors }))
+    }
+
+    fn parse_descriptor(executable: &Executable, descriptor: DelayLoadDescriptor) -> Result<DelayImportDescriptor, failure::Error> {
+        let image_base = executable.optional_header().unwrap().image_base();
+        let is_rva_based = descriptor.is_rva_based();
+
+        // VA to RVA for the legacy layout, which exists only in PE32 images
+        let to_rva = |address: u64| -> u32 {
+            if is_rva_based || address == 0 {
+                address as u32
+            } else {
+                address.wrapping_sub(image_base) as u32
+            }
+        };
+
+        let name_address = resolve(executable, to_rva(descriptor.dll_name_rva() as u64), "name of IMAGE_DELAYLOAD_DESCRIPTOR")?;
+        let name = executable.buffer().pread::<&str>(name_address).map_err(|_| {
+            let msg = format!("Failed to read the name of IMAGE_DELAYLOAD_DESCRIPTOR at {:#010X}", name_address);
+            failure::err_msg(msg)
+        })?;
+
+        let import_address_table = to_rva(descriptor.import_address_table_rva() as u64);
+        let import_name_table = to_rva(descriptor.import_name_table_rva() as u64);
+        let bound_import_address_table = to_rva(descriptor.bound_import_address_table_rva() as u64);
+        let unload_information_table = to_rva(descriptor.unload_information_table_rva() as u64);
+
+        let names = read_thunks(executable, import_name_table, None)?;
+        let addresses = read_thunks(executable, import_address_table, Some(names.len()))?;
+        let bound_addresses = match bound_import_address_table {
+            0 => Vec::new(),
+            rva => read_thunks(executable, rva, Some(names.len()))?,
+        };
+        let unload_addresses = match unload_information_table {
+            0 => Vec::new(),
+            rva => read_thunks(executable, rva, Some(names.len()))?,
+        };
+
+        let thunk_size = if executable.is_pe32_plus() { 8 } else { 4 };
+        let ordinal_flag = if executable.is_pe32_plus() { 0x8000_0000_0000_0000 } else { 0x8000_0000 };
+
+        let mut functions: Vec<DelayImportFunction> = Vec::new();
+        for (i, thunk) in names.iter().enumerate() {
+            let (name, hint, ordinal) = if thunk & ordinal_flag != 0 {
+                let ordinal = (thunk & 0xFFFF) as u16;
+                (format!("(Ordinal {})", ordinal), None, Some(ordinal))
+            } else {
+                // IMAGE_IMPORT_BY_NAME
+                let address = resolve(executable, to_rva(*thunk), "IMAGE_IMPORT_BY_NAME")?;
+                let read = |_: scroll::Error| {
+                    let msg = format!("Failed to read IMAGE_IMPORT_BY_NAME at {:#010X}", address);
+                    failure::err_msg(msg)
+                };
+
+                let hint = executable.buffer().pread_with::<u16>(address, LE).map_err(read)?;
+                let name = executable.buffer().pread::<&str>(address + 2).map_err(read)?;
+                (name.to_owned(), Some(hint), None)
+            };
+
+            functions.push(DelayImportFunction {
+                name,
+                hint,
+                ordinal,
+                thunk_rva: import_address_table + (i * thunk_size) as u32,
+                address: addresses[i],
+                bound_address: bound_addresses.get(i).copied(),
+                unload_address: unload_addresses.get(i).copied(),
+            });
+        }
+
+        Ok(DelayImportDescriptor {
+            descriptor,
+            name: name.to_owned(),
+            module_handle: to_rva(descriptor.module_handle_rva() as u64),
+            import_address_table,
+            import_name_table,
+            bound_import_address_table,
+            unload_information_table,
+            functions,
+        })
+    }
+
+    pub fn descriptors(&self) -> Vec<&DelayImportDescriptor> {
+        self.descriptors.iter().collect()
+    }
+}
+
+fn resolve(executable: &Executable, rva: u32, name: &str) -> Result<usize, failure::Error> {
+    executable.rva_to_offset(rva).ok_or_else(|| {
+        let msg = format!("Failed to resolve the {} at RVA {:#010X}", name, rva);
+        failure::err_msg(msg)
+    })
+}
+
+// pointer sized entries, null-terminated unless the count is known from the name table
+fn read_thunks(executable: &Executable, rva: u32, count: Option<usize>) -> Result<Vec<u64>, failure::Error> {
+    let mut offset = resolve(executable, rva, "delay import thunks")?;
+    let mut thunks: Vec<u64> = Vec::new();
+
+    while count != Some(thunks.len()) {
+        let address = offset;
+        let read = |_: scroll::Error| {
+            let msg = format!("Failed to read the delay import thunk at {:#010X}", address);
+            failure::err_msg(msg)
+        };
+
+        let thunk = if executable.is_pe32_plus() {
+            executable.buffer().gread_with::<u64>(&mut offset, LE).map_err(read)?
+        } else {
+            executable.buffer().gread_with::<u32>(&mut offset, LE).map_err(read)? as u64
+        };
+
+        if count.is_none() && thunk == 0 {
+            break;
+        }
+
+        if thunks.len() >= MAX_FUNCTIONS {
+            let msg = format!("Delay import thunks at RVA {:#010X} are not terminated", rva);
+            return Err(failure::err_msg(msg));
+        }
+
+        thunks.push(thunk);
+    }
+
+    Ok(thunks)
+}
@@ -0,0 +1,720 @@
+use std::cmp::Ordering;
+use std::mem::size_of;
+
+use scroll::{Pread, LE};
+
+use crate::constant::{IMAGE_DIRECTORY_ENTRY_EXCEPTION, IMAGE_FILE_MACHINE_AMD64, IMAGE_FILE_MACHINE_ARM64};
+use crate::directories::{Arm64RuntimeFunction, RuntimeFunction};
+use crate::Executable;
+
+const UNW_FLAG_EHANDLER: u8 = 0x1;
+const UNW_FLAG_UHANDLER: u8 = 0x2;
+const UNW_FLAG_CHAININFO: u8 = 0x4;
+const RUNTIME_FUNCTION_INDIRECT: u32 = 0x1;
+const MAX_CHAIN_DEPTH: u32 = 32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum X64UnwindOperation {
+    PushNonVolatile { register: u8 },
+    AllocLarge { size: u32 },
+    AllocSmall { size: u32 },
+    SetFramePointer,
+    SaveNonVolatile { register: u8, offset: u32 },
+    SaveXmm128 { register: u8, offset: u32 },
+    Epilog { info: u8 }, // version 2 only
+    PushMachineFrame { error_code: bool },
+    Unknown { operation: u8, info: u8 },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct X64UnwindCode {
+    code_offset: u8,
+    operation: X64UnwindOperation,
+}
+
+impl X64UnwindCode {
+    // offset from the beginning of the prolog to the end of the instruction
+    pub fn code_offset(&self) -> u8 {
+        self.code_offset
+    }
+
+    pub fn operation(&self) -> X64UnwindOperation {
+        self.operation
+    }
+}
+
+#[derive(Debug)]
+pub struct X64UnwindInfo {
+    // https://docs.microsoft.com/en-us/cpp/build/exception-handling-x64#struct-unwind_info
+    version: u8,
+    flags: u8,
+    size_of_prolog: u8,
+    count_of_codes: u8,
+    frame_register: u8,
+    frame_offset: u8,
+    codes: Vec<X64UnwindCode>,
+    exception_handler: Option<u32>,
+    handler_data_address: Option<u32>,
+    chained_function: Option<RuntimeFunction>,
+    chained_unwind_info: Option<Box<X64UnwindInfo>>,
+}
+
+impl X64UnwindInfo {
+    fn parse(executable: &Executable, rva: u32, depth: u32) -> Result<Self, failure::Error> {
+        if depth > MAX_CHAIN_DEPTH {
+            let msg = format!("Chained UNWIND_INFO at {:#010X} is nested too deeply", rva);
+            return Err(failure::err_msg(msg));
+        }
+
+        let read = |_: scroll::Error| {
+            let msg = format!("Failed to read the UNWIND_INFO at {:#010X}", rva);
+            failure::err_msg(msg)
+        };
+
+        let start = resolve(executable, rva, "UNWIND_INFO")?;
+        let mut offset = start;
+        let buffer = executable.buffer();
+
+        let version_and_flags = buffer.gread_with::<u8>(&mut offset, LE).map_err(read)?;
+        let size_of_prolog = buffer.gread_with::<u8>(&mut offset, LE).map_err(read)?;
+        let count_of_codes = buffer.gread_with::<u8>(&mut offset, LE).map_err(read)?;
+        let frame = buffer.gread_with::<u8>(&mut offset, LE).map_err(read)?;
+
+        let version = version_and_flags & 0x7;
+        let flags = version_and_flags >> 3;
+
+        let mut slots: Vec<u16> = Vec::new();
+        for _ in 0..count_of_codes {
+            slots.push(buffer.gread_with::<u16>(&mut offset, LE).map_err(read)?);
+        }
+
+        // the array is padded to an even number of slots
+        if count_of_codes % 2 == 1 {
+            offset += 2;
+        }
+
+        let codes = X64UnwindInfo::decode(&slots, version).map_err(|e| {
+            let msg = format!("Failed to decode the unwind codes of UNWIND_INFO at {:#010X}: {}", rva, e);
+            failure::err_msg(msg)
+        })?;
+
+        let mut exception_handler = None;
+        let mut handler_data_address = None;
+        let mut chained_function = None;
+        let mut chained_unwind_info = None;
+
+        if flags & UNW_FLAG_CHAININFO != 0 {
+            let function = RuntimeFunction::parse(executable, &mut offset)?;
+            let unwind_info = X64UnwindInfo::parse(executable, function.unwind_info_address(), depth + 1)?;
+
+            chained_function = Some(function);
+            chained_unwind_info = Some(Box::new(unwind_info));
+        } else if flags & (UNW_FLAG_EHANDLER | UNW_FLAG_UHANDLER) != 0 {
+            exception_handler = Some(buffer.gread_with::<u32>(&mut offset, LE).map_err(read)?);
+            handler_data_address = Some(rva + (offset - start) as u32);
+        }
+
+        Ok(X64UnwindInfo {
+            version,
+            flags,
+            size_of_prolog,
+            count_of_codes,
+            frame_register: frame & 0xF,
+            frame_offset: frame >> 4,
+            codes,
+            exception_handler,
+            handler_data_address,
+            chained_function,
+            chained_unwind_info,
+        })
+    }
+
+    // https://docs.microsoft.com/en-us/cpp/build/exception-handling-x64#struct-unwind_code
+    fn decode(slots: &[u16], version: u8) -> Result<Vec<X64UnwindCode>, &'static str> {
+        let mut codes: Vec<X64UnwindCode> = Vec::new();
+        let mut i = 0;
+
+        while i < slots.len() {
+            let code_offset = (slots[i] & 0xFF) as u8;
+            let operation = ((slots[i] >> 8) & 0xF) as u8;
+            let info = (slots[i] >> 12) as u8;
+
+            let slot = |n: usize| slots.get(i + n).map(|w| *w as u32).ok_or("truncated unwind code");
+
+            let (operation, used) = match operation {
+                0 => (X64UnwindOperation::PushNonVolatile { register: info }, 1),
+                1 if info == 0 => (X64UnwindOperation::AllocLarge { size: slot(1)? * 8 }, 2),
+                1 => (X64UnwindOperation::AllocLarge { size: slot(1)? | slot(2)? << 16 }, 3),
+                2 => (X64UnwindOperation::AllocSmall { size: info as u32 * 8 + 8 }, 1),
+                3 => (X64UnwindOperation::SetFramePointer, 1),
+                4 => (X64UnwindOperation::SaveNonVolatile { register: info, offset: slot(1)? * 8 }, 2),
+                5 => (
+                    X64UnwindOperation::SaveNonVolatile {
+                        register: info,
+                        offset: slot(1)? | slot(2)? << 16,
+                    },
+                    3,
+                ),
+                6 if version >= 2 => (X64UnwindOperation::Epilog { info }, 2),
+                6 => (X64UnwindOperation::Unknown { operation, info }, 2), // UWOP_SAVE_XMM in version 1
+                7 => (X64UnwindOperation::Unknown { operation, info }, 3), // UWOP_SAVE_XMM_FAR in version 1
+                8 => (X64UnwindOperation::SaveXmm128 { register: info, offset: slot(1)? * 16 }, 2),
+                9 => (
+                    X64UnwindOperation::SaveXmm128 {
+                        register: info,
+                        offset: slot(1)? | slot(2)? << 16,
+                    },
+                    3,
+                ),
+                10 => (X64UnwindOperation::PushMachineFrame { error_code: info == 1 }, 1),
+                _ => (X64UnwindOperation::Unknown { operation, info }, 1),
+            };
+
+            codes.push(X64UnwindCode { code_offset, operation });
+            i += used;
+        }
+
+        Ok(codes)
+    }
+
+    pub fn chained_function(&self) -> Option<&RuntimeFunction> {
+        self.chained_function.as_ref()
+    }
+
+    // the unwind info of the primary function, when this function is a fragment of it
+    pub fn chained_unwind_info(&self) -> Option<&X64UnwindInfo> {
+        self.chained_unwind_info.as_deref()
+    }
+
+    pub fn codes(&self) -> Vec<&X64UnwindCode> {
+        self.codes.iter().collect()
+    }
+
+    // number of slots, not decoded codes
+    pub fn count_of_codes(&self) -> u8 {
+        self.count_of_codes
+    }
+
+    pub fn exception_handler(&self) -> Option<u32> {
+        self.exception_handler
+    }
+
+    // UNW_FLAG_EHANDLER, UNW_FLAG_UHANDLER and UNW_FLAG_CHAININFO
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    // scaled by 16
+    pub fn frame_offset(&self) -> u8 {
+        self.frame_offset
+    }
+
+    // 0 means no frame pointer
+    pub fn frame_register(&self) -> u8 {
+        self.frame_register
+    }
+
+    // RVA of the language specific handler data, that follows the handler
+    pub fn handler_data_address(&self) -> Option<u32> {
+        self.handler_data_address
+    }
+
+    pub fn size_of_prolog(&self) -> u8 {
+        self.size_of_prolog
+    }
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Arm64UnwindOperation {
+    AllocStack { size: u32 },                                   // alloc_s, alloc_m, alloc_l
+    SaveR19R20PreIndexed { offset: u32 },                       // save_r19r20_x
+    SaveFpLr { offset: u32 },                                   // save_fplr
+    SaveFpLrPreIndexed { offset: u32 },                         // save_fplr_x
+    SaveRegisterPair { register: u8, offset: u32 },             // save_regp
+    SaveRegisterPairPreIndexed { register: u8, offset: u32 },   // save_regp_x
+    SaveRegister { register: u8, offset: u32 },                 // save_reg
+    SaveRegisterPreIndexed { register: u8, offset: u32 },       // save_reg_x
+    SaveLrPair { register: u8, offset: u32 },                   // save_lrpair
+    SaveFpRegisterPair { register: u8, offset: u32 },           // save_fregp
+    SaveFpRegisterPairPreIndexed { register: u8, offset: u32 }, // save_fregp_x
+    SaveFpRegister { register: u8, offset: u32 },               // save_freg
+    SaveFpRegisterPreIndexed { register: u8, offset: u32 },     // save_freg_x
+    SetFp,
+    AddFp { offset: u32 },
+    Nop,
+    End,
+    EndChained, // end_c
+    SaveNext,
+    TrapFrame,
+    MachineFrame,
+    Context,
+    EcContext,
+    ClearUnwoundToCall,
+    PacSignLr,
+    Unknown,
+}
+
+#[derive(Clone, Debug)]
+pub struct Arm64UnwindCode {
+    index: usize,
+    bytes: Vec<u8>,
+    operation: Arm64UnwindOperation,
+}
+
+impl Arm64UnwindCode {
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    // byte index in the unwind code array, referred by epilog scopes
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    // registers are x0-x30 and d0-d31, stack offsets are in bytes
+    pub fn operation(&self) -> Arm64UnwindOperation {
+        self.operation
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Arm64EpilogScope {
+    start_offset: u32,
+    start_index: u16,
+}
+
+impl Arm64EpilogScope {
+    // byte index of the first unwind code of this epilog
+    pub fn start_index(&self) -> u16 {
+        self.start_index
+    }
+
+    // in bytes, relative to the start of the function
+    pub fn start_offset(&self) -> u32 {
+        self.start_offset
+    }
+}
+
+#[derive(Debug)]
+pub struct Arm64UnwindInfo {
+    // https://docs.microsoft.com/en-us/cpp/build/arm64-exception-handling#xdata-records
+    function_length: u32,
+    version: u8,
+    single_epilog_start_index: Option<u16>,
+    epilog_scopes: Vec<Arm64EpilogScope>,
+    codes: Vec<Arm64UnwindCode>,
+    exception_handler: Option<u32>,
+    handler_data_address: Option<u32>,
+}
+
+impl Arm64UnwindInfo {
+    fn parse(executable: &Executable, rva: u32) -> Result<Self, failure::Error> {
+        let read = |_: scroll::Error| {
+            let msg = format!("Failed to read the .xdata record at {:#010X}", rva);
+            failure::err_msg(msg)
+        };
+
+        let start = resolve(executable, rva, ".xdata record")?;
+        let mut offset = start;
+        let buffer = executable.buffer();
+
+        let header = buffer.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+        let function_length = (header & 0x3FFFF) * 4;
+        let version = ((header >> 18) & 0x3) as u8;
+        let has_exception_data = (header >> 20) & 0x1 != 0;
+        let single_epilog = (header >> 21) & 0x1 != 0;
+        let mut epilog_count = (header >> 22) & 0x1F;
+        let mut code_words = (header >> 27) & 0x1F;
+
+        // both zero means the counts are in the extension word
+        if epilog_count == 0 && code_words == 0 {
+            let extension = buffer.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+            epilog_count = extension & 0xFFFF;
+            code_words = (extension >> 16) & 0xFF;
+        }
+
+        // with E bit, the epilog count field holds the index of the only epilog
+        let mut epilog_scopes: Vec<Arm64EpilogScope> = Vec::new();
+        let single_epilog_start_index = if single_epilog {
+            Some(epilog_count as u16)
+        } else {
+            for _ in 0..epilog_count {
+                let scope = buffer.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+                epilog_scopes.push(Arm64EpilogScope {
+                    start_offset: (scope & 0x3FFFF) * 4,
+                    start_index: (scope >> 22) as u16,
+                });
+            }
+
+            None
+        };
+
+        let size = code_words as usize * 4;
+        let bytes = match buffer.get(offset..offset + size) {
+            Some(bytes) => bytes,
+            None => {
+                let msg = format!("Failed to read the unwind codes of .xdata record at {:#010X}", rva);
+                return Err(failure::err_msg(msg));
+            }
+        };
+        offset += size;
+
+        let codes = Arm64UnwindInfo::decode(bytes).map_err(|e| {
+            let msg = format!("Failed to decode the unwind codes of .xdata record at {:#010X}: {}", rva, e);
+            failure::err_msg(msg)
+        })?;
+
+        let (exception_handler, handler_data_address) = if has_exception_data {
+            let handler = buffer.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+            (Some(handler), Some(rva + (offset - start) as u32))
+        } else {
+            (None, None)
+        };
+
+        Ok(Arm64UnwindInfo {
+            function_length,
+            version,
+            single_epilog_start_index,
+            epilog_scopes,
+            codes,
+            exception_handler,
+            handler_data_address,
+        })
+    }
+
+    // https://docs.microsoft.com/en-us/cpp/build/arm64-exception-handling#unwind-codes
+    fn decode(bytes: &[u8]) -> Result<Vec<Arm64UnwindCode>, &'static str> {
+        let mut codes: Vec<Arm64UnwindCode> = Vec::new();
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let b = bytes[i];
+            let length = match b {
+                0xC0..=0xDF | 0xE2 | 0xF8 => 2,
+                0xE7 | 0xF9 => 3,
+                0xE0 | 0xFA => 4,
+                0xFB => 5,
+                _ => 1,
+            };
+
+            let code = match bytes.get(i..i + length) {
+                Some(code) => code,
+                None => return Err("truncated unwind code"),
+            };
+
+            let next = if length > 1 { code[1] as u32 } else { 0 };
+            let x = |bits: u32| ((b as u32 & ((1 << bits) - 1)) << 2 | next >> 6) as u8;
+            let z = next & 0x3F;
+
+            let operation = match b {
+                0x00..=0x1F => Arm64UnwindOperation::AllocStack { size: (b as u32 & 0x1F) * 16 },
+                0x20..=0x3F => Arm64UnwindOperation::SaveR19R20PreIndexed { offset: (b as u32 & 0x1F) * 8 },
+                0x40..=0x7F => Arm64UnwindOperation::SaveFpLr { offset: (b as u32 & 0x3F) * 8 },
+                0x80..=0xBF => Arm64UnwindOperation::SaveFpLrPreIndexed { offset: ((b as u32 & 0x3F) + 1) * 8 },
+                0xC0..=0xC7 => Arm64UnwindOperation::AllocStack {
+                    size: ((b as u32 & 0x7) << 8 | next) * 16,
+                },
+                0xC8..=0xCB => Arm64UnwindOperation::SaveRegisterPair { register: 19 + x(2), offset: z * 8 },
+                0xCC..=0xCF => Arm64UnwindOperation::SaveRegisterPairPreIndexed { register: 19 + x(2), offset: (z + 1) * 8 },
+                0xD0..=0xD3 => Arm64UnwindOperation::SaveRegister { register: 19 + x(2), offset: z * 8 },
+                0xD4..=0xD5 => Arm64UnwindOperation::SaveRegisterPreIndexed {
+                    register: 19 + ((b & 0x1) << 3 | (next >> 5) as u8),
+                    offset: ((next & 0x1F) + 1) * 8,
+                },
+                0xD6..=0xD7 => Arm64UnwindOperation::SaveLrPair { register: 19 + 2 * x(1), offset: z * 8 },
+                0xD8..=0xD9 => Arm64UnwindOperation::SaveFpRegisterPair { register: 8 + x(1), offset: z * 8 },
+                0xDA..=0xDB => Arm64UnwindOperation::SaveFpRegisterPairPreIndexed { register: 8 + x(1), offset: (z + 1) * 8 },
+                0xDC..=0xDD => Arm64UnwindOperation::SaveFpRegister { register: 8 + x(1), offset: z * 8 },
+                0xDE => Arm64UnwindOperation::SaveFpRegisterPreIndexed {
+                    register: 8 + (next >> 5) as u8,
+                    offset: ((next & 0x1F) + 1) * 8,
+                },
+                0xE0 => Arm64UnwindOperation::AllocStack {
+                    size: (next << 16 | (code[2] as u32) << 8 | code[3] as u32) * 16,
+                },
+                0xE1 => Arm64UnwindOperation::SetFp,
+                0xE2 => Arm64UnwindOperation::AddFp { offset: next * 8 },
+                0xE3 => Arm64UnwindOperation::Nop,
+                0xE4 => Arm64UnwindOperation::End,
+                0xE5 => Arm64UnwindOperation::EndChained,
+                0xE6 => Arm64UnwindOperation::SaveNext,
+                0xE8 => Arm64UnwindOperation::TrapFrame,
+                0xE9 => Arm64UnwindOperation::MachineFrame,
+                0xEA => Arm64UnwindOperation::Context,
+                0xEB => Arm64UnwindOperation::EcContext,
+                0xEC => Arm64UnwindOperation::ClearUnwoundToCall,
+                0xFC => Arm64UnwindOperation::PacSignLr,
+                _ => Arm64UnwindOperation::Unknown, // alloc_z, save_any_reg and reserved codes
+            };
+
+            codes.push(Arm64UnwindCode {
+                index: i,
+                bytes: code.to_vec(),
+                operation,
+            });
+            i += length;
+
+            // the array is padded to a word boundary after the last end
+            let is_end = operation == Arm64UnwindOperation::End || operation == Arm64UnwindOperation::EndChained;
+            if is_end && bytes[i..].iter().all(|w| *w == 0x00 || *w == 0xE3) {
+                break;
+            }
+        }
+
+        Ok(codes)
+    }
+
+    pub fn codes(&self) -> Vec<&Arm64UnwindCode> {
+        self.codes.iter().collect()
+    }
+
+    pub fn epilog_scopes(&self) -> Vec<&Arm64EpilogScope> {
+        self.epilog_scopes.iter().collect()
+    }
+
+    pub fn exception_handler(&self) -> Option<u32> {
+        self.exception_handler
+    }
+
+    // in bytes
+    pub fn function_length(&self) -> u32 {
+        self.function_length
+    }
+
+    // RVA of the language specific handler data, that follows the handler
+    pub fn handler_data_address(&self) -> Option<u32> {
+        self.handler_data_address
+    }
+
+    // the E bit, the function has a single epilog that shares the unwind codes from this index
+    pub fn single_epilog_start_index(&self) -> Option<u16> {
+        self.single_epilog_start_index
+    }
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Arm64PackedUnwindData {
+    // https://docs.microsoft.com/en-us/cpp/build/arm64-exception-handling#packed-unwind-data
+    data: u32,
+}
+
+impl Arm64PackedUnwindData {
+    // 1: packed unwind data, 2: packed unwind data of a function fragment without prolog
+    pub fn flag(&self) -> u8 {
+        (self.data & 0x3) as u8
+    }
+
+    // in bytes
+    pub fn frame_size(&self) -> u32 {
+        ((self.data >> 23) & 0x1FF) * 16
+    }
+
+    // in bytes
+    pub fn function_length(&self) -> u32 {
+        ((self.data >> 2) & 0x7FF) * 4
+    }
+
+    // 0: no link register, 1: chained with lr, 2: reserved, 3: chained with frame pointer
+    pub fn cr(&self) -> u8 {
+        ((self.data >> 21) & 0x3) as u8
+    }
+
+    // homes x0-x7 parameter registers
+    pub fn h(&self) -> bool {
+        (self.data >> 20) & 0x1 != 0
+    }
+
+    // number of non-volatile FP registers (d8-d15) saved, 0 means none
+    pub fn reg_f(&self) -> u8 {
+        ((self.data >> 13) & 0x7) as u8
+    }
+
+    // number of non-volatile INT registers (x19-x28) saved
+    pub fn reg_i(&self) -> u8 {
+        ((self.data >> 16) & 0xF) as u8
+    }
+}
+
+#[derive(Debug)]
+pub enum UnwindInfo {
+    X64(X64UnwindInfo),
+    Arm64(Arm64UnwindInfo),
+    Arm64Packed(Arm64PackedUnwindData),
+}
+
+impl UnwindInfo {
+    pub fn exception_handler(&self) -> Option<u32> {
+        match self {
+            UnwindInfo::X64(info) => info.exception_handler(),
+            UnwindInfo::Arm64(info) => info.exception_handler(),
+            UnwindInfo::Arm64Packed(_) => None,
+        }
+    }
+
+    pub fn handler_data_address(&self) -> Option<u32> {
+        match self {
+            UnwindInfo::X64(info) => info.handler_data_address(),
+            UnwindInfo::Arm64(info) => info.handler_data_address(),
+            UnwindInfo::Arm64Packed(_) => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FunctionEntry {
+    begin_address: u32,
+    end_address: u32,
+    unwind_info_address: Option<u32>, // none for packed unwind data
+    unwind_info: Result<UnwindInfo, String>,
+}
+
+impl FunctionEntry {
+    pub fn begin_address(&self) -> u32 {
+        self.begin_address
+    }
+
+    // exclusive
+    pub fn end_address(&self) -> u32 {
+        self.end_address
+    }
+
+    // a function whose unwind info cannot be read keeps the error, the other functions are still readable
+    pub fn unwind_info(&self) -> Result<&UnwindInfo, failure::Error> {
+        self.unwind_info.as_ref().map_err(|e| failure::err_msg(e.clone()))
+    }
+
+    pub fn unwind_info_address(&self) -> Option<u32> {
+        self.unwind_info_address
+    }
+}
+
+#[derive(Debug)]
+pub struct ExceptionContainer {
+    machine: u16,
+    functions: Vec<FunctionEntry>,
+}
+
+impl ExceptionContainer {
+    pub fn parse(executable: &Executable) -> Result<Option<Self>, failure::Error> {
+        let data_directory = executable.optional_header().unwrap().data_directories()[IMAGE_DIRECTORY_ENTRY_EXCEPTION as usize];
+        if data_directory.size() == 0 {
+            return Ok(None);
+        }
+
+        // x86 uses SafeSEH in the load config instead, and the other architectures have different layouts
+        let machine = executable.file_header().unwrap().machine();
+        if machine != IMAGE_FILE_MACHINE_AMD64 && machine != IMAGE_FILE_MACHINE_ARM64 {
+            return Ok(None);
+        }
+
+        let section = match executable.in_section(data_directory) {
+            Some(section) => section,
+            None => {
+                let msg = "Failed to read exception directory";
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        let mut offset = executable.rva_to_file_pointer(data_directory.virtual_address(), section);
+        let mut functions: Vec<FunctionEntry> = Vec::new();
+
+        if machine == IMAGE_FILE_MACHINE_AMD64 {
+            for _ in 0..data_directory.size() as usize / size_of::<RuntimeFunction>() {
+                let function = RuntimeFunction::parse(executable, &mut offset)?;
+
+                // the unwind info is shared with another RUNTIME_FUNCTION
+                let mut unwind_info_address = function.unwind_info_address();
+                let mut indirect = Ok(());
+                if unwind_info_address & RUNTIME_FUNCTION_INDIRECT != 0 {
+                    match resolve(executable, unwind_info_address & !RUNTIME_FUNCTION_INDIRECT, "RUNTIME_FUNCTION").and_then(|mut address| RuntimeFunction::parse(executable, &mut address)) {
+                        Ok(primary) => unwind_info_address = primary.unwind_info_address(),
+                        Err(e) => indirect = Err(e),
+                    }
+                }
+
+                let unwind_info = indirect.and_then(|_| X64UnwindInfo::parse(executable, unwind_info_address, 0));
+
+                functions.push(FunctionEntry {
+                    begin_address: function.begin_address(),
+                    end_address: function.end_address(),
+                    unwind_info_address: Some(unwind_info_address),
+                    unwind_info: unwind_info.map(UnwindInfo::X64).map_err(|e| e.to_string()),
+                });
+            }
+        } else {
+            for _ in 0..data_directory.size() as usize / size_of::<Arm64RuntimeFunction>() {
+                let function = Arm64RuntimeFunction::parse(executable, &mut offset)?;
+
+                // the length is in the unwind data, a function without readable unwind data covers no address
+                let (unwind_info_address, unwind_info, length) = match function.flag() {
+                    0 => match Arm64UnwindInfo::parse(executable, function.unwind_data()) {
+                        Ok(info) => {
+                            let length = info.function_length();
+                            (Some(function.unwind_data()), Ok(UnwindInfo::Arm64(info)), length)
+                        }
+                        Err(e) => (Some(function.unwind_data()), Err(e.to_string()), 0),
+                    },
+                    1 | 2 => {
+                        let data = Arm64PackedUnwindData { data: function.unwind_data() };
+                        (None, Ok(UnwindInfo::Arm64Packed(data)), data.function_length())
+                    }
+                    _ => {
+                        let msg = format!("Reserved unwind data flag in IMAGE_ARM64_RUNTIME_FUNCTION_ENTRY of {:#010X}", function.begin_address());
+                        (None, Err(msg), 0)
+                    }
+                };
+
+                functions.push(FunctionEntry {
+                    begin_address: function.begin_address(),
+                    // a length past the end of the address space is as unusable as a missing one
+                    end_address: function.begin_address().checked_add(length).unwrap_or(function.begin_address()),
+                    unwind_info_address,
+                    unwind_info,
+                });
+            }
+        }
+
+        Ok(Some(ExceptionContainer { machine, functions }))
+    }
+
+    // lookup the function that contains the RVA, the table is sorted by the begin address
+    pub fn function_at(&self, rva: u32) -> Option<&FunctionEntry> {
+        let index = self
+            .functions
+            .binary_search_by(|w| {
+                if rva < w.begin_address {
+                    Ordering::Greater
+                } else if rva >= w.end_address {
+                    Ordering::Less
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .ok()?;
+
+        self.functions.get(index)
+    }
+
+    pub fn functions(&self) -> Vec<&FunctionEntry> {
+        self.functions.iter().collect()
+    }
+
+    pub fn machine(&self) -> u16 {
+        self.machine
+    }
+}
+
+fn resolve(executable: &Executable, rva: u32, name: &str) -> Result<usize, failure::Error> {
+    executable.rva_to_offset(rva).ok_or_else(|| {
+        let msg = format!("Failed to resolve the {} at RVA {:#010X}", name, rva);
+        failure::err_msg(msg)
+    })
+}
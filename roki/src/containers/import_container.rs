@@ -11,6 +11,7 @@ pub struct ImportFunction {
     address: u64,
     hint: Option<u16>,
     name: String,
+    ordinal: Option<u16>,
 }
 
 impl ImportFunction {
@@ -25,12 +26,18 @@ impl ImportFunction {
     pub fn name(&self) -> String {
         self.name.to_owned()
     }
+
+    // Some if imported by ordinal
+    pub fn ordinal(&self) -> Option<u16> {
+        self.ordinal
+    }
 }
 
 #[derive(Debug)]
 pub struct ImageImportByName {
     hint: Option<u16>,
     name: String,
+    ordinal: Option<u16>,
 }
 
 impl<'a> ctx::TryFromCtx<'a, Endian> for ImageImportByName {
@@ -42,7 +49,14 @@ impl<'a> ctx::TryFromCtx<'a, Endian> for ImageImportByName {
         let hint = src.gread_with::<u16>(offset, LE)?;
         let name = src.gread::<&str>(offset)?;
 
-        Ok((ImageImportByName { hint: Some(hint), name: name.to_owned() }, *offset))
+        Ok((
+            ImageImportByName {
+                hint: Some(hint),
+                name: name.to_owned(),
+                ordinal: None,
+            },
+            *offset,
+        ))
     }
 }
 
@@ -101,6 +115,7 @@ pub struct ImportDescriptor {
     name: String,
     first_thunk: u32,
     functions: Option<Vec<ImportFunction>>,
+    bound_time_date_stamp: Option<u32>,
 }
 
 impl<'a> ctx::TryFromCtx<'a, Endian> for ImportDescriptor {
@@ -124,6 +139,7 @@ impl<'a> ctx::TryFromCtx<'a, Endian> for ImportDescriptor {
                 name: "".to_string(),
                 first_thunk,
                 functions: None,
+                bound_time_date_stamp: None,
             },
             *offset,
         ))
@@ -131,6 +147,11 @@ impl<'a> ctx::TryFromCtx<'a, Endian> for ImportDescriptor {
 }
 
 impl ImportDescriptor {
+    // the timestamp of the DLL that the IAT was bound against, from the bound import table for the new style binding
+    pub fn bound_time_date_stamp(&self) -> Option<u32> {
+        self.bound_time_date_stamp
+    }
+
     pub fn characteristics(&self) -> u32 {
         self.characteristics
     }
@@ -151,10 +172,19 @@ impl ImportDescriptor {
         self.name.to_owned()
     }
 
+    // 0 if not bound, -1 for the new style binding, otherwise the timestamp of the bound DLL
     pub fn time_date_stamp(&self) -> u32 {
         self.time_date_stamp
     }
 
+    pub fn is_bound(&self) -> bool {
+        self.time_date_stamp != 0
+    }
+
+    pub fn is_new_style_bound(&self) -> bool {
+        self.time_date_stamp == u32::MAX
+    }
+
     pub fn functions(&self) -> Vec<&ImportFunction> {
         self.functions.as_ref().unwrap().iter().map(|w| w).collect()
     }
@@ -201,7 +231,13 @@ impl ImportContainer {
                     }
                 };
 
-                if executable.is_x64() {
+                descriptor.bound_time_date_stamp = match descriptor.time_date_stamp() {
+                    0 => None,
+                    u32::MAX => executable.bound_import_data().and_then(|w| w.import_of(&descriptor.name)).map(|w| w.time_date_stamp()),
+                    time_date_stamp => Some(time_date_stamp),
+                };
+
+                if executable.is_pe32_plus() {
                     let address = executable.rva_to_file_pointer(descriptor.first_thunk(), section);
                     let iat = ImportContainer::create_import_address_table_64(&executable, address)?;
 
@@ -218,6 +254,7 @@ impl ImportContainer {
                             address,
                             name: by_name.name.to_owned(),
                             hint: by_name.hint,
+                            ordinal: by_name.ordinal,
                         });
                     }
 
@@ -239,6 +276,7 @@ impl ImportContainer {
                             address,
                             name: by_name.name.to_owned(),
                             hint: by_name.hint,
+                            ordinal: by_name.ordinal,
                         });
                     }
 
@@ -331,9 +369,10 @@ impl ImportContainer {
                     vector.push(ImageImportByName {
                         name: format!("(Ordinal {})", ImportContainer::ordinal32(thunk.function())),
                         hint: None,
+                        ordinal: Some(ImportContainer::ordinal32(thunk.function()) as u16),
                     });
                 } else {
-                    let addr_of_name = executable.rva_to_file_pointer(thunk.address_of_data() as u32, section);
+                    let addr_of_name = executable.rva_to_file_pointer(thunk.address_of_data(), section);
                     let by_name = executable.buffer().pread_with::<ImageImportByName>(addr_of_name, LE).map_err(|_| {
                         let msg = format!("Failed to read IMAGE_IMPORT_BY_NAME at {:#010X}", addr_of_name);
                         return failure::err_msg(msg);
@@ -368,6 +407,7 @@ impl ImportContainer {
                     vector.push(ImageImportByName {
                         name: format!("(Ordinal {})", ImportContainer::ordinal64(thunk.function())),
                         hint: None,
+                        ordinal: Some(ImportContainer::ordinal64(thunk.function()) as u16),
                     });
                 } else {
                     let addr_of_name = executable.rva_to_file_pointer(thunk.address_of_data() as u32, section);
@@ -1,11 +1,25 @@
+mod bound_import_container;
 mod clr_container;
 mod debug_container;
+mod delay_import_container;
+mod exception_container;
 mod export_container;
 mod import_container;
 mod load_config_container;
+mod relocation_container;
+mod resource_container;
+mod security_container;
+mod tls_container;
 
+pub use bound_import_container::*;
 pub use clr_container::*;
 pub use debug_container::*;
+pub use delay_import_container::*;
+pub use exception_container::*;
 pub use export_container::*;
 pub use import_container::*;
 pub use load_config_container::*;
+pub use relocation_container::*;
+pub use resource_container::*;
+pub use security_container::*;
+pub use tls_container::*;
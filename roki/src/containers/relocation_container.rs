@@ -0,0 +1,235 @@
+use std::mem::size_of;
+
+use scroll::{Pread, LE};
+
+use crate::constant::{
+    IMAGE_DIRECTORY_ENTRY_BASERELOC, IMAGE_FILE_MACHINE_ARMNT, IMAGE_FILE_MACHINE_IA64, IMAGE_FILE_MACHINE_MIPS16, IMAGE_FILE_MACHINE_MIPSFPU, IMAGE_FILE_MACHINE_MIPSFPU16, IMAGE_FILE_MACHINE_R4000,
+    IMAGE_FILE_MACHINE_RISCV128, IMAGE_FILE_MACHINE_RISCV32, IMAGE_FILE_MACHINE_RISCV64, IMAGE_FILE_MACHINE_WCEMIPSV2, IMAGE_REL_BASED_ABSOLUTE, IMAGE_REL_BASED_DIR64, IMAGE_REL_BASED_HIGH, IMAGE_REL_BASED_HIGHADJ,
+    IMAGE_REL_BASED_HIGHLOW, IMAGE_REL_BASED_LOW, IMAGE_REL_BASED_MACHINE_SPECIFIC_5, IMAGE_REL_BASED_MACHINE_SPECIFIC_7, IMAGE_REL_BASED_MACHINE_SPECIFIC_8, IMAGE_REL_BASED_MACHINE_SPECIFIC_9,
+};
+use crate::directories::BaseRelocationBlock;
+use crate::Executable;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RelocationType {
+    Absolute, // padding, skipped by the loader
+    High,
+    Low,
+    HighLow,
+    HighAdjust,
+    ArmMov32,
+    ThumbMov32,
+    MipsJumpAddress,
+    MipsJumpAddress16,
+    Ia64Immediate64,
+    RiscVHigh20,
+    RiscVLow12I,
+    RiscVLow12S,
+    Dir64,
+    Unknown(u8),
+}
+
+impl RelocationType {
+    // types 5, 7, 8 and 9 are interpreted by the machine
+    fn from_raw(r#type: u8, machine: u16) -> Self {
+        let is_mips = matches!(
+            machine,
+            IMAGE_FILE_MACHINE_R4000 | IMAGE_FILE_MACHINE_WCEMIPSV2 | IMAGE_FILE_MACHINE_MIPS16 | IMAGE_FILE_MACHINE_MIPSFPU | IMAGE_FILE_MACHINE_MIPSFPU16
+        );
+        let is_riscv = matches!(machine, IMAGE_FILE_MACHINE_RISCV32 | IMAGE_FILE_MACHINE_RISCV64 | IMAGE_FILE_MACHINE_RISCV128);
+
+        match r#type {
+            IMAGE_REL_BASED_ABSOLUTE => RelocationType::Absolute,
+            IMAGE_REL_BASED_HIGH => RelocationType::High,
+            IMAGE_REL_BASED_LOW => RelocationType::Low,
+            IMAGE_REL_BASED_HIGHLOW => RelocationType::HighLow,
+            IMAGE_REL_BASED_HIGHADJ => RelocationType::HighAdjust,
+            IMAGE_REL_BASED_MACHINE_SPECIFIC_5 if machine == IMAGE_FILE_MACHINE_ARMNT => RelocationType::ArmMov32,
+            IMAGE_REL_BASED_MACHINE_SPECIFIC_5 if is_mips => RelocationType::MipsJumpAddress,
+            IMAGE_REL_BASED_MACHINE_SPECIFIC_5 if is_riscv => RelocationType::RiscVHigh20,
+            IMAGE_REL_BASED_MACHINE_SPECIFIC_7 if machine == IMAGE_FILE_MACHINE_ARMNT => RelocationType::ThumbMov32,
+            IMAGE_REL_BASED_MACHINE_SPECIFIC_7 if is_riscv => RelocationType::RiscVLow12I,
+            IMAGE_REL_BASED_MACHINE_SPECIFIC_8 if is_riscv => RelocationType::RiscVLow12S,
+            IMAGE_REL_BASED_MACHINE_SPECIFIC_9 if is_mips => RelocationType::MipsJumpAddress16,
+            IMAGE_REL_BASED_MACHINE_SPECIFIC_9 if machine == IMAGE_FILE_MACHINE_IA64 => RelocationType::Ia64Immediate64,
+            IMAGE_REL_BASED_DIR64 => RelocationType::Dir64,
+            _ => RelocationType::Unknown(r#type),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        let name = match self {
+            RelocationType::Absolute => "ABSOLUTE",
+            RelocationType::High => "HIGH",
+            RelocationType::Low => "LOW",
+            RelocationType::HighLow => "HIGHLOW",
+            RelocationType::HighAdjust => "HIGHADJ",
+            RelocationType::ArmMov32 => "ARM_MOV32",
+            RelocationType::ThumbMov32 => "THUMB_MOV32",
+            RelocationType::MipsJumpAddress => "MIPS_JMPADDR",
+            RelocationType::MipsJumpAddress16 => "MIPS_JMPADDR16",
+            RelocationType::Ia64Immediate64 => "IA64_IMM64",
+            RelocationType::RiscVHigh20 => "RISCV_HIGH20",
+            RelocationType::RiscVLow12I => "RISCV_LOW12I",
+            RelocationType::RiscVLow12S => "RISCV_LOW12S",
+            RelocationType::Dir64 => "DIR64",
+            RelocationType::Unknown(r#type) => return format!("UNKNOWN ({})", r#type),
+        };
+
+        name.to_owned()
+    }
+
+    // number of bytes patched at the target
+    pub fn size(&self) -> usize {
+        match self {
+            RelocationType::Absolute | RelocationType::Unknown(_) => 0,
+            RelocationType::High | RelocationType::Low | RelocationType::HighAdjust => 2,
+            RelocationType::ArmMov32 | RelocationType::ThumbMov32 | RelocationType::Dir64 => 8,
+            RelocationType::Ia64Immediate64 => 16,
+            _ => 4,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Relocation {
+    r#type: RelocationType,
+    offset: u16,
+    rva: u32,
+    parameter: Option<u16>,
+}
+
+impl Relocation {
+    // offset from the page RVA of the block
+    pub fn offset(&self) -> u16 {
+        self.offset
+    }
+
+    // the low 16 bits of the adjusted value, only for HIGHADJ which occupies two slots
+    pub fn parameter(&self) -> Option<u16> {
+        self.parameter
+    }
+
+    pub fn r#type(&self) -> RelocationType {
+        self.r#type
+    }
+
+    // RVA of the location to be patched
+    pub fn rva(&self) -> u32 {
+        self.rva
+    }
+}
+
+#[derive(Debug)]
+pub struct RelocationBlock {
+    block: BaseRelocationBlock,
+    relocations: Vec<Relocation>,
+}
+
+impl RelocationBlock {
+    pub fn page_rva(&self) -> u32 {
+        self.block.virtual_address()
+    }
+
+    // including the ABSOLUTE padding entries
+    pub fn relocations(&self) -> Vec<&Relocation> {
+        self.relocations.iter().collect()
+    }
+
+    pub fn size_of_block(&self) -> u32 {
+        self.block.size_of_block()
+    }
+}
+
+#[derive(Debug)]
+pub struct RelocationContainer {
+    blocks: Vec<RelocationBlock>,
+}
+
+impl RelocationContainer {
+    pub fn parse(executable: &Executable) -> Result<Option<Self>, failure::Error> {
+        let data_directory = executable.optional_header().unwrap().data_directories()[IMAGE_DIRECTORY_ENTRY_BASERELOC as usize];
+        if data_directory.size() == 0 {
+            return Ok(None);
+        }
+
+        let section = match executable.in_section(data_directory) {
+            Some(section) => section,
+            None => {
+                let msg = "Failed to read base relocation directory";
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        let machine = executable.file_header().unwrap().machine();
+        let start = executable.rva_to_file_pointer(data_directory.virtual_address(), section);
+        let end = start + data_directory.size() as usize;
+
+        let mut blocks: Vec<RelocationBlock> = Vec::new();
+        let mut offset = start;
+
+        while offset + size_of::<BaseRelocationBlock>() <= end {
+            let address = offset;
+            let block = BaseRelocationBlock::parse(executable, &mut offset)?;
+
+            // some linkers pad the directory with zeros
+            if block.virtual_address() == 0 && block.size_of_block() == 0 {
+                break;
+            }
+
+            let size_of_block = block.size_of_block() as usize;
+            if size_of_block < size_of::<BaseRelocationBlock>() || address + size_of_block > end {
+                let msg = format!("IMAGE_BASE_RELOCATION at {:#X} has an invalid SizeOfBlock {:#X}", address, size_of_block);
+                return Err(failure::err_msg(msg));
+            }
+
+            let read = |_: scroll::Error| {
+                let msg = format!("Failed to read the base relocation entries of IMAGE_BASE_RELOCATION at {:#X}", address);
+                failure::err_msg(msg)
+            };
+
+            let mut relocations: Vec<Relocation> = Vec::new();
+            while offset + size_of::<u16>() <= address + size_of_block {
+                let entry = executable.buffer().gread_with::<u16>(&mut offset, LE).map_err(read)?;
+
+                let r#type = RelocationType::from_raw((entry >> 12) as u8, machine);
+                let parameter = if r#type == RelocationType::HighAdjust && offset + size_of::<u16>() <= address + size_of_block {
+                    Some(executable.buffer().gread_with::<u16>(&mut offset, LE).map_err(read)?)
+                } else {
+                    None
+                };
+
+                relocations.push(Relocation {
+                    r#type,
+                    offset: entry & 0x0FFF,
+                    rva: block.virtual_address().wrapping_add((entry & 0x0FFF) as u32),
+                    parameter,
+                });
+            }
+
+            // an odd SizeOfBlock is not aligned, continue from the declared end
+            offset = address + size_of_block;
+            blocks.push(RelocationBlock { block, relocations });
+        }
+
+        Ok(Some(RelocationContainer { blocks }))
+    }
+
+    pub fn blocks(&self) -> Vec<&RelocationBlock> {
+        self.blocks.iter().collect()
+    }
+
+    // whether the pointer sized value at the RVA is fixed up by the loader
+    pub fn relocation_at(&self, rva: u32) -> Option<&Relocation> {
+        self.blocks
+            .iter()
+            .filter(|w| w.page_rva() == rva & !0xFFF)
+            .flat_map(|w| w.relocations.iter())
+            .find(|w| w.r#type != RelocationType::Absolute && w.rva == rva)
+    }
+
+    // every fixup, without the ABSOLUTE padding entries
+    pub fn relocations(&self) -> Vec<&Relocation> {
+        self.blocks.iter().flat_map(|w| w.relocations.iter()).filter(|w| w.r#type != RelocationType::Absolute).collect()
+    }
+}
@@ -0,0 +1,460 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+use scroll::{Pread, LE};
+
+use crate::constant::{IMAGE_DIRECTORY_ENTRY_RESOURCE, RT_ACCELERATOR, RT_DIALOG, RT_GROUP_CURSOR, RT_GROUP_ICON, RT_MANIFEST, RT_MENU, RT_MESSAGETABLE, RT_STRING, RT_VERSION};
+use crate::directories::{ResourceDataEntry, ResourceDirectory, ResourceDirectoryEntry};
+use crate::resources::{AcceleratorTable, Dialog, IconGroup, IconKind, Manifest, Menu, MessageTable, StringTableBlock, VersionInfo};
+use crate::strings::gread_utf16;
+use crate::Executable;
+
+// type -> name -> language, deeper trees are malformed (or malicious)
+const MAX_RESOURCE_DEPTH: u32 = 3;
+
+// entries of the whole tree, far more than any real image carries
+const MAX_RESOURCE_ENTRIES: usize = 0x40000;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ResourceName {
+    Id(u16),
+    Name(String),
+}
+
+impl ResourceName {
+    pub fn id(&self) -> Option<u16> {
+        match self {
+            ResourceName::Id(id) => Some(*id),
+            ResourceName::Name(_) => None,
+        }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            ResourceName::Id(_) => None,
+            ResourceName::Name(name) => Some(name),
+        }
+    }
+}
+
+impl Display for ResourceName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResourceName::Id(id) => write!(f, "#{}", id),
+            ResourceName::Name(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ResourceData {
+    entry: ResourceDataEntry,
+    data: Vec<u8>,
+    error: Option<String>,
+}
+
+impl ResourceData {
+    pub fn code_page(&self) -> u32 {
+        self.entry.code_page()
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn entry(&self) -> &ResourceDataEntry {
+        &self.entry
+    }
+
+    // why the data could not be read, the data is empty then
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn rva(&self) -> u32 {
+        self.entry.offset_to_data()
+    }
+
+    pub fn size(&self) -> u32 {
+        self.entry.size()
+    }
+}
+
+#[derive(Debug)]
+pub struct ResourceEntry {
+    name: ResourceName,
+    table: Option<ResourceTable>,
+    data: Option<ResourceData>,
+    error: Option<String>,
+}
+
+impl ResourceEntry {
+    pub fn data(&self) -> Option<&ResourceData> {
+        self.data.as_ref()
+    }
+
+    // why the name or the child of the entry could not be read, it has neither a table nor data then
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn name(&self) -> &ResourceName {
+        &self.name
+    }
+
+    pub fn table(&self) -> Option<&ResourceTable> {
+        self.table.as_ref()
+    }
+
+    fn invalid(name: ResourceName, error: failure::Error) -> Self {
+        ResourceEntry {
+            name,
+            table: None,
+            data: None,
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ResourceTable {
+    directory: ResourceDirectory,
+    entries: Vec<ResourceEntry>,
+    error: Option<String>,
+}
+
+impl ResourceTable {
+    pub fn directory(&self) -> &ResourceDirectory {
+        &self.directory
+    }
+
+    pub fn entries(&self) -> Vec<&ResourceEntry> {
+        self.entries.iter().collect()
+    }
+
+    // why the entries were read only partially, the entries before the problem are kept
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+// state shared by the whole walk, a crafted tree may reference the same directory or data from many entries
+struct ResourceWalk {
+    base: usize,
+    directories: HashSet<usize>,
+    entries: usize,
+    data: usize,
+}
+
+// a leaf of the resource tree, flattened with its path
+#[derive(Debug)]
+pub struct Resource<'a> {
+    r#type: &'a ResourceName,
+    name: &'a ResourceName,
+    language: &'a ResourceName,
+    data: &'a ResourceData,
+}
+
+impl<'a> Resource<'a> {
+    pub fn data(&self) -> &'a ResourceData {
+        self.data
+    }
+
+    pub fn language(&self) -> &'a ResourceName {
+        self.language
+    }
+
+    pub fn name(&self) -> &'a ResourceName {
+        self.name
+    }
+
+    pub fn r#type(&self) -> &'a ResourceName {
+        self.r#type
+    }
+}
+
+#[derive(Debug)]
+pub struct ResourceContainer {
+    root: ResourceTable,
+}
+
+impl ResourceContainer {
+    pub fn parse(executable: &Executable) -> Result<Option<Self>, failure::Error> {
+        let data_directory = executable.optional_header().unwrap().data_directories()[IMAGE_DIRECTORY_ENTRY_RESOURCE as usize];
+        if data_directory.size() == 0 {
+            return Ok(None);
+        }
+
+        let section = match executable.in_section(data_directory) {
+            Some(section) => section,
+            None => {
+                let msg = "Failed to read resource directory";
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        let base = executable.rva_to_file_pointer(data_directory.virtual_address(), section);
+        let mut walk = ResourceWalk {
+            base,
+            directories: HashSet::new(),
+            entries: 0,
+            data: 0,
+        };
+
+        walk.directories.insert(base);
+        let root = ResourceContainer::parse_table(executable, &mut walk, base, 0)?;
+
+        Ok(Some(ResourceContainer { root }))
+    }
+
+    pub fn root(&self) -> &ResourceTable {
+        &self.root
+    }
+
+    pub fn resources(&self) -> Vec<Resource<'_>> {
+        let mut vector: Vec<Resource> = Vec::new();
+
+        for r#type in self.root.entries.iter() {
+            let names = match &r#type.table {
+                Some(table) => table,
+                None => continue,
+            };
+
+            for name in names.entries.iter() {
+                let languages = match &name.table {
+                    Some(table) => table,
+                    None => continue,
+                };
+
+                for language in languages.entries.iter() {
+                    if let Some(data) = &language.data {
+                        vector.push(Resource {
+                            r#type: &r#type.name,
+                            name: &name.name,
+                            language: &language.name,
+                            data,
+                        });
+                    }
+                }
+            }
+        }
+
+        vector
+    }
+
+    // the leaves whose data could not be read are left out, they would only fail to decode
+    pub fn resources_by_type(&self, r#type: u16) -> Vec<Resource<'_>> {
+        self.resources().into_iter().filter(|w| w.r#type.id() == Some(r#type) && w.data.error().is_none()).collect()
+    }
+
+    pub fn accelerator_tables(&self) -> Vec<Result<AcceleratorTable, failure::Error>> {
+        decode_each(self.resources_by_type(RT_ACCELERATOR), |w| AcceleratorTable::parse(w.data().data(), w.name(), w.language().id().unwrap_or(0)))
+    }
+
+    // rebuild .cur files from RT_GROUP_CURSOR and RT_CURSOR
+    pub fn cursor_groups(&self) -> Result<Vec<IconGroup>, failure::Error> {
+        self.resources_by_type(RT_GROUP_CURSOR).iter().map(|w| IconGroup::parse(self, w, IconKind::Cursor)).collect()
+    }
+
+    pub fn dialogs(&self) -> Vec<Result<Dialog, failure::Error>> {
+        decode_each(self.resources_by_type(RT_DIALOG), |w| Dialog::parse(w.data().data(), w.name(), w.language().id().unwrap_or(0)))
+    }
+
+    // rebuild .ico files from RT_GROUP_ICON and RT_ICON
+    pub fn icon_groups(&self) -> Result<Vec<IconGroup>, failure::Error> {
+        self.resources_by_type(RT_GROUP_ICON).iter().map(|w| IconGroup::parse(self, w, IconKind::Icon)).collect()
+    }
+
+    // decode RT_MANIFEST resources that the loader recognizes (ID 1 to 3)
+    pub fn manifests(&self) -> Result<Vec<Manifest>, failure::Error> {
+        let mut vector: Vec<Manifest> = Vec::new();
+
+        for resource in self.resources_by_type(RT_MANIFEST) {
+            let id = match resource.name().id() {
+                Some(id) if (1..=3).contains(&id) => id,
+                _ => continue,
+            };
+
+            let language = resource.language().id().unwrap_or(0);
+            vector.push(Manifest::parse(resource.data().data(), id, language)?);
+        }
+
+        Ok(vector)
+    }
+
+    pub fn menus(&self) -> Vec<Result<Menu, failure::Error>> {
+        decode_each(self.resources_by_type(RT_MENU), |w| Menu::parse(w.data().data(), w.name(), w.language().id().unwrap_or(0)))
+    }
+
+    pub fn message_tables(&self) -> Vec<Result<MessageTable, failure::Error>> {
+        decode_each(self.resources_by_type(RT_MESSAGETABLE), |w| MessageTable::parse(w.data().data(), w.language().id().unwrap_or(0)))
+    }
+
+    // RT_STRING resources must be named by their block id
+    pub fn string_tables(&self) -> Vec<Result<StringTableBlock, failure::Error>> {
+        let mut resources = self.resources_by_type(RT_STRING);
+        resources.retain(|w| w.name().id().is_some());
+
+        decode_each(resources, |w| StringTableBlock::parse(w.data().data(), w.name().id().unwrap(), w.language().id().unwrap_or(0)))
+    }
+
+    // decode the first RT_VERSION resource
+    pub fn version_info(&self) -> Result<Option<VersionInfo>, failure::Error> {
+        match self.resources_by_type(RT_VERSION).first() {
+            Some(resource) => Ok(Some(VersionInfo::parse(resource.data().data())?)),
+            None => Ok(None),
+        }
+    }
+
+    // only an unreadable IMAGE_RESOURCE_DIRECTORY is an error, the problems of its entries are recorded instead
+    fn parse_table(executable: &Executable, walk: &mut ResourceWalk, address: usize, depth: u32) -> Result<ResourceTable, failure::Error> {
+        let directory = ResourceDirectory::parse(executable, address)?;
+        let mut address = address + std::mem::size_of::<ResourceDirectory>();
+
+        let number_of_entries = directory.number_of_named_entries() as u32 + directory.number_of_id_entries() as u32;
+        let mut entries: Vec<ResourceEntry> = Vec::new();
+        let mut error: Option<String> = None;
+
+        for _ in 0..number_of_entries {
+            if walk.entries >= MAX_RESOURCE_ENTRIES {
+                error = Some(format!("The resource tree has more than {} entries", MAX_RESOURCE_ENTRIES));
+                break;
+            }
+
+            walk.entries += 1;
+
+            // the entries after an unreadable one cannot be located either
+            let entry = match ResourceDirectoryEntry::parse(executable, &mut address) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    error = Some(e.to_string());
+                    break;
+                }
+            };
+
+            let name = if entry.name_is_string() {
+                match ResourceContainer::parse_name(executable, walk.base + entry.name_offset() as usize) {
+                    Ok(name) => name,
+                    Err(e) => {
+                        entries.push(ResourceEntry::invalid(ResourceName::Name(String::new()), e));
+                        continue;
+                    }
+                }
+            } else {
+                ResourceName::Id(entry.id())
+            };
+
+            let address = walk.base + entry.offset_to_data() as usize;
+            if !entry.data_is_directory() {
+                match ResourceContainer::parse_data(executable, walk, address) {
+                    Ok(data) => entries.push(ResourceEntry {
+                        name,
+                        table: None,
+                        data: Some(data),
+                        error: None,
+                    }),
+                    Err(e) => entries.push(ResourceEntry::invalid(name, e)),
+                }
+                continue;
+            }
+
+            if depth + 1 >= MAX_RESOURCE_DEPTH {
+                let msg = format!("Resource directory at {:#X} is nested too deeply", address);
+                entries.push(ResourceEntry::invalid(name, failure::err_msg(msg)));
+                continue;
+            }
+
+            // each directory belongs to a single entry, sharing it would expand the tree exponentially
+            if !walk.directories.insert(address) {
+                let msg = format!("Resource directory at {:#X} is referenced more than once", address);
+                entries.push(ResourceEntry::invalid(name, failure::err_msg(msg)));
+                continue;
+            }
+
+            match ResourceContainer::parse_table(executable, walk, address, depth + 1) {
+                Ok(table) => entries.push(ResourceEntry {
+                    name,
+                    table: Some(table),
+                    data: None,
+                    error: None,
+                }),
+                Err(e) => entries.push(ResourceEntry::invalid(name, e)),
+            }
+        }
+
+        Ok(ResourceTable { directory, entries, error })
+    }
+
+    // IMAGE_RESOURCE_DIR_STRING_U
+    fn parse_name(executable: &Executable, address: usize) -> Result<ResourceName, failure::Error> {
+        let mut address = address;
+        let length = executable.buffer().gread_with::<u16>(&mut address, LE).map_err(|_| {
+            let msg = format!("Failed to read the IMAGE_RESOURCE_DIR_STRING_U at {:#X}", address);
+            failure::err_msg(msg)
+        })?;
+
+        let name = gread_utf16(executable.buffer(), &mut address, length as usize).map_err(|_| {
+            let msg = format!("Failed to read the name of IMAGE_RESOURCE_DIR_STRING_U at {:#X}", address);
+            failure::err_msg(msg)
+        })?;
+
+        Ok(ResourceName::Name(name))
+    }
+
+    // a leaf pointing outside of the file is flagged, the other resources are still readable
+    fn parse_data(executable: &Executable, walk: &mut ResourceWalk, address: usize) -> Result<ResourceData, failure::Error> {
+        let entry = ResourceDataEntry::parse(executable, address)?;
+
+        let address = match executable.rva_to_offset(entry.offset_to_data()) {
+            Some(address) => address,
+            None => {
+                let error = format!("Failed to resolve the resource data at RVA {:#010X}", entry.offset_to_data());
+                return Ok(ResourceData {
+                    entry,
+                    data: Vec::new(),
+                    error: Some(error),
+                });
+            }
+        };
+
+        // leaves sharing the same bytes would be copied once for each of them
+        let size = entry.size() as usize;
+        if walk.data.saturating_add(size) > executable.buffer().len() {
+            let error = format!("Resource data at {:#X} exceeds the size of the file in total", address);
+            return Ok(ResourceData {
+                entry,
+                data: Vec::new(),
+                error: Some(error),
+            });
+        }
+
+        let data = match executable.buffer().get(address..address.saturating_add(size)) {
+            Some(data) => data.to_vec(),
+            None => {
+                let error = format!("Failed to read the resource data at {:#X}", address);
+                return Ok(ResourceData {
+                    entry,
+                    data: Vec::new(),
+                    error: Some(error),
+                });
+            }
+        };
+
+        walk.data += size;
+
+        Ok(ResourceData { entry, data, error: None })
+    }
+}
+
+// a resource that fails to decode does not hide the others, its error names it
+fn decode_each<T>(resources: Vec<Resource<'_>>, decode: impl Fn(&Resource) -> Result<T, failure::Error>) -> Vec<Result<T, failure::Error>> {
+    resources
+        .iter()
+        .map(|w| {
+            decode(w).map_err(|e| {
+                let msg = format!("Failed to decode {} [{:#06X}]: {}", w.name(), w.language().id().unwrap_or(0), e);
+                failure::err_msg(msg)
+            })
+        })
+        .collect()
+}
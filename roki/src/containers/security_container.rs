@@ -0,0 +1,116 @@
+use std::mem::size_of;
+
+use crate::authenticode::AuthenticodeSignature;
+use crate::constant::{IMAGE_DIRECTORY_ENTRY_SECURITY, WIN_CERT_TYPE_PKCS_SIGNED_DATA};
+use crate::directories::WinCertificate;
+use crate::Executable;
+
+#[derive(Debug)]
+pub struct Certificate {
+    header: WinCertificate,
+    offset: usize,
+    data: Vec<u8>,
+}
+
+impl Certificate {
+    pub fn certificate_type(&self) -> u16 {
+        self.header.certificate_type()
+    }
+
+    // bCertificate, a PKCS#7 SignedData for WIN_CERT_TYPE_PKCS_SIGNED_DATA
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn header(&self) -> &WinCertificate {
+        &self.header
+    }
+
+    // file offset of the WIN_CERTIFICATE
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn revision(&self) -> u16 {
+        self.header.revision()
+    }
+}
+
+#[derive(Debug)]
+pub struct SecurityContainer {
+    offset: usize,
+    size: usize,
+    certificates: Vec<Certificate>,
+    error: Option<String>,
+}
+
+impl SecurityContainer {
+    pub fn parse(executable: &Executable) -> Result<Option<Self>, failure::Error> {
+        let data_directory = executable.optional_header().unwrap().data_directories()[IMAGE_DIRECTORY_ENTRY_SECURITY as usize];
+        if data_directory.size() == 0 {
+            return Ok(None);
+        }
+
+        // VirtualAddress of the attribute certificate table is a file offset, it is not mapped into memory
+        let offset = data_directory.virtual_address() as usize;
+        let size = data_directory.size() as usize;
+        let mut end = offset.saturating_add(size);
+        let mut error: Option<String> = None;
+
+        // a truncated table still yields the certificates before the end of file
+        if end > executable.buffer().len() {
+            error = Some(format!("Attribute certificate table at {:#X} exceeds the end of file", offset));
+            end = executable.buffer().len();
+        }
+
+        let mut certificates: Vec<Certificate> = Vec::new();
+        let mut address = offset;
+
+        while address + size_of::<WinCertificate>() <= end {
+            let header = WinCertificate::parse(executable, address)?;
+            let length = header.length() as usize;
+
+            // the entries after a bad length cannot be located
+            if length < size_of::<WinCertificate>() || address + length > end {
+                error = Some(format!("WIN_CERTIFICATE at {:#X} has an invalid length {:#X}", address, length));
+                break;
+            }
+
+            let data = executable.buffer()[address + size_of::<WinCertificate>()..address + length].to_vec();
+            certificates.push(Certificate { header, offset: address, data });
+
+            // each entry starts on an octaword boundary
+            address = (address + length + 7) & !7;
+        }
+
+        Ok(Some(SecurityContainer { offset, size, certificates, error }))
+    }
+
+    // decode WIN_CERT_TYPE_PKCS_SIGNED_DATA entries
+    pub fn authenticode_signatures(&self) -> Result<Vec<AuthenticodeSignature>, failure::Error> {
+        self.signatures().iter().map(|w| AuthenticodeSignature::parse(w)).collect()
+    }
+
+    pub fn certificates(&self) -> Vec<&Certificate> {
+        self.certificates.iter().collect()
+    }
+
+    // why the table was read only partially, the certificates before the problem are kept
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    // file offset of the attribute certificate table
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    // raw PKCS#7 SignedData blobs
+    pub fn signatures(&self) -> Vec<&[u8]> {
+        self.certificates.iter().filter(|w| w.certificate_type() == WIN_CERT_TYPE_PKCS_SIGNED_DATA).map(|w| w.data()).collect()
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
@@ -0,0 +1,222 @@
+use scroll::{Pread, LE};
+
+use crate::constant::IMAGE_DIRECTORY_ENTRY_TLS;
+use crate::directories::TlsDirectory;
+use crate::Executable;
+
+const MAX_CALLBACKS: usize = 0x1000;
+
+#[derive(Clone, Copy, Debug)]
+pub struct TlsCallback {
+    va: u64,
+    rva: Option<u32>,
+}
+
+impl TlsCallback {
+    pub fn rva(&self) -> Option<u32> {
+        self.rva
+    }
+
+    pub fn va(&self) -> u64 {
+        self.va
+    }
+}
+
+#[derive(Debug)]
+pub struct TlsContainer {
+    directory: TlsDirectory,
+    // RVAs of the VA fields in the directory
+    start_address_of_raw_data: Option<u32>,
+    end_address_of_raw_data: Option<u32>,
+    address_of_index: Option<u32>,
+    address_of_callbacks: Option<u32>,
+    template: Vec<u8>,
+    template_size: u64,
+    template_error: Option<String>,
+    callbacks: Vec<TlsCallback>,
+    callbacks_error: Option<String>,
+}
+
+impl TlsContainer {
+    pub fn parse(executable: &Executable) -> Result<Option<Self>, failure::Error> {
+        let data_directory = executable.optional_header().unwrap().data_directories()[IMAGE_DIRECTORY_ENTRY_TLS as usize];
+        if data_directory.size() == 0 {
+            return Ok(None);
+        }
+
+        let section = match executable.in_section(data_directory) {
+            Some(section) => section,
+            None => {
+                let msg = "Failed to read TLS directory";
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        let offset = executable.rva_to_file_pointer(data_directory.virtual_address(), section);
+        let directory = TlsDirectory::parse(executable, offset)?;
+
+        // the template is copied into each thread's TLS block, the rest up to SizeOfZeroFill is zeroed
+        let template_size = directory.end_address_of_raw_data().saturating_sub(directory.start_address_of_raw_data());
+        let (template, template_error) = match TlsContainer::read_template(executable, &directory, template_size) {
+            Ok(template) => (template, None),
+            Err(e) => (Vec::new(), Some(e.to_string())),
+        };
+
+        // a bad callback array keeps the callbacks read before it, the rest of the directory is still valid
+        let mut callbacks: Vec<TlsCallback> = Vec::new();
+        let callbacks_error = TlsContainer::read_callbacks(executable, &directory, &mut callbacks).err().map(|e| e.to_string());
+
+        Ok(Some(TlsContainer {
+            directory,
+            start_address_of_raw_data: va_to_rva(executable, directory.start_address_of_raw_data()),
+            end_address_of_raw_data: va_to_rva(executable, directory.end_address_of_raw_data()),
+            address_of_index: va_to_rva(executable, directory.address_of_index()),
+            address_of_callbacks: va_to_rva(executable, directory.address_of_callbacks()),
+            template,
+            template_size,
+            template_error,
+            callbacks,
+            callbacks_error,
+        }))
+    }
+
+    fn read_callbacks(executable: &Executable, directory: &TlsDirectory, callbacks: &mut Vec<TlsCallback>) -> Result<(), failure::Error> {
+        if directory.address_of_callbacks() == 0 {
+            return Ok(());
+        }
+
+        let rva = match va_to_rva(executable, directory.address_of_callbacks()) {
+            Some(rva) => rva,
+            None => {
+                let msg = format!("AddressOfCallBacks {:#X} is out of the image", directory.address_of_callbacks());
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        let mut offset = match executable.rva_to_offset(rva) {
+            Some(offset) => offset,
+            None => {
+                let msg = format!("Failed to resolve the TLS callbacks at RVA {:#010X}", rva);
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        // null-terminated array of PIMAGE_TLS_CALLBACK
+        loop {
+            let address = offset;
+            let read = |_: scroll::Error| {
+                let msg = format!("Failed to read the TLS callback at {:#X}", address);
+                failure::err_msg(msg)
+            };
+
+            let va = if executable.is_pe32_plus() {
+                executable.buffer().gread_with::<u64>(&mut offset, LE).map_err(read)?
+            } else {
+                executable.buffer().gread_with::<u32>(&mut offset, LE).map_err(read)? as u64
+            };
+
+            if va == 0 {
+                return Ok(());
+            }
+
+            if callbacks.len() >= MAX_CALLBACKS {
+                let msg = format!("TLS callback array at RVA {:#010X} is not terminated", rva);
+                return Err(failure::err_msg(msg));
+            }
+
+            callbacks.push(TlsCallback { va, rva: va_to_rva(executable, va) });
+        }
+    }
+
+    // only the bytes backed by the file, the uninitialized part of the section reads as zeros
+    fn read_template(executable: &Executable, directory: &TlsDirectory, size: u64) -> Result<Vec<u8>, failure::Error> {
+        if size == 0 {
+            return Ok(Vec::new());
+        }
+
+        let rva = match va_to_rva(executable, directory.start_address_of_raw_data()) {
+            Some(rva) => rva,
+            None => {
+                let msg = format!("StartAddressOfRawData {:#X} is out of the image", directory.start_address_of_raw_data());
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        if size > executable.optional_header().unwrap().size_of_image() as u64 {
+            let msg = format!("TLS template at RVA {:#010X} is larger than the image", rva);
+            return Err(failure::err_msg(msg));
+        }
+
+        let section = match executable.rva_in_section(rva) {
+            Some(section) => section,
+            None => {
+                let msg = format!("Failed to resolve the TLS template at RVA {:#010X}", rva);
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        let start = executable.rva_to_file_pointer(rva, section);
+        let end_of_section = section.pointer_to_raw_data() as usize + section.size_of_raw_data() as usize;
+        let end_of_data = (start + size as usize).min(end_of_section).min(executable.buffer().len());
+
+        Ok(executable.buffer().get(start..end_of_data).unwrap_or_default().to_vec())
+    }
+
+    pub fn address_of_callbacks_rva(&self) -> Option<u32> {
+        self.address_of_callbacks
+    }
+
+    pub fn address_of_index_rva(&self) -> Option<u32> {
+        self.address_of_index
+    }
+
+    pub fn callbacks(&self) -> Vec<&TlsCallback> {
+        self.callbacks.iter().collect()
+    }
+
+    // why the callback array was not read to its end, the callbacks before the problem are kept
+    pub fn callbacks_error(&self) -> Option<&str> {
+        self.callbacks_error.as_deref()
+    }
+
+    pub fn directory(&self) -> &TlsDirectory {
+        &self.directory
+    }
+
+    pub fn end_address_of_raw_data_rva(&self) -> Option<u32> {
+        self.end_address_of_raw_data
+    }
+
+    pub fn size_of_zero_fill(&self) -> u32 {
+        self.directory.size_of_zero_fill()
+    }
+
+    pub fn start_address_of_raw_data_rva(&self) -> Option<u32> {
+        self.start_address_of_raw_data
+    }
+
+    // the part of the raw data between StartAddressOfRawData and EndAddressOfRawData that is backed by the file
+    pub fn template(&self) -> &[u8] {
+        &self.template
+    }
+
+    // why the template could not be read, it is empty then
+    pub fn template_error(&self) -> Option<&str> {
+        self.template_error.as_deref()
+    }
+
+    // EndAddressOfRawData - StartAddressOfRawData, the template occupies that much of each TLS block
+    pub fn template_size(&self) -> u64 {
+        self.template_size
+    }
+}
+
+// None if the VA is below the image base or beyond the image
+fn va_to_rva(executable: &Executable, va: u64) -> Option<u32> {
+    let optional_header = executable.optional_header().unwrap();
+
+    match va.checked_sub(optional_header.image_base()) {
+        Some(rva) if rva < optional_header.size_of_image() as u64 => Some(rva as u32),
+        _ => None,
+    }
+}
@@ -0,0 +1,30 @@
+use scroll::{Pread, LE};
+
+use crate::Executable;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+pub struct BaseRelocationBlock {
+    // https://docs.microsoft.com/en-us/windows/win32/debug/pe-format#base-relocation-block
+    virtual_address: u32, // page RVA
+    size_of_block: u32,   // including this header
+}
+
+impl BaseRelocationBlock {
+    pub fn parse(executable: &Executable, offset: &mut usize) -> Result<BaseRelocationBlock, failure::Error> {
+        let block = executable.buffer().gread_with::<BaseRelocationBlock>(offset, LE).map_err(|_| {
+            let msg = format!("Failed to read the IMAGE_BASE_RELOCATION at {:#X}", offset);
+            failure::err_msg(msg)
+        })?;
+
+        Ok(block)
+    }
+
+    pub fn size_of_block(&self) -> u32 {
+        self.size_of_block
+    }
+
+    pub fn virtual_address(&self) -> u32 {
+        self.virtual_address
+    }
+}
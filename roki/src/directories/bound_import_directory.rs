@@ -0,0 +1,67 @@
+use scroll::{Pread, LE};
+
+use crate::Executable;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+pub struct BoundImportDescriptor {
+    // https://docs.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-image_bound_import_descriptor
+    time_date_stamp: u32,
+    offset_module_name: u16, // from the beginning of the bound import table
+    number_of_module_forwarder_refs: u16,
+}
+
+impl BoundImportDescriptor {
+    pub fn parse(executable: &Executable, offset: &mut usize) -> Result<BoundImportDescriptor, failure::Error> {
+        let descriptor = executable.buffer().gread_with::<BoundImportDescriptor>(offset, LE).map_err(|_| {
+            let msg = format!("Failed to read the IMAGE_BOUND_IMPORT_DESCRIPTOR at {:#X}", offset);
+            failure::err_msg(msg)
+        })?;
+
+        Ok(descriptor)
+    }
+
+    pub fn number_of_module_forwarder_refs(&self) -> u16 {
+        self.number_of_module_forwarder_refs
+    }
+
+    pub fn offset_module_name(&self) -> u16 {
+        self.offset_module_name
+    }
+
+    pub fn time_date_stamp(&self) -> u32 {
+        self.time_date_stamp
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+pub struct BoundForwarderRef {
+    // https://docs.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-image_bound_forwarder_ref
+    time_date_stamp: u32,
+    offset_module_name: u16, // from the beginning of the bound import table
+    reserved: u16,
+}
+
+impl BoundForwarderRef {
+    pub fn parse(executable: &Executable, offset: &mut usize) -> Result<BoundForwarderRef, failure::Error> {
+        let forwarder_ref = executable.buffer().gread_with::<BoundForwarderRef>(offset, LE).map_err(|_| {
+            let msg = format!("Failed to read the IMAGE_BOUND_FORWARDER_REF at {:#X}", offset);
+            failure::err_msg(msg)
+        })?;
+
+        Ok(forwarder_ref)
+    }
+
+    pub fn offset_module_name(&self) -> u16 {
+        self.offset_module_name
+    }
+
+    pub fn reserved(&self) -> u16 {
+        self.reserved
+    }
+
+    pub fn time_date_stamp(&self) -> u32 {
+        self.time_date_stamp
+    }
+}
@@ -0,0 +1,69 @@
+use scroll::{Pread, LE};
+
+use crate::Executable;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+pub struct DelayLoadDescriptor {
+    // https://docs.microsoft.com/en-us/windows/win32/debug/pe-format#delay-load-directory-table
+    attributes: u32,
+    dll_name_rva: u32,
+    module_handle_rva: u32,
+    import_address_table_rva: u32,
+    import_name_table_rva: u32,
+    bound_import_address_table_rva: u32,
+    unload_information_table_rva: u32,
+    time_date_stamp: u32,
+}
+
+impl DelayLoadDescriptor {
+    pub fn parse(executable: &Executable, offset: &mut usize) -> Result<DelayLoadDescriptor, failure::Error> {
+        let descriptor = executable.buffer().gread_with::<DelayLoadDescriptor>(offset, LE).map_err(|_| {
+            let msg = format!("Failed to read the IMAGE_DELAYLOAD_DESCRIPTOR at {:#X}", offset);
+            failure::err_msg(msg)
+        })?;
+
+        Ok(descriptor)
+    }
+
+    pub fn attributes(&self) -> u32 {
+        self.attributes
+    }
+
+    pub fn bound_import_address_table_rva(&self) -> u32 {
+        self.bound_import_address_table_rva
+    }
+
+    pub fn dll_name_rva(&self) -> u32 {
+        self.dll_name_rva
+    }
+
+    pub fn import_address_table_rva(&self) -> u32 {
+        self.import_address_table_rva
+    }
+
+    pub fn import_name_table_rva(&self) -> u32 {
+        self.import_name_table_rva
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dll_name_rva == 0 && self.import_address_table_rva == 0 && self.import_name_table_rva == 0
+    }
+
+    // RvaBased, the address fields are VAs in the legacy layout (Visual C++ 6.0)
+    pub fn is_rva_based(&self) -> bool {
+        self.attributes & 0x1 != 0
+    }
+
+    pub fn module_handle_rva(&self) -> u32 {
+        self.module_handle_rva
+    }
+
+    pub fn time_date_stamp(&self) -> u32 {
+        self.time_date_stamp
+    }
+
+    pub fn unload_information_table_rva(&self) -> u32 {
+        self.unload_information_table_rva
+    }
+}
@@ -0,0 +1,67 @@
+use scroll::{Pread, LE};
+
+use crate::Executable;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+pub struct RuntimeFunction {
+    // https://docs.microsoft.com/en-us/cpp/build/exception-handling-x64#struct-runtime_function
+    begin_address: u32,
+    end_address: u32,
+    unwind_info_address: u32, // a.k.a unwind_data
+}
+
+impl RuntimeFunction {
+    pub fn parse(executable: &Executable, offset: &mut usize) -> Result<RuntimeFunction, failure::Error> {
+        let runtime_function = executable.buffer().gread_with::<RuntimeFunction>(offset, LE).map_err(|_| {
+            let msg = format!("Failed to read the RUNTIME_FUNCTION at {:#X}", offset);
+            failure::err_msg(msg)
+        })?;
+
+        Ok(runtime_function)
+    }
+
+    pub fn begin_address(&self) -> u32 {
+        self.begin_address
+    }
+
+    pub fn end_address(&self) -> u32 {
+        self.end_address
+    }
+
+    pub fn unwind_info_address(&self) -> u32 {
+        self.unwind_info_address
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+pub struct Arm64RuntimeFunction {
+    // https://docs.microsoft.com/en-us/cpp/build/arm64-exception-handling#pdata-records
+    begin_address: u32,
+    unwind_data: u32,
+}
+
+impl Arm64RuntimeFunction {
+    pub fn parse(executable: &Executable, offset: &mut usize) -> Result<Arm64RuntimeFunction, failure::Error> {
+        let runtime_function = executable.buffer().gread_with::<Arm64RuntimeFunction>(offset, LE).map_err(|_| {
+            let msg = format!("Failed to read the IMAGE_ARM64_RUNTIME_FUNCTION_ENTRY at {:#X}", offset);
+            failure::err_msg(msg)
+        })?;
+
+        Ok(runtime_function)
+    }
+
+    pub fn begin_address(&self) -> u32 {
+        self.begin_address
+    }
+
+    // 0: RVA of .xdata record, 1: packed unwind data, 2: packed unwind data of a function fragment without prolog
+    pub fn flag(&self) -> u8 {
+        (self.unwind_data & 0x3) as u8
+    }
+
+    pub fn unwind_data(&self) -> u32 {
+        self.unwind_data
+    }
+}
@@ -166,7 +166,7 @@ pub struct LoadConfigDirectory {
 
 impl LoadConfigDirectory {
     pub fn parse(executable: &Executable, offset: usize) -> Result<Self, failure::Error> {
-        let directory = if executable.is_x64() {
+        let directory = if executable.is_pe32_plus() {
             LoadConfigDirectory::from_load_config_directory_64(executable.buffer().pread_with::<LoadConfigDirectory64>(offset, LE).map_err(|_| {
                 let msg = format!("Failed to read the IMAGE_LOAD_CONFIG_DIRECTORY64 at {:#010X}", offset);
                 return failure::err_msg(msg);
@@ -1,9 +1,23 @@
+mod base_relocation_directory;
+mod bound_import_directory;
 mod data_directory;
 mod debug_directory;
+mod delay_import_directory;
+mod exception_directory;
 mod export_directory;
 mod load_config_directory;
+mod resource_directory;
+mod security_directory;
+mod tls_directory;
 
+pub use base_relocation_directory::*;
+pub use bound_import_directory::*;
 pub use data_directory::*;
 pub use debug_directory::*;
+pub use delay_import_directory::*;
+pub use exception_directory::*;
 pub use export_directory::*;
 pub use load_config_directory::*;
+pub use resource_directory::*;
+pub use security_directory::*;
+pub use tls_directory::*;
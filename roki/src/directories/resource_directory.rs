@@ -0,0 +1,124 @@
+use scroll::{Pread, LE};
+
+use crate::Executable;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+pub struct ResourceDirectory {
+    // https://docs.microsoft.com/en-us/windows/win32/debug/pe-format#resource-directory-table
+    characteristics: u32,
+    time_date_stamp: u32,
+    major_version: u16,
+    minor_version: u16,
+    number_of_named_entries: u16,
+    number_of_id_entries: u16,
+}
+
+impl ResourceDirectory {
+    pub fn parse(executable: &Executable, offset: usize) -> Result<ResourceDirectory, failure::Error> {
+        let resource_directory = executable.buffer().pread_with::<ResourceDirectory>(offset, LE).map_err(|_| {
+            let msg = format!("Failed to read the IMAGE_RESOURCE_DIRECTORY at {:#X}", offset);
+            failure::err_msg(msg)
+        })?;
+
+        Ok(resource_directory)
+    }
+
+    pub fn characteristics(&self) -> u32 {
+        self.characteristics
+    }
+
+    pub fn major_version(&self) -> u16 {
+        self.major_version
+    }
+
+    pub fn minor_version(&self) -> u16 {
+        self.minor_version
+    }
+
+    pub fn number_of_id_entries(&self) -> u16 {
+        self.number_of_id_entries
+    }
+
+    pub fn number_of_named_entries(&self) -> u16 {
+        self.number_of_named_entries
+    }
+
+    pub fn time_date_stamp(&self) -> u32 {
+        self.time_date_stamp
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+pub struct ResourceDirectoryEntry {
+    // https://docs.microsoft.com/en-us/windows/win32/debug/pe-format#resource-directory-entries
+    name: u32,           // a.k.a id
+    offset_to_data: u32, // a.k.a offset_to_directory
+}
+
+impl ResourceDirectoryEntry {
+    pub fn parse(executable: &Executable, offset: &mut usize) -> Result<ResourceDirectoryEntry, failure::Error> {
+        let entry = executable.buffer().gread_with::<ResourceDirectoryEntry>(offset, LE).map_err(|_| {
+            let msg = format!("Failed to read the IMAGE_RESOURCE_DIRECTORY_ENTRY at {:#X}", offset);
+            failure::err_msg(msg)
+        })?;
+
+        Ok(entry)
+    }
+
+    pub fn data_is_directory(&self) -> bool {
+        self.offset_to_data & 0x80000000 != 0
+    }
+
+    pub fn id(&self) -> u16 {
+        (self.name & 0xFFFF) as u16
+    }
+
+    pub fn name_is_string(&self) -> bool {
+        self.name & 0x80000000 != 0
+    }
+
+    // relative to the beginning of the resource directory
+    pub fn name_offset(&self) -> u32 {
+        self.name & 0x7FFFFFFF
+    }
+
+    // relative to the beginning of the resource directory
+    pub fn offset_to_data(&self) -> u32 {
+        self.offset_to_data & 0x7FFFFFFF
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+pub struct ResourceDataEntry {
+    // https://docs.microsoft.com/en-us/windows/win32/debug/pe-format#resource-data-entry
+    offset_to_data: u32, // RVA, not relative to the resource directory
+    size: u32,
+    code_page: u32,
+    reserved: u32,
+}
+
+impl ResourceDataEntry {
+    pub fn parse(executable: &Executable, offset: usize) -> Result<ResourceDataEntry, failure::Error> {
+        let entry = executable.buffer().pread_with::<ResourceDataEntry>(offset, LE).map_err(|_| {
+            let msg = format!("Failed to read the IMAGE_RESOURCE_DATA_ENTRY at {:#X}", offset);
+            failure::err_msg(msg)
+        })?;
+
+        Ok(entry)
+    }
+
+    pub fn code_page(&self) -> u32 {
+        self.code_page
+    }
+
+    pub fn offset_to_data(&self) -> u32 {
+        self.offset_to_data
+    }
+
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+}
@@ -0,0 +1,37 @@
+use scroll::{Pread, LE};
+
+use crate::Executable;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+pub struct WinCertificate {
+    // https://docs.microsoft.com/en-us/windows/win32/api/wintrust/ns-wintrust-win_certificate
+    length: u32, // includes this header
+    revision: u16,
+    certificate_type: u16,
+}
+
+impl WinCertificate {
+    pub fn parse(executable: &Executable, offset: usize) -> Result<WinCertificate, failure::Error> {
+        let certificate = executable.buffer().pread_with::<WinCertificate>(offset, LE).map_err(|_| {
+            let msg = format!("Failed to read the WIN_CERTIFICATE at {:#X}", offset);
+            failure::err_msg(msg)
+        })?;
+
+        Ok(certificate)
+    }
+
+    // WIN_CERT_TYPE_X509, WIN_CERT_TYPE_PKCS_SIGNED_DATA or WIN_CERT_TYPE_TS_STACK_SIGNED
+    pub fn certificate_type(&self) -> u16 {
+        self.certificate_type
+    }
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    // WIN_CERT_REVISION_1_0 or WIN_CERT_REVISION_2_0
+    pub fn revision(&self) -> u16 {
+        self.revision
+    }
+}
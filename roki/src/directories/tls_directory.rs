@@ -0,0 +1,106 @@
+use scroll::{Pread, LE};
+
+use crate::Executable;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+struct TlsDirectory32 {
+    // https://docs.microsoft.com/en-us/windows/win32/debug/pe-format#the-tls-directory
+    start_address_of_raw_data: u32,
+    end_address_of_raw_data: u32,
+    address_of_index: u32,
+    address_of_callbacks: u32,
+    size_of_zero_fill: u32,
+    characteristics: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+struct TlsDirectory64 {
+    // https://docs.microsoft.com/en-us/windows/win32/debug/pe-format#the-tls-directory
+    start_address_of_raw_data: u64,
+    end_address_of_raw_data: u64,
+    address_of_index: u64,
+    address_of_callbacks: u64,
+    size_of_zero_fill: u32,
+    characteristics: u32,
+}
+
+// the address fields are VAs, not RVAs
+#[derive(Clone, Copy, Debug)]
+pub struct TlsDirectory {
+    start_address_of_raw_data: u64,
+    end_address_of_raw_data: u64,
+    address_of_index: u64,
+    address_of_callbacks: u64,
+    size_of_zero_fill: u32,
+    characteristics: u32,
+}
+
+impl TlsDirectory {
+    pub fn parse(executable: &Executable, offset: usize) -> Result<Self, failure::Error> {
+        let directory = if executable.is_pe32_plus() {
+            let directory = executable.buffer().pread_with::<TlsDirectory64>(offset, LE).map_err(|_| {
+                let msg = format!("Failed to read the IMAGE_TLS_DIRECTORY64 at {:#010X}", offset);
+                failure::err_msg(msg)
+            })?;
+
+            TlsDirectory {
+                start_address_of_raw_data: directory.start_address_of_raw_data,
+                end_address_of_raw_data: directory.end_address_of_raw_data,
+                address_of_index: directory.address_of_index,
+                address_of_callbacks: directory.address_of_callbacks,
+                size_of_zero_fill: directory.size_of_zero_fill,
+                characteristics: directory.characteristics,
+            }
+        } else {
+            let directory = executable.buffer().pread_with::<TlsDirectory32>(offset, LE).map_err(|_| {
+                let msg = format!("Failed to read the IMAGE_TLS_DIRECTORY32 at {:#010X}", offset);
+                failure::err_msg(msg)
+            })?;
+
+            TlsDirectory {
+                start_address_of_raw_data: directory.start_address_of_raw_data as u64,
+                end_address_of_raw_data: directory.end_address_of_raw_data as u64,
+                address_of_index: directory.address_of_index as u64,
+                address_of_callbacks: directory.address_of_callbacks as u64,
+                size_of_zero_fill: directory.size_of_zero_fill,
+                characteristics: directory.characteristics,
+            }
+        };
+
+        Ok(directory)
+    }
+
+    pub fn address_of_callbacks(&self) -> u64 {
+        self.address_of_callbacks
+    }
+
+    pub fn address_of_index(&self) -> u64 {
+        self.address_of_index
+    }
+
+    // IMAGE_SCN_ALIGN_* in bits 20:23, the other bits are reserved
+    pub fn alignment(&self) -> Option<u32> {
+        match (self.characteristics >> 20) & 0xF {
+            0 => None,
+            w => Some(1 << (w - 1)),
+        }
+    }
+
+    pub fn characteristics(&self) -> u32 {
+        self.characteristics
+    }
+
+    pub fn end_address_of_raw_data(&self) -> u64 {
+        self.end_address_of_raw_data
+    }
+
+    pub fn size_of_zero_fill(&self) -> u32 {
+        self.size_of_zero_fill
+    }
+
+    pub fn start_address_of_raw_data(&self) -> u64 {
+        self.start_address_of_raw_data
+    }
+}
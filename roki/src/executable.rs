@@ -3,12 +3,13 @@ use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
-use crate::containers::{ClrContainer, DebugContainer, ExportContainer, ImportContainer, LoadConfigContainer};
+use crate::containers::{
+    BoundImportContainer, ClrContainer, DebugContainer, DelayImportContainer, ExceptionContainer, ExportContainer, ImportContainer, LoadConfigContainer, RelocationContainer, ResourceContainer, SecurityContainer,
+    TlsContainer,
+};
 use crate::directories::DataDirectory;
 use crate::headers::{DosHeader, FileHeader, OptionalHeader, SectionHeader};
 
-const X64_MACHINE: u16 = 0x8664;
-
 #[derive(Debug)]
 pub struct Executable {
     path: String,
@@ -21,18 +22,18 @@ pub struct Executable {
 
     export_data: Option<ExportContainer>,
     import_data: Option<ImportContainer>,
-    resource_data: Option<()>,
-    exception_data: Option<()>,
-    security_data: Option<()>,
-    base_relocation_data: Option<()>,
+    resource_data: Option<ResourceContainer>,
+    exception_data: Option<ExceptionContainer>,
+    security_data: Option<SecurityContainer>,
+    base_relocation_data: Option<RelocationContainer>,
     debug_data: Option<Vec<DebugContainer>>,
     architecture_data: Option<()>,
     global_pointer_data: Option<()>,
-    tls_data: Option<()>,
+    tls_data: Option<TlsContainer>,
     load_config_data: Option<LoadConfigContainer>,
-    bound_import_data: Option<()>,
+    bound_import_data: Option<BoundImportContainer>,
     entry_iat_data: Option<()>,
-    delay_import_data: Option<()>,
+    delay_import_data: Option<DelayImportContainer>,
     com_descriptor_data: Option<ClrContainer>,
     // reserved: Option<()>,
 }
@@ -84,6 +85,14 @@ impl Executable {
         return array;
     }
 
+    pub fn base_relocation_data(&self) -> Option<&RelocationContainer> {
+        self.base_relocation_data.as_ref()
+    }
+
+    pub fn bound_import_data(&self) -> Option<&BoundImportContainer> {
+        self.bound_import_data.as_ref()
+    }
+
     pub fn com_descriptor_data(&self) -> Option<&ClrContainer> {
         self.com_descriptor_data.as_ref()
     }
@@ -95,10 +104,18 @@ impl Executable {
         }
     }
 
+    pub fn delay_import_data(&self) -> Option<&DelayImportContainer> {
+        self.delay_import_data.as_ref()
+    }
+
     pub fn dos_header(&self) -> Option<&DosHeader> {
         self.dos_header.as_ref()
     }
 
+    pub fn exception_data(&self) -> Option<&ExceptionContainer> {
+        self.exception_data.as_ref()
+    }
+
     pub fn export_data(&self) -> Option<&ExportContainer> {
         self.export_data.as_ref()
     }
@@ -119,6 +136,18 @@ impl Executable {
         self.optional_header.as_ref()
     }
 
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn resource_data(&self) -> Option<&ResourceContainer> {
+        self.resource_data.as_ref()
+    }
+
+    pub fn security_data(&self) -> Option<&SecurityContainer> {
+        self.security_data.as_ref()
+    }
+
     pub fn section_headers(&self) -> Option<Vec<&SectionHeader>> {
         match &self.section_headers {
             Some(section_headers) => Some(section_headers.iter().map(|s| s).collect()),
@@ -126,19 +155,60 @@ impl Executable {
         }
     }
 
+    pub fn tls_data(&self) -> Option<&TlsContainer> {
+        self.tls_data.as_ref()
+    }
+
     // functions
     pub fn parse(&mut self) -> Result<(), failure::Error> {
-        // headers
+        if !self.parse_headers()? {
+            return Ok(());
+        }
+
+        // directories
+        // TODO other data
+        self.export_data = ExportContainer::parse(self)?;
+        self.bound_import_data = BoundImportContainer::parse(self)?; // referenced by the import descriptors
+        self.import_data = ImportContainer::parse(self)?;
+        self.resource_data = ResourceContainer::parse(self)?;
+        self.exception_data = ExceptionContainer::parse(self)?;
+        self.security_data = SecurityContainer::parse(self)?;
+        self.base_relocation_data = RelocationContainer::parse(self)?;
+        self.debug_data = DebugContainer::parse(self)?;
+        self.tls_data = TlsContainer::parse(self)?;
+        self.load_config_data = LoadConfigContainer::parse(self)?;
+        self.delay_import_data = DelayImportContainer::parse(self)?;
+        self.com_descriptor_data = ClrContainer::parse(self)?;
+
+        Ok(())
+    }
+
+    // the headers and the directories that link modules together, enough to resolve the dependents of a DLL
+    pub fn parse_dependencies(&mut self) -> Result<(), failure::Error> {
+        if !self.parse_headers()? {
+            return Ok(());
+        }
+
+        self.export_data = ExportContainer::parse(self)?;
+        self.bound_import_data = BoundImportContainer::parse(self)?; // referenced by the import descriptors
+        self.import_data = ImportContainer::parse(self)?;
+        self.delay_import_data = DelayImportContainer::parse(self)?;
+
+        Ok(())
+    }
+
+    // false if the file is not a portable executable, the directories are not parsed then
+    fn parse_headers(&mut self) -> Result<bool, failure::Error> {
         self.dos_header = Some(DosHeader::parse(self)?);
         if !self.dos_header().unwrap().is_windows_executable() {
-            return Ok(());
+            return Ok(false);
         }
 
         let mut offset: usize = 0;
 
         self.file_header = Some(FileHeader::parse(self, &mut offset)?);
         if !self.file_header().unwrap().is_portable_executable() {
-            return Ok(());
+            return Ok(false);
         }
 
         self.optional_header = Some(OptionalHeader::parse(self, &mut offset)?);
@@ -150,20 +220,15 @@ impl Executable {
 
         self.section_headers = Some(section_headers);
 
-        // directories
-        // TODO other data
-        self.export_data = ExportContainer::parse(self)?;
-        self.import_data = ImportContainer::parse(self)?;
-        self.debug_data = DebugContainer::parse(self)?;
-        self.load_config_data = LoadConfigContainer::parse(self)?;
-        self.com_descriptor_data = ClrContainer::parse(self)?;
-
-        Ok(())
+        Ok(true)
     }
 
     pub(in crate) fn in_section(&self, directory: &DataDirectory) -> Option<&SectionHeader> {
+        self.rva_in_section(directory.virtual_address())
+    }
+
+    pub(in crate) fn rva_in_section(&self, address: u32) -> Option<&SectionHeader> {
         let number_of_sections = self.file_header().unwrap().number_of_sections();
-        let address = directory.virtual_address();
 
         for i in 0..number_of_sections {
             let section = self.section_headers().unwrap()[i as usize];
@@ -182,7 +247,13 @@ impl Executable {
         (rva - section.virtual_address() + section.pointer_to_raw_data()).try_into().unwrap()
     }
 
-    pub(in crate) fn is_x64(&self) -> bool {
-        self.file_header().unwrap().machine() == X64_MACHINE
+    // for RVAs that are not guaranteed to be in the same section as their directory
+    pub(in crate) fn rva_to_offset(&self, rva: u32) -> Option<usize> {
+        self.rva_in_section(rva).map(|section| self.rva_to_file_pointer(rva, section))
+    }
+
+    // pointer sized fields are 64-bit wide in PE32+
+    pub(in crate) fn is_pe32_plus(&self) -> bool {
+        self.optional_header().unwrap().is_pe32_plus()
     }
 }
@@ -1,9 +1,65 @@
 use scroll::{Pread, LE};
 
-use crate::constant::IMAGE_DIRECTORY_ENTRY_COM_DESCRIPTOR;
+use crate::constant::{IMAGE_DIRECTORY_ENTRY_COM_DESCRIPTOR, IMAGE_FILE_MACHINE_AMD64, IMAGE_FILE_MACHINE_ARM64, IMAGE_FILE_MACHINE_ARMNT, IMAGE_FILE_MACHINE_I386};
 use crate::directories::DataDirectory;
 use crate::Executable;
 
+// https://docs.microsoft.com/en-us/dotnet/framework/unmanaged-api/metadata/corheaderflags-enumeration
+pub const COMIMAGE_FLAGS_ILONLY: u32 = 0x0000_0001;
+pub const COMIMAGE_FLAGS_32BITREQUIRED: u32 = 0x0000_0002;
+pub const COMIMAGE_FLAGS_IL_LIBRARY: u32 = 0x0000_0004;
+pub const COMIMAGE_FLAGS_STRONGNAMESIGNED: u32 = 0x0000_0008;
+pub const COMIMAGE_FLAGS_NATIVE_ENTRYPOINT: u32 = 0x0000_0010;
+pub const COMIMAGE_FLAGS_TRACKDEBUGDATA: u32 = 0x0001_0000;
+pub const COMIMAGE_FLAGS_32BITPREFERRED: u32 = 0x0002_0000;
+
+pub const COMIMAGE_FLAGS: [(u32, &str); 7] = [
+    (COMIMAGE_FLAGS_ILONLY, "COMIMAGE_FLAGS_ILONLY"),
+    (COMIMAGE_FLAGS_32BITREQUIRED, "COMIMAGE_FLAGS_32BITREQUIRED"),
+    (COMIMAGE_FLAGS_IL_LIBRARY, "COMIMAGE_FLAGS_IL_LIBRARY"),
+    (COMIMAGE_FLAGS_STRONGNAMESIGNED, "COMIMAGE_FLAGS_STRONGNAMESIGNED"),
+    (COMIMAGE_FLAGS_NATIVE_ENTRYPOINT, "COMIMAGE_FLAGS_NATIVE_ENTRYPOINT"),
+    (COMIMAGE_FLAGS_TRACKDEBUGDATA, "COMIMAGE_FLAGS_TRACKDEBUGDATA"),
+    (COMIMAGE_FLAGS_32BITPREFERRED, "COMIMAGE_FLAGS_32BITPREFERRED"),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClrEntryPoint {
+    // the image has no entry point, e.g. a class library
+    None,
+    // MethodDef or File token of the managed entry point
+    Token(u32),
+    // RVA of the native entry point of a mixed-mode image
+    NativeRva(u32),
+}
+
+// the /platform option of the compiler that produced the image
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlatformTarget {
+    AnyCpu,
+    AnyCpuPrefer32Bit,
+    X86,
+    X64,
+    Arm,
+    Arm64,
+    // an unusual combination of the machine, the optional header magic and the flags
+    Unknown,
+}
+
+impl PlatformTarget {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlatformTarget::AnyCpu => "AnyCPU",
+            PlatformTarget::AnyCpuPrefer32Bit => "AnyCPU (32-bit preferred)",
+            PlatformTarget::X86 => "x86",
+            PlatformTarget::X64 => "x64",
+            PlatformTarget::Arm => "ARM",
+            PlatformTarget::Arm64 => "ARM64",
+            PlatformTarget::Unknown => "unknown",
+        }
+    }
+}
+
 // .NET CLR Header / This header may be change in the future.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, Pread)]
@@ -56,12 +112,31 @@ impl Cor20Header {
         &self.code_manager_table
     }
 
-    pub fn entry_point_rva(&self) -> u32 {
-        self.entry_point_rva
+    // the same field holds a token or an RVA depending on COMIMAGE_FLAGS_NATIVE_ENTRYPOINT
+    pub fn entry_point(&self) -> ClrEntryPoint {
+        if self.entry_point_rva == 0 {
+            ClrEntryPoint::None
+        } else if self.has_native_entry_point() {
+            ClrEntryPoint::NativeRva(self.entry_point_rva)
+        } else {
+            ClrEntryPoint::Token(self.entry_point_rva)
+        }
+    }
+
+    // Some only if the image has a native entry point
+    pub fn entry_point_rva(&self) -> Option<u32> {
+        match self.entry_point() {
+            ClrEntryPoint::NativeRva(rva) => Some(rva),
+            _ => None,
+        }
     }
 
-    pub fn entry_point_token(&self) -> u32 {
-        self.entry_point_rva
+    // Some only if the image has a managed entry point
+    pub fn entry_point_token(&self) -> Option<u32> {
+        match self.entry_point() {
+            ClrEntryPoint::Token(token) => Some(token),
+            _ => None,
+        }
     }
 
     pub fn export_address_table_jumps(&self) -> &DataDirectory {
@@ -72,6 +147,35 @@ impl Cor20Header {
         self.flags
     }
 
+    pub fn has_native_entry_point(&self) -> bool {
+        self.flags & COMIMAGE_FLAGS_NATIVE_ENTRYPOINT != 0
+    }
+
+    pub fn is_32bit_preferred(&self) -> bool {
+        self.flags & COMIMAGE_FLAGS_32BITPREFERRED != 0
+    }
+
+    pub fn is_32bit_required(&self) -> bool {
+        self.flags & COMIMAGE_FLAGS_32BITREQUIRED != 0
+    }
+
+    pub fn is_il_library(&self) -> bool {
+        self.flags & COMIMAGE_FLAGS_IL_LIBRARY != 0
+    }
+
+    pub fn is_il_only(&self) -> bool {
+        self.flags & COMIMAGE_FLAGS_ILONLY != 0
+    }
+
+    // native code is linked with the IL, e.g. C++/CLI without /clr:pure
+    pub fn is_mixed_mode(&self) -> bool {
+        !self.is_il_only()
+    }
+
+    pub fn is_strong_name_signed(&self) -> bool {
+        self.flags & COMIMAGE_FLAGS_STRONGNAMESIGNED != 0
+    }
+
     pub fn major_runtime_version(&self) -> u16 {
         self.major_runtime_version
     }
@@ -88,6 +192,22 @@ impl Cor20Header {
         self.minor_runtime_version
     }
 
+    // AnyCPU images are ILONLY PE32 images for I386, 32BITREQUIRED alone pins them to x86
+    pub fn platform_target(&self, machine: u16, is_pe32_plus: bool) -> PlatformTarget {
+        match (machine, is_pe32_plus) {
+            (IMAGE_FILE_MACHINE_I386, false) => match (self.is_il_only(), self.is_32bit_required(), self.is_32bit_preferred()) {
+                (true, false, false) => PlatformTarget::AnyCpu,
+                (true, true, true) => PlatformTarget::AnyCpuPrefer32Bit,
+                (_, _, false) => PlatformTarget::X86,
+                _ => PlatformTarget::Unknown,
+            },
+            (IMAGE_FILE_MACHINE_AMD64, true) => PlatformTarget::X64,
+            (IMAGE_FILE_MACHINE_ARMNT, false) => PlatformTarget::Arm,
+            (IMAGE_FILE_MACHINE_ARM64, true) => PlatformTarget::Arm64,
+            _ => PlatformTarget::Unknown,
+        }
+    }
+
     pub fn resources(&self) -> &DataDirectory {
         &self.resources
     }
@@ -96,6 +216,10 @@ impl Cor20Header {
         &self.strong_name_signature
     }
 
+    pub fn track_debug_data(&self) -> bool {
+        self.flags & COMIMAGE_FLAGS_TRACKDEBUGDATA != 0
+    }
+
     pub fn v_table_fixups(&self) -> &DataDirectory {
         &self.v_table_fixups
     }
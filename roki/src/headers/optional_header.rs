@@ -6,7 +6,7 @@ use crate::Executable;
 use scroll::{Pread, LE};
 
 const NUMBER_OF_DATA_DIRECTORIES: usize = 16;
-const X64_MACHINE: u16 = 0x8664;
+const IMAGE_NT_OPTIONAL_HDR64_MAGIC: u16 = 0x20B;
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, Pread)]
@@ -117,9 +117,13 @@ pub struct OptionalHeader {
 
 impl OptionalHeader {
     pub fn parse(executable: &mut Executable, mut offset: &mut usize) -> Result<OptionalHeader, failure::Error> {
-        let machine = executable.file_header().unwrap().machine();
+        // PE32+ is not limited to AMD64, e.g. ARM64 and IA64 also use it
+        let magic = executable.buffer().pread_with::<u16>(*offset, LE).map_err(|_| {
+            let msg = format!("Failed to read the magic of OPTIONAL_HEADER at {:#X}", offset);
+            failure::err_msg(msg)
+        })?;
 
-        if machine == X64_MACHINE {
+        if magic == IMAGE_NT_OPTIONAL_HDR64_MAGIC {
             let optional_header = executable.buffer().gread_with::<OptionalHeader64>(&mut offset, LE).map_err(|_| {
                 let msg = format!("Failed to read the OPTIONAL_HEADER_64 at {:#X}", offset);
                 return failure::err_msg(msg);
@@ -283,6 +287,10 @@ impl OptionalHeader {
         self.image_base
     }
 
+    pub fn is_pe32_plus(&self) -> bool {
+        self.magic == IMAGE_NT_OPTIONAL_HDR64_MAGIC
+    }
+
     pub fn loader_flags(&self) -> u32 {
         self.loader_flags
     }
@@ -1,8 +1,14 @@
+pub mod api_set;
+pub mod authenticode;
+pub mod clr;
 pub mod constant;
 pub mod containers;
 pub mod directories;
 pub mod executable;
 pub mod guid;
 pub mod headers;
+pub mod resources;
+
+mod strings;
 
 pub use executable::Executable;
@@ -0,0 +1,72 @@
+use scroll::{Pread, LE};
+
+use crate::containers::ResourceName;
+
+const ACCELERATOR_LAST_ENTRY: u16 = 0x80;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+pub struct Accelerator {
+    // https://docs.microsoft.com/en-us/windows/win32/menurc/acceltableentry
+    flags: u16,
+    ansi: u16, // a.k.a key
+    id: u16,
+    padding: u16,
+}
+
+impl Accelerator {
+    // FVIRTKEY, FNOINVERT, FSHIFT, FCONTROL and FALT
+    pub fn flags(&self) -> u16 {
+        self.flags
+    }
+
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+
+    // a virtual-key code when FVIRTKEY is set, otherwise an ANSI character
+    pub fn key(&self) -> u16 {
+        self.ansi
+    }
+}
+
+#[derive(Debug)]
+pub struct AcceleratorTable {
+    name: ResourceName,
+    language: u16,
+    entries: Vec<Accelerator>,
+}
+
+impl AcceleratorTable {
+    pub fn parse(data: &[u8], name: &ResourceName, language: u16) -> Result<Self, failure::Error> {
+        let mut offset = 0;
+        let mut entries: Vec<Accelerator> = Vec::new();
+
+        while offset < data.len() {
+            let entry = data.gread_with::<Accelerator>(&mut offset, LE).map_err(|_| {
+                let msg = format!("Failed to read the ACCELTABLEENTRY of {} at {:#X}", name, offset);
+                failure::err_msg(msg)
+            })?;
+
+            entries.push(entry);
+
+            if entry.flags & ACCELERATOR_LAST_ENTRY != 0 {
+                break;
+            }
+        }
+
+        Ok(AcceleratorTable { name: name.clone(), language, entries })
+    }
+
+    pub fn entries(&self) -> Vec<&Accelerator> {
+        self.entries.iter().collect()
+    }
+
+    pub fn language(&self) -> u16 {
+        self.language
+    }
+
+    pub fn name(&self) -> &ResourceName {
+        &self.name
+    }
+}
@@ -0,0 +1,341 @@
+use scroll::{Pread, LE};
+
+use super::align4;
+use crate::containers::ResourceName;
+use crate::strings::gread_utf16_null_terminated;
+
+const DS_SETFONT: u32 = 0x40;
+
+#[derive(Debug)]
+pub struct DialogFont {
+    point_size: u16,
+    weight: u16,
+    italic: bool,
+    charset: u8,
+    typeface: String,
+}
+
+impl DialogFont {
+    pub fn charset(&self) -> u8 {
+        self.charset
+    }
+
+    pub fn is_italic(&self) -> bool {
+        self.italic
+    }
+
+    pub fn point_size(&self) -> u16 {
+        self.point_size
+    }
+
+    pub fn typeface(&self) -> &str {
+        &self.typeface
+    }
+
+    pub fn weight(&self) -> u16 {
+        self.weight
+    }
+}
+
+#[derive(Debug)]
+pub struct DialogItem {
+    help_id: u32,
+    style: u32,
+    extended_style: u32,
+    x: i16,
+    y: i16,
+    cx: i16,
+    cy: i16,
+    id: u32,
+    class: Option<ResourceName>,
+    title: Option<ResourceName>,
+    creation_data: Vec<u8>,
+}
+
+impl DialogItem {
+    // predefined classes are stored as atoms
+    pub fn class(&self) -> Option<&ResourceName> {
+        self.class.as_ref()
+    }
+
+    pub fn class_name(&self) -> Option<String> {
+        match &self.class {
+            Some(ResourceName::Id(0x0080)) => Some("Button".to_owned()),
+            Some(ResourceName::Id(0x0081)) => Some("Edit".to_owned()),
+            Some(ResourceName::Id(0x0082)) => Some("Static".to_owned()),
+            Some(ResourceName::Id(0x0083)) => Some("ListBox".to_owned()),
+            Some(ResourceName::Id(0x0084)) => Some("ScrollBar".to_owned()),
+            Some(ResourceName::Id(0x0085)) => Some("ComboBox".to_owned()),
+            Some(name) => Some(name.to_string()),
+            None => None,
+        }
+    }
+
+    pub fn creation_data(&self) -> &[u8] {
+        &self.creation_data
+    }
+
+    pub fn cx(&self) -> i16 {
+        self.cx
+    }
+
+    pub fn cy(&self) -> i16 {
+        self.cy
+    }
+
+    pub fn extended_style(&self) -> u32 {
+        self.extended_style
+    }
+
+    pub fn help_id(&self) -> u32 {
+        self.help_id
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn style(&self) -> u32 {
+        self.style
+    }
+
+    // text or the resource id of an image
+    pub fn title(&self) -> Option<&ResourceName> {
+        self.title.as_ref()
+    }
+
+    pub fn x(&self) -> i16 {
+        self.x
+    }
+
+    pub fn y(&self) -> i16 {
+        self.y
+    }
+}
+
+#[derive(Debug)]
+pub struct Dialog {
+    name: ResourceName,
+    language: u16,
+    extended: bool,
+    help_id: u32,
+    style: u32,
+    extended_style: u32,
+    x: i16,
+    y: i16,
+    cx: i16,
+    cy: i16,
+    menu: Option<ResourceName>,
+    class: Option<ResourceName>,
+    title: String,
+    font: Option<DialogFont>,
+    items: Vec<DialogItem>,
+}
+
+impl Dialog {
+    // DLGTEMPLATE or DLGTEMPLATEEX
+    pub fn parse(data: &[u8], name: &ResourceName, language: u16) -> Result<Self, failure::Error> {
+        let mut offset = 0;
+        let read = |e: scroll::Error| {
+            let msg = format!("Failed to read the dialog template {}: {}", name, e);
+            failure::err_msg(msg)
+        };
+
+        let extended = data.pread_with::<u16>(0, LE).map_err(read)? == 1 && data.pread_with::<u16>(2, LE).map_err(read)? == 0xFFFF;
+
+        let (help_id, extended_style, style) = if extended {
+            offset += 4; // dlgVer, signature
+            let help_id = data.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+            let extended_style = data.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+            let style = data.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+            (help_id, extended_style, style)
+        } else {
+            let style = data.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+            let extended_style = data.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+            (0, extended_style, style)
+        };
+
+        let number_of_items = data.gread_with::<u16>(&mut offset, LE).map_err(read)?;
+        let x = data.gread_with::<i16>(&mut offset, LE).map_err(read)?;
+        let y = data.gread_with::<i16>(&mut offset, LE).map_err(read)?;
+        let cx = data.gread_with::<i16>(&mut offset, LE).map_err(read)?;
+        let cy = data.gread_with::<i16>(&mut offset, LE).map_err(read)?;
+
+        let menu = gread_sz_or_ord(data, &mut offset).map_err(read)?;
+        let class = gread_sz_or_ord(data, &mut offset).map_err(read)?;
+        let title = gread_utf16_null_terminated(data, &mut offset).map_err(read)?;
+
+        // DS_SHELLFONT includes DS_SETFONT
+        let font = if style & DS_SETFONT != 0 {
+            let point_size = data.gread_with::<u16>(&mut offset, LE).map_err(read)?;
+            let (weight, italic, charset) = if extended {
+                let weight = data.gread_with::<u16>(&mut offset, LE).map_err(read)?;
+                let italic = data.gread_with::<u8>(&mut offset, LE).map_err(read)?;
+                let charset = data.gread_with::<u8>(&mut offset, LE).map_err(read)?;
+                (weight, italic != 0, charset)
+            } else {
+                (0, false, 0)
+            };
+            let typeface = gread_utf16_null_terminated(data, &mut offset).map_err(read)?;
+
+            Some(DialogFont {
+                point_size,
+                weight,
+                italic,
+                charset,
+                typeface,
+            })
+        } else {
+            None
+        };
+
+        let mut items: Vec<DialogItem> = Vec::new();
+        for _ in 0..number_of_items {
+            offset = align4(offset);
+
+            let (help_id, extended_style, style) = if extended {
+                let help_id = data.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+                let extended_style = data.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+                let style = data.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+                (help_id, extended_style, style)
+            } else {
+                let style = data.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+                let extended_style = data.gread_with::<u32>(&mut offset, LE).map_err(read)?;
+                (0, extended_style, style)
+            };
+
+            let x = data.gread_with::<i16>(&mut offset, LE).map_err(read)?;
+            let y = data.gread_with::<i16>(&mut offset, LE).map_err(read)?;
+            let cx = data.gread_with::<i16>(&mut offset, LE).map_err(read)?;
+            let cy = data.gread_with::<i16>(&mut offset, LE).map_err(read)?;
+            let id = if extended {
+                data.gread_with::<u32>(&mut offset, LE).map_err(read)?
+            } else {
+                data.gread_with::<u16>(&mut offset, LE).map_err(read)? as u32
+            };
+
+            let class = gread_sz_or_ord(data, &mut offset).map_err(read)?;
+            let title = gread_sz_or_ord(data, &mut offset).map_err(read)?;
+
+            // the size includes itself in DLGTEMPLATE, but not in DLGTEMPLATEEX
+            let size = data.gread_with::<u16>(&mut offset, LE).map_err(read)? as usize;
+            let size = if !extended && size >= 2 { size - 2 } else { size };
+            let creation_data = match data.get(offset..offset + size) {
+                Some(creation_data) => creation_data.to_vec(),
+                None => {
+                    let msg = format!("Failed to read the creation data of dialog item {} in {}", id, name);
+                    return Err(failure::err_msg(msg));
+                }
+            };
+            offset += size;
+
+            items.push(DialogItem {
+                help_id,
+                style,
+                extended_style,
+                x,
+                y,
+                cx,
+                cy,
+                id,
+                class,
+                title,
+                creation_data,
+            });
+        }
+
+        Ok(Dialog {
+            name: name.clone(),
+            language,
+            extended,
+            help_id,
+            style,
+            extended_style,
+            x,
+            y,
+            cx,
+            cy,
+            menu,
+            class,
+            title,
+            font,
+            items,
+        })
+    }
+
+    pub fn class(&self) -> Option<&ResourceName> {
+        self.class.as_ref()
+    }
+
+    pub fn cx(&self) -> i16 {
+        self.cx
+    }
+
+    pub fn cy(&self) -> i16 {
+        self.cy
+    }
+
+    pub fn extended_style(&self) -> u32 {
+        self.extended_style
+    }
+
+    pub fn font(&self) -> Option<&DialogFont> {
+        self.font.as_ref()
+    }
+
+    pub fn help_id(&self) -> u32 {
+        self.help_id
+    }
+
+    // DLGTEMPLATEEX
+    pub fn is_extended(&self) -> bool {
+        self.extended
+    }
+
+    pub fn items(&self) -> Vec<&DialogItem> {
+        self.items.iter().collect()
+    }
+
+    pub fn language(&self) -> u16 {
+        self.language
+    }
+
+    pub fn menu(&self) -> Option<&ResourceName> {
+        self.menu.as_ref()
+    }
+
+    pub fn name(&self) -> &ResourceName {
+        &self.name
+    }
+
+    pub fn style(&self) -> u32 {
+        self.style
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn x(&self) -> i16 {
+        self.x
+    }
+
+    pub fn y(&self) -> i16 {
+        self.y
+    }
+}
+
+// sz_Or_Ord: 0x0000 is empty, 0xFFFF is followed by an ordinal, otherwise a NUL-terminated string
+fn gread_sz_or_ord(data: &[u8], offset: &mut usize) -> Result<Option<ResourceName>, scroll::Error> {
+    match data.pread_with::<u16>(*offset, LE)? {
+        0x0000 => {
+            *offset += 2;
+            Ok(None)
+        }
+        0xFFFF => {
+            *offset += 2;
+            Ok(Some(ResourceName::Id(data.gread_with::<u16>(offset, LE)?)))
+        }
+        _ => Ok(Some(ResourceName::Name(gread_utf16_null_terminated(data, offset)?))),
+    }
+}
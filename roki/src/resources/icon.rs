@@ -0,0 +1,231 @@
+use std::mem::size_of;
+
+use scroll::{Pread, LE};
+
+use crate::constant::{RT_CURSOR, RT_ICON};
+use crate::containers::{Resource, ResourceContainer, ResourceName};
+
+const ICONDIR_SIZE: usize = 6;
+const ICONDIRENTRY_SIZE: usize = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IconKind {
+    Icon,
+    Cursor,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pread)]
+struct GroupIconDirectory {
+    reserved: u16,
+    r#type: u16, // 1: icon, 2: cursor
+    count: u16,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+pub struct GroupIconDirectoryEntry {
+    // GRPICONDIRENTRY, for cursors the first 4 bytes are WORD width and WORD height
+    width: u8,
+    height: u8,
+    color_count: u8,
+    reserved: u8,
+    planes: u16,
+    bit_count: u16,
+    bytes_in_res: u32,
+    id: u16,
+}
+
+impl GroupIconDirectoryEntry {
+    pub fn bit_count(&self) -> u16 {
+        self.bit_count
+    }
+
+    pub fn bytes_in_res(&self) -> u32 {
+        self.bytes_in_res
+    }
+
+    // 0 means 256 pixels for icons
+    pub fn color_count(&self) -> u8 {
+        self.color_count
+    }
+
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+
+    pub fn planes(&self) -> u16 {
+        self.planes
+    }
+}
+
+#[derive(Debug)]
+pub struct IconEntry {
+    kind: IconKind,
+    entry: GroupIconDirectoryEntry,
+}
+
+impl IconEntry {
+    pub fn entry(&self) -> &GroupIconDirectoryEntry {
+        &self.entry
+    }
+
+    // in pixels
+    pub fn height(&self) -> u16 {
+        match self.kind {
+            IconKind::Icon if self.entry.height == 0 => 256,
+            IconKind::Icon => self.entry.height as u16,
+            IconKind::Cursor => u16::from_le_bytes([self.entry.color_count, self.entry.reserved]) / 2, // includes the AND mask
+        }
+    }
+
+    // in pixels
+    pub fn width(&self) -> u16 {
+        match self.kind {
+            IconKind::Icon if self.entry.width == 0 => 256,
+            IconKind::Icon => self.entry.width as u16,
+            IconKind::Cursor => u16::from_le_bytes([self.entry.width, self.entry.height]),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct IconGroup {
+    kind: IconKind,
+    name: ResourceName,
+    language: u16,
+    entries: Vec<IconEntry>,
+    file: Vec<u8>,
+}
+
+impl IconGroup {
+    // rebuild an .ico / .cur file from RT_GROUP_ICON / RT_GROUP_CURSOR and its members
+    pub fn parse(container: &ResourceContainer, group: &Resource, kind: IconKind) -> Result<Self, failure::Error> {
+        let data = group.data().data();
+        let language = group.language().id().unwrap_or(0);
+
+        let directory = data.pread_with::<GroupIconDirectory>(0, LE).map_err(|_| {
+            let msg = format!("Failed to read the GRPICONDIR of {}", group.name());
+            failure::err_msg(msg)
+        })?;
+
+        let member_type = match kind {
+            IconKind::Icon => RT_ICON,
+            IconKind::Cursor => RT_CURSOR,
+        };
+        let members = container.resources_by_type(member_type);
+
+        let mut entries: Vec<IconEntry> = Vec::new();
+        let mut images: Vec<&[u8]> = Vec::new();
+        let mut offset = size_of::<GroupIconDirectory>();
+
+        for _ in 0..directory.count {
+            let entry = data.gread_with::<GroupIconDirectoryEntry>(&mut offset, LE).map_err(|_| {
+                let msg = format!("Failed to read the GRPICONDIRENTRY of {} at {:#X}", group.name(), offset);
+                failure::err_msg(msg)
+            })?;
+
+            // prefer the member that has the same language as the group
+            let candidates: Vec<&Resource> = members.iter().filter(|w| w.name().id() == Some(entry.id)).collect();
+            let member = match candidates.iter().find(|w| w.language().id() == Some(language)).or_else(|| candidates.first()) {
+                Some(member) => member,
+                None => continue, // dangling entry, the loader ignores it too
+            };
+
+            entries.push(IconEntry { kind, entry });
+            images.push(member.data().data());
+        }
+
+        let file = IconGroup::build(kind, &entries, &images)?;
+
+        Ok(IconGroup {
+            kind,
+            name: group.name().clone(),
+            language,
+            entries,
+            file,
+        })
+    }
+
+    pub fn entries(&self) -> Vec<&IconEntry> {
+        self.entries.iter().collect()
+    }
+
+    pub fn extension(&self) -> &str {
+        match self.kind {
+            IconKind::Icon => "ico",
+            IconKind::Cursor => "cur",
+        }
+    }
+
+    // the contents of .ico / .cur file
+    pub fn file(&self) -> &[u8] {
+        &self.file
+    }
+
+    pub fn kind(&self) -> IconKind {
+        self.kind
+    }
+
+    pub fn language(&self) -> u16 {
+        self.language
+    }
+
+    pub fn name(&self) -> &ResourceName {
+        &self.name
+    }
+
+    fn build(kind: IconKind, entries: &[IconEntry], images: &[&[u8]]) -> Result<Vec<u8>, failure::Error> {
+        let mut header: Vec<u8> = Vec::new();
+        let mut body: Vec<u8> = Vec::new();
+
+        let r#type: u16 = match kind {
+            IconKind::Icon => 1,
+            IconKind::Cursor => 2,
+        };
+
+        header.extend_from_slice(&0u16.to_le_bytes());
+        header.extend_from_slice(&r#type.to_le_bytes());
+        header.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+
+        let mut offset = ICONDIR_SIZE + ICONDIRENTRY_SIZE * entries.len();
+
+        for (entry, image) in entries.iter().zip(images.iter()) {
+            let image = match kind {
+                IconKind::Icon => {
+                    header.push(entry.entry.width);
+                    header.push(entry.entry.height);
+                    header.push(entry.entry.color_count);
+                    header.push(0);
+                    header.extend_from_slice(&entry.entry.planes.to_le_bytes());
+                    header.extend_from_slice(&entry.entry.bit_count.to_le_bytes());
+
+                    *image
+                }
+                IconKind::Cursor => {
+                    // RT_CURSOR starts with the hotspot, that is stored in ICONDIRENTRY in .cur files
+                    let hotspot_x = image.pread_with::<u16>(0, LE).map_err(|_| failure::err_msg("Failed to read the hotspot of cursor"))?;
+                    let hotspot_y = image.pread_with::<u16>(2, LE).map_err(|_| failure::err_msg("Failed to read the hotspot of cursor"))?;
+
+                    header.push(entry.width() as u8); // 256 wraps to 0
+                    header.push(entry.height() as u8);
+                    header.push(0);
+                    header.push(0);
+                    header.extend_from_slice(&hotspot_x.to_le_bytes());
+                    header.extend_from_slice(&hotspot_y.to_le_bytes());
+
+                    &image[4..]
+                }
+            };
+
+            header.extend_from_slice(&(image.len() as u32).to_le_bytes());
+            header.extend_from_slice(&(offset as u32).to_le_bytes());
+
+            body.extend_from_slice(image);
+            offset += image.len();
+        }
+
+        header.extend_from_slice(&body);
+        Ok(header)
+    }
+}
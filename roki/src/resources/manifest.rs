@@ -0,0 +1,170 @@
+use roxmltree::{Document, Node};
+
+#[derive(Debug)]
+pub struct RequestedExecutionLevel {
+    level: String,
+    ui_access: Option<bool>,
+}
+
+impl RequestedExecutionLevel {
+    // asInvoker, highestAvailable or requireAdministrator
+    pub fn level(&self) -> &str {
+        &self.level
+    }
+
+    pub fn ui_access(&self) -> Option<bool> {
+        self.ui_access
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct AssemblyIdentity {
+    r#type: Option<String>,
+    name: Option<String>,
+    version: Option<String>,
+    processor_architecture: Option<String>,
+    public_key_token: Option<String>,
+    language: Option<String>,
+}
+
+impl AssemblyIdentity {
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn processor_architecture(&self) -> Option<&str> {
+        self.processor_architecture.as_deref()
+    }
+
+    pub fn public_key_token(&self) -> Option<&str> {
+        self.public_key_token.as_deref()
+    }
+
+    pub fn r#type(&self) -> Option<&str> {
+        self.r#type.as_deref()
+    }
+
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+}
+
+#[derive(Debug)]
+pub struct Manifest {
+    resource_id: u16,
+    language: u16,
+    xml: String,
+    requested_execution_level: Option<RequestedExecutionLevel>,
+    dependent_assemblies: Vec<AssemblyIdentity>,
+    dpi_aware: Option<String>,
+    dpi_awareness: Option<String>,
+    supported_os: Vec<String>,
+}
+
+impl Manifest {
+    pub fn parse(data: &[u8], resource_id: u16, language: u16) -> Result<Self, failure::Error> {
+        let xml = Manifest::decode(data);
+        let document = Document::parse(&xml).map_err(|e| {
+            let msg = format!("Failed to parse the application manifest #{}: {}", resource_id, e);
+            failure::err_msg(msg)
+        })?;
+
+        // the schema uses several namespaces (asm.v1, asm.v3, compatibility.v1, WindowsSettings...), so match local names only
+        let requested_execution_level = document.descendants().find(|w| w.has_tag_name("requestedExecutionLevel")).map(|w| RequestedExecutionLevel {
+            level: w.attribute("level").unwrap_or("").to_owned(),
+            ui_access: w.attribute("uiAccess").map(|w| w.eq_ignore_ascii_case("true")),
+        });
+
+        let dependent_assemblies = document
+            .descendants()
+            .filter(|w| w.has_tag_name("dependentAssembly"))
+            .flat_map(|w| w.children().filter(|w| w.has_tag_name("assemblyIdentity")))
+            .map(|w| Manifest::assembly_identity(&w))
+            .collect();
+
+        let dpi_aware = Manifest::text_of(&document, "dpiAware");
+        let dpi_awareness = Manifest::text_of(&document, "dpiAwareness");
+
+        let supported_os = document.descendants().filter(|w| w.has_tag_name("supportedOS")).filter_map(|w| w.attribute("Id")).map(|w| w.to_owned()).collect();
+
+        Ok(Manifest {
+            resource_id,
+            language,
+            requested_execution_level,
+            dependent_assemblies,
+            dpi_aware,
+            dpi_awareness,
+            supported_os,
+            xml,
+        })
+    }
+
+    pub fn dependent_assemblies(&self) -> Vec<&AssemblyIdentity> {
+        self.dependent_assemblies.iter().collect()
+    }
+
+    pub fn dpi_aware(&self) -> Option<&str> {
+        self.dpi_aware.as_deref()
+    }
+
+    pub fn dpi_awareness(&self) -> Option<&str> {
+        self.dpi_awareness.as_deref()
+    }
+
+    pub fn language(&self) -> u16 {
+        self.language
+    }
+
+    pub fn requested_execution_level(&self) -> Option<&RequestedExecutionLevel> {
+        self.requested_execution_level.as_ref()
+    }
+
+    // 1: CREATEPROCESS_MANIFEST_RESOURCE_ID, 2: ISOLATIONAWARE_MANIFEST_RESOURCE_ID, 3: ISOLATIONAWARE_NOSTATICIMPORT_MANIFEST_RESOURCE_ID
+    pub fn resource_id(&self) -> u16 {
+        self.resource_id
+    }
+
+    // GUIDs of supportedOS elements
+    pub fn supported_os(&self) -> Vec<&str> {
+        self.supported_os.iter().map(|w| w.as_str()).collect()
+    }
+
+    pub fn xml(&self) -> &str {
+        &self.xml
+    }
+
+    fn assembly_identity(node: &Node) -> AssemblyIdentity {
+        let attribute = |name: &str| node.attribute(name).map(|w| w.to_owned());
+
+        AssemblyIdentity {
+            r#type: attribute("type"),
+            name: attribute("name"),
+            version: attribute("version"),
+            processor_architecture: attribute("processorArchitecture"),
+            public_key_token: attribute("publicKeyToken"),
+            language: attribute("language"),
+        }
+    }
+
+    fn decode(data: &[u8]) -> String {
+        let xml = if data.starts_with(&[0xFF, 0xFE]) {
+            let units: Vec<u16> = data[2..].chunks_exact(2).map(|w| u16::from_le_bytes([w[0], w[1]])).collect();
+            String::from_utf16_lossy(&units)
+        } else if data.starts_with(&[0xEF, 0xBB, 0xBF]) {
+            String::from_utf8_lossy(&data[3..]).into_owned()
+        } else {
+            String::from_utf8_lossy(data).into_owned()
+        };
+
+        // resource compilers pad the data with NULs
+        xml.trim_end_matches(|w: char| w == '\0' || w.is_whitespace()).to_owned()
+    }
+
+    fn text_of(document: &Document, name: &str) -> Option<String> {
+        document.descendants().find(|w| w.has_tag_name(name)).and_then(|w| w.text()).map(|w| w.trim().to_owned())
+    }
+}
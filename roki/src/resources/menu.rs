@@ -0,0 +1,200 @@
+use scroll::{Pread, LE};
+
+use super::align4;
+use crate::containers::ResourceName;
+use crate::strings::gread_utf16_null_terminated;
+
+const MF_POPUP: u16 = 0x0010;
+const MF_END: u16 = 0x0080;
+const MFR_POPUP: u16 = 0x0001;
+const MFR_END: u16 = 0x0080;
+const MAX_MENU_DEPTH: u32 = 16;
+
+#[derive(Debug)]
+pub struct MenuItem {
+    flags: u32,
+    state: u32,
+    id: u32,
+    help_id: u32,
+    text: String,
+    popup: bool,
+    children: Vec<MenuItem>,
+}
+
+impl MenuItem {
+    pub fn children(&self) -> Vec<&MenuItem> {
+        self.children.iter().collect()
+    }
+
+    // MF_* options in MENU, MFT_* type in MENUEX
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    // MENUEX only
+    pub fn help_id(&self) -> u32 {
+        self.help_id
+    }
+
+    // always 0 for popups in MENU
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn is_popup(&self) -> bool {
+        self.popup
+    }
+
+    // MFS_* in MENUEX only
+    pub fn state(&self) -> u32 {
+        self.state
+    }
+
+    // empty for separators
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+#[derive(Debug)]
+pub struct Menu {
+    name: ResourceName,
+    language: u16,
+    extended: bool,
+    help_id: u32,
+    items: Vec<MenuItem>,
+}
+
+impl Menu {
+    // MENUHEADER (version 0) or MENUEX_TEMPLATE_HEADER (version 1)
+    pub fn parse(data: &[u8], name: &ResourceName, language: u16) -> Result<Self, failure::Error> {
+        let read = |e: scroll::Error| {
+            let msg = format!("Failed to read the menu template {}: {}", name, e);
+            failure::err_msg(msg)
+        };
+
+        let version = data.pread_with::<u16>(0, LE).map_err(read)?;
+        let header_size = data.pread_with::<u16>(2, LE).map_err(read)? as usize;
+
+        let (extended, help_id, items) = match version {
+            0 => {
+                let mut offset = 4 + header_size;
+                (false, 0, Menu::parse_items(data, &mut offset, 0).map_err(read)?)
+            }
+            1 => {
+                // wOffset is relative to the end of itself
+                let help_id = data.pread_with::<u32>(4, LE).map_err(read)?;
+                let mut offset = 4 + header_size;
+                (true, help_id, Menu::parse_items_ex(data, &mut offset, 0).map_err(read)?)
+            }
+            _ => {
+                let msg = format!("Unsupported menu template version {} in {}", version, name);
+                return Err(failure::err_msg(msg));
+            }
+        };
+
+        Ok(Menu {
+            name: name.clone(),
+            language,
+            extended,
+            help_id,
+            items,
+        })
+    }
+
+    pub fn help_id(&self) -> u32 {
+        self.help_id
+    }
+
+    // MENUEX
+    pub fn is_extended(&self) -> bool {
+        self.extended
+    }
+
+    pub fn items(&self) -> Vec<&MenuItem> {
+        self.items.iter().collect()
+    }
+
+    pub fn language(&self) -> u16 {
+        self.language
+    }
+
+    pub fn name(&self) -> &ResourceName {
+        &self.name
+    }
+
+    // NORMALMENUITEM and POPUPMENUITEM, each level ends with an item that has MF_END
+    fn parse_items(data: &[u8], offset: &mut usize, depth: u32) -> Result<Vec<MenuItem>, scroll::Error> {
+        if depth > MAX_MENU_DEPTH {
+            return Err(scroll::Error::Custom("menu is nested too deeply".to_owned()));
+        }
+
+        let mut items: Vec<MenuItem> = Vec::new();
+        while *offset < data.len() {
+            let flags = data.gread_with::<u16>(offset, LE)?;
+            let popup = flags & MF_POPUP != 0;
+            let id = if popup { 0 } else { data.gread_with::<u16>(offset, LE)? };
+            let text = gread_utf16_null_terminated(data, offset)?;
+            let children = if popup { Menu::parse_items(data, offset, depth + 1)? } else { Vec::new() };
+
+            items.push(MenuItem {
+                flags: (flags & !MF_END) as u32,
+                state: 0,
+                id: id as u32,
+                help_id: 0,
+                text,
+                popup,
+                children,
+            });
+
+            if flags & MF_END != 0 {
+                break;
+            }
+        }
+
+        Ok(items)
+    }
+
+    // MENUEX_TEMPLATE_ITEM, each level ends with an item that has 0x80 in bResInfo
+    fn parse_items_ex(data: &[u8], offset: &mut usize, depth: u32) -> Result<Vec<MenuItem>, scroll::Error> {
+        if depth > MAX_MENU_DEPTH {
+            return Err(scroll::Error::Custom("menu is nested too deeply".to_owned()));
+        }
+
+        let mut items: Vec<MenuItem> = Vec::new();
+        while *offset < data.len() {
+            *offset = align4(*offset);
+
+            let flags = data.gread_with::<u32>(offset, LE)?;
+            let state = data.gread_with::<u32>(offset, LE)?;
+            let id = data.gread_with::<u32>(offset, LE)?;
+            let res_info = data.gread_with::<u16>(offset, LE)?;
+            let text = gread_utf16_null_terminated(data, offset)?;
+
+            let popup = res_info & MFR_POPUP != 0;
+            let (help_id, children) = if popup {
+                *offset = align4(*offset);
+                let help_id = data.gread_with::<u32>(offset, LE)?;
+                (help_id, Menu::parse_items_ex(data, offset, depth + 1)?)
+            } else {
+                (0, Vec::new())
+            };
+
+            items.push(MenuItem {
+                flags,
+                state,
+                id,
+                help_id,
+                text,
+                popup,
+                children,
+            });
+
+            if res_info & MFR_END != 0 {
+                break;
+            }
+        }
+
+        Ok(items)
+    }
+}
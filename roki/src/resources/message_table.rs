@@ -0,0 +1,117 @@
+use scroll::{Pread, LE};
+
+const MESSAGE_RESOURCE_UNICODE: u16 = 0x0001;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+pub struct MessageResourceBlock {
+    // https://docs.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-message_resource_block
+    low_id: u32,
+    high_id: u32,
+    offset_to_entries: u32,
+}
+
+impl MessageResourceBlock {
+    pub fn high_id(&self) -> u32 {
+        self.high_id
+    }
+
+    pub fn low_id(&self) -> u32 {
+        self.low_id
+    }
+
+    pub fn offset_to_entries(&self) -> u32 {
+        self.offset_to_entries
+    }
+}
+
+#[derive(Debug)]
+pub struct MessageEntry {
+    id: u32,
+    unicode: bool,
+    text: String,
+}
+
+impl MessageEntry {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn is_unicode(&self) -> bool {
+        self.unicode
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+#[derive(Debug)]
+pub struct MessageTable {
+    language: u16,
+    blocks: Vec<MessageResourceBlock>,
+    entries: Vec<MessageEntry>,
+}
+
+impl MessageTable {
+    pub fn parse(data: &[u8], language: u16) -> Result<Self, failure::Error> {
+        let mut offset = 0;
+        let number_of_blocks = data.gread_with::<u32>(&mut offset, LE).map_err(|_| failure::err_msg("Failed to read the MESSAGE_RESOURCE_DATA"))?;
+
+        let mut blocks: Vec<MessageResourceBlock> = Vec::new();
+        for _ in 0..number_of_blocks {
+            let block = data.gread_with::<MessageResourceBlock>(&mut offset, LE).map_err(|_| {
+                let msg = format!("Failed to read the MESSAGE_RESOURCE_BLOCK at {:#X}", offset);
+                failure::err_msg(msg)
+            })?;
+
+            blocks.push(block);
+        }
+
+        let mut entries: Vec<MessageEntry> = Vec::new();
+        for block in blocks.iter() {
+            let mut offset = block.offset_to_entries as usize;
+
+            for id in block.low_id..=block.high_id {
+                let entry_offset = offset;
+                let length = data.gread_with::<u16>(&mut offset, LE)?;
+                let flags = data.gread_with::<u16>(&mut offset, LE)?;
+
+                // length includes the header
+                let text = match data.get(offset..entry_offset + length as usize) {
+                    Some(text) if length >= 4 => text,
+                    _ => {
+                        let msg = format!("Failed to read the MESSAGE_RESOURCE_ENTRY of message {:#010X}", id);
+                        return Err(failure::err_msg(msg));
+                    }
+                };
+
+                let unicode = flags & MESSAGE_RESOURCE_UNICODE != 0;
+                let text = if unicode {
+                    let units: Vec<u16> = text.chunks_exact(2).map(|w| u16::from_le_bytes([w[0], w[1]])).take_while(|w| *w != 0).collect();
+                    String::from_utf16_lossy(&units)
+                } else {
+                    // the code page is unknown here, read as Latin-1
+                    text.iter().take_while(|w| **w != 0).map(|w| *w as char).collect()
+                };
+
+                entries.push(MessageEntry { id, unicode, text });
+                offset = entry_offset + length as usize;
+            }
+        }
+
+        Ok(MessageTable { language, blocks, entries })
+    }
+
+    pub fn blocks(&self) -> Vec<&MessageResourceBlock> {
+        self.blocks.iter().collect()
+    }
+
+    pub fn entries(&self) -> Vec<&MessageEntry> {
+        self.entries.iter().collect()
+    }
+
+    pub fn language(&self) -> u16 {
+        self.language
+    }
+}
@@ -0,0 +1,22 @@
+mod accelerator;
+mod dialog;
+mod icon;
+mod manifest;
+mod menu;
+mod message_table;
+mod string_table;
+mod version_info;
+
+pub use accelerator::*;
+pub use dialog::*;
+pub use icon::*;
+pub use manifest::*;
+pub use menu::*;
+pub use message_table::*;
+pub use string_table::*;
+pub use version_info::*;
+
+// resource structures are aligned to DWORD from the beginning of the resource data
+fn align4(address: usize) -> usize {
+    (address + 3) & !3
+}
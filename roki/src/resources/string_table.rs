@@ -0,0 +1,79 @@
+use scroll::{Pread, LE};
+
+use crate::strings::gread_utf16;
+
+#[derive(Debug)]
+pub struct StringTableEntry {
+    id: u16,
+    value: String,
+}
+
+impl StringTableEntry {
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+// RT_STRING resources are blocks of 16 length-prefixed strings, the resource name is the block id
+#[derive(Debug)]
+pub struct StringTableBlock {
+    block_id: u16,
+    language: u16,
+    entries: Vec<StringTableEntry>,
+}
+
+impl StringTableBlock {
+    pub fn parse(data: &[u8], block_id: u16, language: u16) -> Result<Self, failure::Error> {
+        if block_id == 0 {
+            let msg = "Invalid RT_STRING block id 0";
+            return Err(failure::err_msg(msg));
+        }
+
+        let mut offset = 0;
+        let mut entries: Vec<StringTableEntry> = Vec::new();
+
+        for i in 0..16 {
+            // blocks may be truncated after the last non-empty string
+            if offset >= data.len() {
+                break;
+            }
+
+            let length = data.gread_with::<u16>(&mut offset, LE).map_err(|_| {
+                let msg = format!("Failed to read the length of string #{} in RT_STRING block {}", i, block_id);
+                failure::err_msg(msg)
+            })?;
+
+            if length == 0 {
+                continue;
+            }
+
+            let value = gread_utf16(data, &mut offset, length as usize).map_err(|_| {
+                let msg = format!("Failed to read string #{} in RT_STRING block {}", i, block_id);
+                failure::err_msg(msg)
+            })?;
+
+            entries.push(StringTableEntry {
+                id: (block_id - 1).wrapping_mul(16).wrapping_add(i),
+                value,
+            });
+        }
+
+        Ok(StringTableBlock { block_id, language, entries })
+    }
+
+    pub fn block_id(&self) -> u16 {
+        self.block_id
+    }
+
+    pub fn entries(&self) -> Vec<&StringTableEntry> {
+        self.entries.iter().collect()
+    }
+
+    pub fn language(&self) -> u16 {
+        self.language
+    }
+}
@@ -0,0 +1,289 @@
+use std::mem::size_of;
+
+use scroll::{Pread, LE};
+
+use super::align4;
+use crate::strings::gread_utf16_null_terminated;
+
+const VS_FIXEDFILEINFO_SIGNATURE: u32 = 0xFEEF04BD;
+const MAX_VERSION_BLOCK_DEPTH: u32 = 4;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pread)]
+pub struct FixedFileInfo {
+    // https://docs.microsoft.com/en-us/windows/win32/api/verrsrc/ns-verrsrc-vs_fixedfileinfo
+    signature: u32,
+    struc_version: u32,
+    file_version_ms: u32,
+    file_version_ls: u32,
+    product_version_ms: u32,
+    product_version_ls: u32,
+    file_flags_mask: u32,
+    file_flags: u32,
+    file_os: u32,
+    file_type: u32,
+    file_subtype: u32,
+    file_date_ms: u32,
+    file_date_ls: u32,
+}
+
+impl FixedFileInfo {
+    pub fn file_date(&self) -> u64 {
+        (self.file_date_ms as u64) << 32 | self.file_date_ls as u64
+    }
+
+    pub fn file_flags(&self) -> u32 {
+        self.file_flags
+    }
+
+    pub fn file_flags_mask(&self) -> u32 {
+        self.file_flags_mask
+    }
+
+    pub fn file_os(&self) -> u32 {
+        self.file_os
+    }
+
+    pub fn file_subtype(&self) -> u32 {
+        self.file_subtype
+    }
+
+    pub fn file_type(&self) -> u32 {
+        self.file_type
+    }
+
+    // major, minor, build, revision
+    pub fn file_version(&self) -> [u16; 4] {
+        FixedFileInfo::split_version(self.file_version_ms, self.file_version_ls)
+    }
+
+    pub fn file_version_ls(&self) -> u32 {
+        self.file_version_ls
+    }
+
+    pub fn file_version_ms(&self) -> u32 {
+        self.file_version_ms
+    }
+
+    // major, minor, build, revision
+    pub fn product_version(&self) -> [u16; 4] {
+        FixedFileInfo::split_version(self.product_version_ms, self.product_version_ls)
+    }
+
+    pub fn product_version_ls(&self) -> u32 {
+        self.product_version_ls
+    }
+
+    pub fn product_version_ms(&self) -> u32 {
+        self.product_version_ms
+    }
+
+    pub fn signature(&self) -> u32 {
+        self.signature
+    }
+
+    pub fn struc_version(&self) -> u32 {
+        self.struc_version
+    }
+
+    fn split_version(ms: u32, ls: u32) -> [u16; 4] {
+        [(ms >> 16) as u16, (ms & 0xFFFF) as u16, (ls >> 16) as u16, (ls & 0xFFFF) as u16]
+    }
+}
+
+#[derive(Debug)]
+pub struct StringTable {
+    key: String,
+    language: u16,
+    code_page: u16,
+    strings: Vec<(String, String)>,
+}
+
+impl StringTable {
+    pub fn code_page(&self) -> u16 {
+        self.code_page
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.strings.iter().find(|w| w.0 == key).map(|w| w.1.as_str())
+    }
+
+    // e.g. 040904B0
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn language(&self) -> u16 {
+        self.language
+    }
+
+    pub fn strings(&self) -> Vec<(&str, &str)> {
+        self.strings.iter().map(|w| (w.0.as_str(), w.1.as_str())).collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Translation {
+    language: u16,
+    code_page: u16,
+}
+
+impl Translation {
+    pub fn code_page(&self) -> u16 {
+        self.code_page
+    }
+
+    pub fn language(&self) -> u16 {
+        self.language
+    }
+}
+
+// VS_VERSIONINFO and its children share the same header layout
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pread)]
+struct VersionBlockHeader {
+    length: u16,
+    value_length: u16,
+    r#type: u16, // 1: text, 0: binary
+}
+
+struct VersionBlock<'a> {
+    key: String,
+    value: &'a [u8],
+    remains: &'a [u8], // value through the end of this block, for strings that lie about their length
+    children: Vec<VersionBlock<'a>>,
+}
+
+#[derive(Debug)]
+pub struct VersionInfo {
+    fixed_file_info: Option<FixedFileInfo>,
+    string_tables: Vec<StringTable>,
+    translations: Vec<Translation>,
+}
+
+impl VersionInfo {
+    pub fn parse(data: &[u8]) -> Result<Self, failure::Error> {
+        let (root, _) = VersionInfo::parse_block(data, 0, 0)?;
+        if root.key != "VS_VERSION_INFO" {
+            let msg = format!("Unexpected VS_VERSIONINFO key `{}`", root.key);
+            return Err(failure::err_msg(msg));
+        }
+
+        let fixed_file_info = match root.value.pread_with::<FixedFileInfo>(0, LE) {
+            Ok(info) if info.signature == VS_FIXEDFILEINFO_SIGNATURE => Some(info),
+            _ => None,
+        };
+
+        let mut string_tables: Vec<StringTable> = Vec::new();
+        let mut translations: Vec<Translation> = Vec::new();
+
+        for child in root.children.iter() {
+            match child.key.as_str() {
+                "StringFileInfo" => {
+                    for table in child.children.iter() {
+                        let (language, code_page) = match u32::from_str_radix(&table.key, 16) {
+                            Ok(value) => ((value >> 16) as u16, (value & 0xFFFF) as u16),
+                            Err(_) => (0, 0),
+                        };
+
+                        let strings = table.children.iter().map(|w| (w.key.to_owned(), VersionInfo::read_string(w.remains))).collect();
+                        string_tables.push(StringTable {
+                            key: table.key.to_owned(),
+                            language,
+                            code_page,
+                            strings,
+                        });
+                    }
+                }
+                "VarFileInfo" => {
+                    for var in child.children.iter().filter(|w| w.key == "Translation") {
+                        let mut offset = 0;
+                        while offset + 4 <= var.value.len() {
+                            let language = var.value.gread_with::<u16>(&mut offset, LE)?;
+                            let code_page = var.value.gread_with::<u16>(&mut offset, LE)?;
+                            translations.push(Translation { language, code_page });
+                        }
+                    }
+                }
+                _ => continue,
+            }
+        }
+
+        Ok(VersionInfo {
+            fixed_file_info,
+            string_tables,
+            translations,
+        })
+    }
+
+    pub fn fixed_file_info(&self) -> Option<&FixedFileInfo> {
+        self.fixed_file_info.as_ref()
+    }
+
+    pub fn string_tables(&self) -> Vec<&StringTable> {
+        self.string_tables.iter().collect()
+    }
+
+    pub fn translations(&self) -> Vec<&Translation> {
+        self.translations.iter().collect()
+    }
+
+    // lookup the first string table that has the key, e.g. CompanyName
+    pub fn value(&self, key: &str) -> Option<&str> {
+        self.string_tables.iter().find_map(|w| w.get(key))
+    }
+
+    fn parse_block(data: &[u8], offset: usize, depth: u32) -> Result<(VersionBlock<'_>, usize), failure::Error> {
+        if depth > MAX_VERSION_BLOCK_DEPTH {
+            let msg = format!("Version information block at {:#X} is nested too deeply", offset);
+            return Err(failure::err_msg(msg));
+        }
+
+        let header = data.pread_with::<VersionBlockHeader>(offset, LE).map_err(|_| {
+            let msg = format!("Failed to read the version information block header at {:#X}", offset);
+            failure::err_msg(msg)
+        })?;
+
+        let mut address = offset + size_of::<VersionBlockHeader>();
+        let end = offset + header.length as usize;
+        if end > data.len() || end < address {
+            let msg = format!("Version information block at {:#X} has an invalid length {:#X}", offset, header.length);
+            return Err(failure::err_msg(msg));
+        }
+
+        let key = gread_utf16_null_terminated(&data[..end], &mut address).map_err(|_| {
+            let msg = format!("Failed to read the key of version information block at {:#X}", offset);
+            failure::err_msg(msg)
+        })?;
+
+        // wValueLength is in WORDs for text values
+        let value_start = align4(address).min(end);
+        let value_size = if header.r#type == 1 { header.value_length as usize * 2 } else { header.value_length as usize };
+        let value_end = (value_start + value_size).min(end);
+
+        let mut children: Vec<VersionBlock> = Vec::new();
+        let mut address = align4(value_end);
+
+        // zero length means padding through the end of the parent
+        while address + size_of::<VersionBlockHeader>() <= end && data.pread_with::<u16>(address, LE)? != 0 {
+            let (child, length) = VersionInfo::parse_block(&data[..end], address, depth + 1)?;
+
+            children.push(child);
+            address = align4(address + length);
+        }
+
+        let block = VersionBlock {
+            key,
+            value: &data[value_start..value_end],
+            remains: &data[value_start..end],
+            children,
+        };
+
+        Ok((block, header.length as usize))
+    }
+
+    fn read_string(data: &[u8]) -> String {
+        let units: Vec<u16> = data.chunks_exact(2).map(|w| u16::from_le_bytes([w[0], w[1]])).take_while(|w| *w != 0).collect();
+        String::from_utf16_lossy(&units)
+    }
+}
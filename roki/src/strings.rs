@@ -0,0 +1,26 @@
+use scroll::{Pread, LE};
+
+// read `length` UTF-16LE code units, advancing the offset
+pub(in crate) fn gread_utf16(buffer: &[u8], offset: &mut usize, length: usize) -> Result<String, scroll::Error> {
+    let mut units: Vec<u16> = Vec::with_capacity(length);
+    for _ in 0..length {
+        units.push(buffer.gread_with::<u16>(offset, LE)?);
+    }
+
+    Ok(String::from_utf16_lossy(&units))
+}
+
+// read UTF-16LE code units until NUL, advancing the offset past the terminator
+pub(in crate) fn gread_utf16_null_terminated(buffer: &[u8], offset: &mut usize) -> Result<String, scroll::Error> {
+    let mut units: Vec<u16> = Vec::new();
+    loop {
+        let unit = buffer.gread_with::<u16>(offset, LE)?;
+        if unit == 0 {
+            break;
+        }
+
+        units.push(unit);
+    }
+
+    Ok(String::from_utf16_lossy(&units))
+}
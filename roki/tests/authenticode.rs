@@ -0,0 +1,123 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use roki::authenticode::{image_hash, verify_image_hash, ImageHashVerification};
+use roki::Executable;
+
+// cli-32.exe of conda (BSD-3-Clause), an Authenticode signed PE32 image
+fn fixture() -> Vec<u8> {
+    fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/authenticode.exe")).unwrap()
+}
+
+fn parse(name: &str, buffer: &[u8]) -> Executable {
+    let path: PathBuf = std::env::temp_dir().join(format!("roki-authenticode-{}-{}.exe", std::process::id(), name));
+    fs::write(&path, buffer).unwrap();
+
+    let mut executable = Executable::new(&path).unwrap();
+    executable.parse().unwrap();
+    fs::remove_file(&path).unwrap();
+
+    executable
+}
+
+fn verify(executable: &Executable) -> ImageHashVerification {
+    let signatures = executable.security_data().unwrap().authenticode_signatures().unwrap();
+    verify_image_hash(executable, &signatures[0]).unwrap()
+}
+
+// file offset of the first byte of the first section
+fn first_section_offset(executable: &Executable) -> usize {
+    executable.section_headers().unwrap()[0].pointer_to_raw_data() as usize
+}
+
+// patch a byte of the image, then rewrite SpcIndirectDataContent to carry the hash of the patched image
+fn redigest(name: &str, mut buffer: Vec<u8>) -> Vec<u8> {
+    let original = parse(&format!("{}-original", name), &buffer);
+    let offset = first_section_offset(&original);
+    buffer[offset] ^= 0xFF;
+
+    let signature = &original.security_data().unwrap().authenticode_signatures().unwrap()[0];
+    let computed = image_hash(&parse(&format!("{}-patched", name), &buffer), signature.digest_algorithm()).unwrap();
+    replace(&mut buffer, signature.digest(), &computed);
+
+    buffer
+}
+
+fn replace(buffer: &mut [u8], from: &[u8], to: &[u8]) {
+    let position = buffer.windows(from.len()).position(|w| w == from).unwrap();
+    buffer[position..position + to.len()].copy_from_slice(to);
+}
+
+#[test]
+fn valid_image() {
+    let executable = parse("valid", &fixture());
+    let verification = verify(&executable);
+
+    assert!(verification.is_image_hash_match());
+    assert!(verification.is_message_digest_match());
+    assert!(verification.is_signature_valid());
+    assert!(verification.is_match());
+}
+
+#[test]
+fn patched_image() {
+    let mut buffer = fixture();
+    let offset = first_section_offset(&parse("patched-original", &buffer));
+    buffer[offset] ^= 0xFF;
+
+    let executable = parse("patched", &buffer);
+    let verification = verify(&executable);
+
+    assert!(!verification.is_image_hash_match());
+    assert!(verification.is_message_digest_match());
+    assert!(verification.is_signature_valid());
+    assert!(!verification.is_match());
+}
+
+#[test]
+fn truncated_headers() {
+    let mut buffer = fixture();
+    let original = parse("truncated-original", &buffer);
+
+    // SizeOfHeaders ends right after the CheckSum, before the certificate table entry
+    let optional_header_offset = original.dos_header().unwrap().addr_of_nt_header() as usize + 4 + 20;
+    let size_of_headers = optional_header_offset + 68;
+    buffer[optional_header_offset + 60..optional_header_offset + 64].copy_from_slice(&(size_of_headers as u32).to_le_bytes());
+
+    let executable = parse("truncated", &buffer);
+    let algorithm = executable.security_data().unwrap().authenticode_signatures().unwrap()[0].digest_algorithm().clone();
+
+    let error = image_hash(&executable, &algorithm).unwrap_err();
+    assert!(error.to_string().starts_with("The headers of the image end at"));
+}
+
+#[test]
+fn patched_indirect_data_digest() {
+    let buffer = redigest("redigested", fixture());
+
+    let executable = parse("redigested", &buffer);
+    let verification = verify(&executable);
+
+    assert!(verification.is_image_hash_match());
+    assert!(!verification.is_message_digest_match());
+    assert!(verification.is_signature_valid());
+    assert!(!verification.is_match());
+}
+
+#[test]
+fn patched_message_digest() {
+    let mut buffer = redigest("resigned", fixture());
+
+    // messageDigest is rewritten to cover the new SpcIndirectDataContent, only the encryptedDigest no longer matches
+    let verification = verify(&parse("resigned-redigested", &buffer));
+    replace(&mut buffer, verification.message_digest().unwrap(), verification.indirect_data_digest().unwrap());
+
+    let executable = parse("resigned", &buffer);
+    let verification = verify(&executable);
+
+    assert!(verification.is_image_hash_match());
+    assert!(verification.is_message_digest_match());
+    assert!(!verification.is_signature_valid());
+    assert!(verification.signature_error().is_none());
+    assert!(!verification.is_match());
+}
@@ -0,0 +1,53 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use roki::clr::verify_strong_name;
+use roki::Executable;
+
+// synthetic assemblies signed with a throwaway 1024-bit key, one of them carries AssemblySignatureKeyAttribute
+fn fixture(name: &str) -> Vec<u8> {
+    fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)).unwrap()
+}
+
+fn parse(name: &str, buffer: &[u8]) -> Executable {
+    let path: PathBuf = std::env::temp_dir().join(format!("roki-strong-name-{}-{}.exe", std::process::id(), name));
+    fs::write(&path, buffer).unwrap();
+
+    let mut executable = Executable::new(&path).unwrap();
+    executable.parse().unwrap();
+    fs::remove_file(&path).unwrap();
+
+    executable
+}
+
+#[test]
+fn valid_signature() {
+    let executable = parse("valid", &fixture("strong_name.exe"));
+    let signature = executable.com_descriptor_data().unwrap().strong_name_signature().unwrap().unwrap();
+
+    assert!(!signature.has_signature_key());
+    assert!(verify_strong_name(&executable, signature).unwrap().is_valid());
+}
+
+#[test]
+fn modified_image() {
+    let mut buffer = fixture("strong_name.exe");
+
+    // a byte of the IL, outside of the signature blob
+    buffer[0x249] ^= 0xFF;
+
+    let executable = parse("modified", &buffer);
+    let signature = executable.com_descriptor_data().unwrap().strong_name_signature().unwrap().unwrap();
+
+    assert!(!verify_strong_name(&executable, signature).unwrap().is_valid());
+}
+
+#[test]
+fn enhanced_strong_name() {
+    let executable = parse("signature-key", &fixture("strong_name_signature_key.exe"));
+    let signature = executable.com_descriptor_data().unwrap().strong_name_signature().unwrap().unwrap();
+
+    // the identity key countersigned by AssemblySignatureKeyAttribute is not verified
+    assert!(signature.has_signature_key());
+    assert!(verify_strong_name(&executable, signature).is_err());
+}